@@ -0,0 +1,216 @@
+// src/component/base64/binary.rs
+//
+// 解码结果不是文本时的处理：按魔数识别常见文件类型、生成十六进制转储、保存原始字节；
+// 以及文件编码 / data URI 用到的 MIME 类型与扩展名对照。
+//
+// 十六进制转储与 `hexdump -C` 相同：偏移、16 个字节（8 个一组）、ASCII 列（不可见字符为 `.`）。
+// 太大的结果只转储开头 `DUMP_LIMIT` 字节，保存文件不受此限制。
+
+use chrono::Local;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::PathBuf;
+
+/// 十六进制转储最多显示的字节数
+pub const DUMP_LIMIT: usize = 64 * 1024;
+
+/// 文件编码时每次读取的字节数（3 的倍数，各块编码结果可直接拼接）
+pub const READ_CHUNK: usize = 768 * 1024;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FileKind {
+    Png,
+    Jpeg,
+    Gzip,
+    Zip,
+    Pdf,
+    /// ASN.1 DER（证书、密钥等）
+    Der,
+}
+
+impl FileKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileKind::Png => "PNG 图片",
+            FileKind::Jpeg => "JPEG 图片",
+            FileKind::Gzip => "gzip 压缩数据",
+            FileKind::Zip => "ZIP 压缩包",
+            FileKind::Pdf => "PDF 文档",
+            FileKind::Der => "DER 编码（证书 / 密钥）",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            FileKind::Png => "image/png",
+            FileKind::Jpeg => "image/jpeg",
+            FileKind::Gzip => "application/gzip",
+            FileKind::Zip => "application/zip",
+            FileKind::Pdf => "application/pdf",
+            FileKind::Der => "application/pkix-cert",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileKind::Png => "png",
+            FileKind::Jpeg => "jpg",
+            FileKind::Gzip => "gz",
+            FileKind::Zip => "zip",
+            FileKind::Pdf => "pdf",
+            FileKind::Der => "der",
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        matches!(self, FileKind::Png | FileKind::Jpeg)
+    }
+}
+
+/// 扩展名 <-> MIME 对照（魔数识别不出时按文件名猜；还原 data URI 时按 MIME 取扩展名）
+const MIME_TYPES: [(&str, &str); 20] = [
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/x-icon"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("der", "application/pkix-cert"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("js", "text/javascript"),
+    ("html", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("wasm", "application/wasm"),
+];
+
+/// 文件内容的 MIME：先看魔数，再看扩展名，都不认识时为 application/octet-stream
+pub fn mime_for(name: &str, bytes: &[u8]) -> &'static str {
+    if let Some(k) = detect(bytes) {
+        return k.mime();
+    }
+    let ext = name.rsplit_once('.').map(|(_, e)| e.to_ascii_lowercase());
+    MIME_TYPES
+        .iter()
+        .find(|(e, _)| Some(*e) == ext.as_deref())
+        .map_or("application/octet-stream", |(_, m)| m)
+}
+
+/// MIME 对应的扩展名（忽略 `;charset=` 等参数）
+pub fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    let essence = mime.split(';').next().unwrap_or("").trim();
+    MIME_TYPES
+        .iter()
+        .find(|(_, m)| m.eq_ignore_ascii_case(essence))
+        .map(|(e, _)| *e)
+}
+
+/// 按开头的魔数识别文件类型
+pub fn detect(bytes: &[u8]) -> Option<FileKind> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(FileKind::Png)
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some(FileKind::Jpeg)
+    } else if bytes.starts_with(&[0x1f, 0x8b]) {
+        Some(FileKind::Gzip)
+    } else if bytes.starts_with(b"PK\x03\x04")
+        || bytes.starts_with(b"PK\x05\x06")
+        || bytes.starts_with(b"PK\x07\x08")
+    {
+        Some(FileKind::Zip)
+    } else if bytes.starts_with(b"%PDF-") {
+        Some(FileKind::Pdf)
+    } else if is_der_sequence(bytes) {
+        Some(FileKind::Der)
+    } else {
+        None
+    }
+}
+
+/// 顶层是一个 SEQUENCE（0x30）且声明的长度正好覆盖全部字节
+fn is_der_sequence(bytes: &[u8]) -> bool {
+    let (Some(&0x30), Some(&first)) = (bytes.first(), bytes.get(1)) else {
+        return false;
+    };
+    let (len, header) = if first < 0x80 {
+        (first as usize, 2)
+    } else {
+        let n = (first & 0x7f) as usize;
+        // 长格式：1~4 个长度字节，且不能有多余的前导 0
+        if n == 0 || n > 4 || bytes.len() < 2 + n || bytes[2] == 0 {
+            return false;
+        }
+        let len = bytes[2..2 + n]
+            .iter()
+            .fold(0usize, |acc, b| acc << 8 | *b as usize);
+        (len, 2 + n)
+    };
+    header + len == bytes.len()
+}
+
+/// `hexdump -C` 风格的转储（最多 `DUMP_LIMIT` 字节）
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let shown = &bytes[..bytes.len().min(DUMP_LIMIT)];
+    let mut out = String::with_capacity(shown.len() / 16 * 79 + 64);
+    for (row, chunk) in shown.chunks(16).enumerate() {
+        out.push_str(&format!("{:08x}  ", row * 16));
+        for i in 0..16 {
+            match chunk.get(i) {
+                Some(b) => out.push_str(&format!("{:02x} ", b)),
+                None => out.push_str("   "),
+            }
+            if i == 7 {
+                out.push(' ');
+            }
+        }
+        out.push_str(" |");
+        for &b in chunk {
+            out.push(if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            });
+        }
+        out.push_str("|\n");
+    }
+    if bytes.len() > shown.len() {
+        out.push_str(&format!(
+            "…（仅显示前 {} 字节，共 {} 字节）\n",
+            shown.len(),
+            bytes.len()
+        ));
+    } else {
+        out.push_str(&format!("{:08x}\n", bytes.len()));
+    }
+    out
+}
+
+/// 读取下一块（最多 `READ_CHUNK` 字节）；读到文件末尾时返回空
+pub fn read_chunk(file: &mut File) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(READ_CHUNK);
+    file.take(READ_CHUNK as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// 把字节保存到下载目录（没有时为主目录），文件名带时间戳；返回保存路径
+pub fn save_to_downloads(bytes: &[u8], ext: &str) -> Result<PathBuf, String> {
+    let dir = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .ok_or("找不到下载目录")?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let mut path = dir.join(format!("base64-{}.{}", stamp, ext));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("base64-{}-{}.{}", stamp, n, ext));
+        n += 1;
+    }
+    fs::write(&path, bytes).map_err(|e| format!("保存失败 {}: {}", path.display(), e))?;
+    Ok(path)
+}
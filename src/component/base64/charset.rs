@@ -0,0 +1,172 @@
+// src/component/base64/charset.rs
+//
+// 文本一侧的字符编码：编码前把输入文本转成字节，解码后把字节还原成文本。
+//
+// - UTF-8 / UTF-16LE / Latin-1 直接按定义转换（encoding_rs 不能编码 UTF-16，
+//   且它的 ISO-8859-1 按 WHATWG 规范实为 windows-1252）。
+// - GBK / Shift_JIS 交给 encoding_rs，不做替换，遇到无法转换的内容即报错。
+// 出错时报告位置：编码报字符序号，解码报字节偏移。
+
+use encoding_rs::{DecoderResult, EncoderResult, Encoding, GBK, SHIFT_JIS};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Charset {
+    #[default]
+    Utf8,
+    Gbk,
+    ShiftJis,
+    Latin1,
+    Utf16Le,
+}
+
+impl Charset {
+    pub const ALL: [Charset; 5] = [
+        Charset::Utf8,
+        Charset::Gbk,
+        Charset::ShiftJis,
+        Charset::Latin1,
+        Charset::Utf16Le,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Charset::Utf8 => "UTF-8",
+            Charset::Gbk => "GBK",
+            Charset::ShiftJis => "Shift_JIS",
+            Charset::Latin1 => "ISO-8859-1",
+            Charset::Utf16Le => "UTF-16LE",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Charset> {
+        let label = label.trim().trim_matches('"');
+        match label.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Charset::Utf8),
+            "gbk" | "gb2312" | "cp936" => Some(Charset::Gbk),
+            "shift_jis" | "shift-jis" | "sjis" => Some(Charset::ShiftJis),
+            "iso-8859-1" | "latin1" | "latin-1" => Some(Charset::Latin1),
+            "utf-16le" => Some(Charset::Utf16Le),
+            _ => None,
+        }
+    }
+
+    /// MIME 参数里的 `charset=`（如 data URI 的 `text/plain;charset=gbk`）
+    pub fn from_mime(mime: &str) -> Option<Charset> {
+        mime.split(';').skip(1).find_map(|p| {
+            let (k, v) = p.split_once('=')?;
+            k.trim()
+                .eq_ignore_ascii_case("charset")
+                .then(|| Charset::from_label(v))?
+        })
+    }
+
+    /// 文本 -> 字节
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Charset::Utf8 => Ok(text.as_bytes().to_vec()),
+            Charset::Utf16Le => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            Charset::Latin1 => text
+                .chars()
+                .enumerate()
+                .map(|(i, c)| u8::try_from(c).map_err(|_| self.unmappable(i, c)))
+                .collect(),
+            Charset::Gbk => self.encode_with(GBK, text),
+            Charset::ShiftJis => self.encode_with(SHIFT_JIS, text),
+        }
+    }
+
+    /// 字节 -> 文本
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        match self {
+            Charset::Utf8 => std::str::from_utf8(bytes)
+                .map(str::to_string)
+                .map_err(|e| self.malformed(bytes, e.valid_up_to())),
+            Charset::Latin1 => Ok(bytes.iter().map(|&b| b as char).collect()),
+            Charset::Utf16Le => {
+                if !bytes.len().is_multiple_of(2) {
+                    return Err(format!(
+                        "偏移 {} 处只剩 1 个字节，不是完整的 UTF-16LE 码元",
+                        bytes.len() - 1
+                    ));
+                }
+                let units = bytes.chunks(2).map(|p| u16::from_le_bytes([p[0], p[1]]));
+                let mut out = String::with_capacity(bytes.len() / 2);
+                let mut offset = 0;
+                for c in char::decode_utf16(units) {
+                    match c {
+                        Ok(c) => {
+                            out.push(c);
+                            offset += c.len_utf16() * 2;
+                        }
+                        Err(_) => return Err(self.malformed(bytes, offset)),
+                    }
+                }
+                Ok(out)
+            }
+            Charset::Gbk => self.decode_with(GBK, bytes),
+            Charset::ShiftJis => self.decode_with(SHIFT_JIS, bytes),
+        }
+    }
+
+    fn encode_with(&self, encoding: &'static Encoding, text: &str) -> Result<Vec<u8>, String> {
+        let mut encoder = encoding.new_encoder();
+        let cap = encoder
+            .max_buffer_length_from_utf8_without_replacement(text.len())
+            .unwrap_or(text.len() * 2);
+        let mut out = vec![0; cap];
+        let (result, read, written) =
+            encoder.encode_from_utf8_without_replacement(text, &mut out, true);
+        match result {
+            EncoderResult::InputEmpty => {
+                out.truncate(written);
+                Ok(out)
+            }
+            EncoderResult::Unmappable(c) => {
+                let index = text[..read].chars().count() - 1;
+                Err(self.unmappable(index, c))
+            }
+            EncoderResult::OutputFull => Err(format!("{} 编码缓冲区不足", self.label())),
+        }
+    }
+
+    fn decode_with(&self, encoding: &'static Encoding, bytes: &[u8]) -> Result<String, String> {
+        let mut decoder = encoding.new_decoder_without_bom_handling();
+        let cap = decoder
+            .max_utf8_buffer_length_without_replacement(bytes.len())
+            .unwrap_or(bytes.len() * 3);
+        let mut out = String::with_capacity(cap);
+        let (result, read) = decoder.decode_to_string_without_replacement(bytes, &mut out, true);
+        match result {
+            DecoderResult::InputEmpty => Ok(out),
+            // 出错的字节序列在已读部分的末尾，其后还多读了 `extra` 个字节
+            DecoderResult::Malformed(bad, extra) => {
+                Err(self.malformed(bytes, read - bad as usize - extra as usize))
+            }
+            DecoderResult::OutputFull => Err(format!("{} 解码缓冲区不足", self.label())),
+        }
+    }
+
+    fn unmappable(&self, index: usize, c: char) -> String {
+        format!(
+            "第 {} 个字符 “{}”（U+{:04X}）无法用 {} 表示",
+            index + 1,
+            c,
+            c as u32,
+            self.label()
+        )
+    }
+
+    fn malformed(&self, bytes: &[u8], offset: usize) -> String {
+        match bytes.get(offset) {
+            Some(b) => format!(
+                "偏移 {}（0x{:x}）处的字节 0x{:02x} 不是有效的 {}",
+                offset,
+                offset,
+                b,
+                self.label()
+            ),
+            None => format!("末尾的 {} 字节序列不完整", self.label()),
+        }
+    }
+}
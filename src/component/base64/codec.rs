@@ -0,0 +1,427 @@
+// src/component/base64/codec.rs
+//
+// Base64 变体：字母表（标准 / URL 安全）、填充（必须 / 可选 / 无）、按列换行（PEM 64 / MIME 76），
+// 以及 `data:<mime>;base64,…` 形式的 data URI（固定为标准字母表、带填充、不换行）。
+//
+// 编码按块进行（`StreamEncoder`），大文件可以边读边编码。
+// 解码有两种方式：
+// - 按选项：字母表与填充须与选项一致；开启换行时允许输入中有换行。
+// - 自动识别：去掉所有空白后按出现的字符判断字母表（`-_` 为 URL 安全、`+/` 为标准），
+//   填充有无都接受，并报告识别出的变体（JWT 段是 URL 安全无填充，邮件正文是标准 76 列换行）。
+// 输入是 data URI 时总是按 data URI 解析，并带回其中的 MIME 类型。
+
+use ::base64::alphabet::{self, Alphabet as B64Alphabet};
+use ::base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use ::base64::Engine;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Alphabet {
+    Standard,
+    UrlSafe,
+}
+
+impl Alphabet {
+    pub const ALL: [Alphabet; 2] = [Alphabet::Standard, Alphabet::UrlSafe];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Alphabet::Standard => "标准",
+            Alphabet::UrlSafe => "URL 安全",
+        }
+    }
+
+    fn table(&self) -> &'static B64Alphabet {
+        match self {
+            Alphabet::Standard => &alphabet::STANDARD,
+            Alphabet::UrlSafe => &alphabet::URL_SAFE,
+        }
+    }
+}
+
+/// 编码时“必须”与“可选”都写填充；区别只在解码时是否要求输入带填充
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Padding {
+    Required,
+    Optional,
+    None,
+}
+
+impl Padding {
+    pub const ALL: [Padding; 3] = [Padding::Required, Padding::Optional, Padding::None];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Padding::Required => "必须",
+            Padding::Optional => "可选",
+            Padding::None => "无",
+        }
+    }
+
+    fn decode_mode(&self) -> DecodePaddingMode {
+        match self {
+            Padding::Required => DecodePaddingMode::RequireCanonical,
+            Padding::Optional => DecodePaddingMode::Indifferent,
+            Padding::None => DecodePaddingMode::RequireNone,
+        }
+    }
+}
+
+/// 换行宽度的可选值（0 = 不换行；64 = PEM；76 = MIME）
+pub const WRAP_WIDTHS: [usize; 3] = [0, 64, 76];
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Options {
+    pub alphabet: Alphabet,
+    pub padding: Padding,
+    /// 编码结果每行的字符数；0 = 不换行
+    pub wrap: usize,
+    /// 解码时忽略空白并自动识别变体（忽略上面三项）
+    pub auto_detect: bool,
+    /// 编码输出为 data URI（忽略字母表 / 填充 / 换行）
+    pub data_uri: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            alphabet: Alphabet::Standard,
+            padding: Padding::Required,
+            wrap: 0,
+            auto_detect: true,
+            data_uri: false,
+        }
+    }
+}
+
+fn engine(alphabet: Alphabet, padding: Padding) -> GeneralPurpose {
+    let config = GeneralPurposeConfig::new()
+        .with_encode_padding(padding != Padding::None)
+        .with_decode_padding_mode(padding.decode_mode());
+    GeneralPurpose::new(alphabet.table(), config)
+}
+
+pub fn encode(bytes: &[u8], opts: &Options, mime: &str) -> String {
+    let mut encoder = StreamEncoder::new(opts);
+    encoder.push(bytes);
+    encoder.finish(mime)
+}
+
+/// 分块编码：凑够 3 字节的整数倍再编码，各块结果直接拼接，与一次性编码完全相同
+pub struct StreamEncoder {
+    engine: GeneralPurpose,
+    opts: Options,
+    pending: Vec<u8>,
+    out: String,
+}
+
+impl StreamEncoder {
+    pub fn new(opts: &Options) -> Self {
+        let engine = if opts.data_uri {
+            engine(Alphabet::Standard, Padding::Required)
+        } else {
+            engine(opts.alphabet, opts.padding)
+        };
+        Self {
+            engine,
+            opts: *opts,
+            pending: Vec::new(),
+            out: String::new(),
+        }
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        let whole = self.pending.len() / 3 * 3;
+        self.engine
+            .encode_string(&self.pending[..whole], &mut self.out);
+        self.pending.drain(..whole);
+    }
+
+    /// 编码余下的字节；`mime` 只用于 data URI
+    pub fn finish(mut self, mime: &str) -> String {
+        self.engine.encode_string(&self.pending, &mut self.out);
+        if self.opts.data_uri {
+            return format!("data:{};base64,{}", mime, self.out);
+        }
+        if self.opts.wrap == 0 {
+            return self.out;
+        }
+        // Base64 文本全是 ASCII，按字节切行即可
+        self.out
+            .as_bytes()
+            .chunks(self.opts.wrap)
+            .map(|line| std::str::from_utf8(line).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// `data:` URI（标准字母表、带填充、不换行）
+pub fn data_uri(mime: &str, bytes: &[u8]) -> String {
+    format!(
+        "data:{};base64,{}",
+        mime,
+        engine(Alphabet::Standard, Padding::Required).encode(bytes)
+    )
+}
+
+/// 自动识别出的变体
+#[derive(Clone, Copy, PartialEq)]
+pub struct Detected {
+    pub alphabet: Alphabet,
+    /// 是否带填充；None = 长度正好是 4 的倍数，看不出是否省略了填充
+    pub padded: Option<bool>,
+    /// 输入中含换行 / 空白（如 MIME 按列换行）
+    pub wrapped: bool,
+}
+
+impl Detected {
+    pub fn describe(&self) -> String {
+        let mut s = self.alphabet.label().to_string();
+        match self.padded {
+            Some(true) => s.push_str(" · 有填充"),
+            Some(false) => s.push_str(" · 无填充"),
+            None => {}
+        }
+        if self.wrapped {
+            s.push_str(" · 含换行");
+        }
+        s
+    }
+}
+
+/// 解码结果
+pub struct Decoded {
+    pub bytes: Vec<u8>,
+    /// 自动识别出的变体（按选项解码时为 None）
+    pub detected: Option<Detected>,
+    /// 输入是 data URI 时其中声明的 MIME 类型
+    pub mime: Option<String>,
+}
+
+pub fn decode(text: &str, opts: &Options) -> Result<Decoded, String> {
+    if let Some(rest) = strip_prefix_ignore_case(text.trim(), "data:") {
+        return decode_data_uri(rest);
+    }
+    if opts.auto_detect {
+        let (bytes, detected) = decode_auto(text)?;
+        return Ok(Decoded {
+            bytes,
+            detected: Some(detected),
+            mime: None,
+        });
+    }
+    let trimmed = text.trim();
+    let compact: String;
+    let input = if opts.wrap > 0 {
+        compact = trimmed.split_whitespace().collect();
+        compact.as_str()
+    } else {
+        trimmed
+    };
+    let bytes = engine(opts.alphabet, opts.padding)
+        .decode(input)
+        .map_err(|e| e.to_string())?;
+    Ok(Decoded {
+        bytes,
+        detected: None,
+        mime: None,
+    })
+}
+
+/// 形状像 Base64 或 data URI：只含 Base64 字符与换行，`=` 只在末尾且最多两个（不验证能否解码）
+pub fn looks_like_base64(text: &str) -> bool {
+    let t = text.trim();
+    if let Some(rest) = strip_prefix_ignore_case(t, "data:") {
+        return rest.contains(',');
+    }
+    let compact: String = t.split(['\r', '\n']).collect();
+    let body = compact.trim_end_matches('=');
+    compact.len() >= 4
+        && compact.len() % 4 != 1
+        && compact.len() - body.len() <= 2
+        && body
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_'))
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &s[prefix.len()..])
+}
+
+/// `data:` 之后的部分：`[<mime>][;参数…][;base64],<数据>`；没有 `;base64` 时数据为百分号编码
+fn decode_data_uri(rest: &str) -> Result<Decoded, String> {
+    let (header, data) = rest
+        .split_once(',')
+        .ok_or("data URI 缺少逗号分隔的数据部分")?;
+    let mut params: Vec<&str> = header.split(';').map(str::trim).collect();
+    let is_base64 = params
+        .last()
+        .is_some_and(|p| p.eq_ignore_ascii_case("base64"));
+    if is_base64 {
+        params.pop();
+    }
+    // 省略 MIME 时按 RFC 2397 为 text/plain（其后的 charset 等参数保留）
+    match params.first_mut() {
+        Some(m) if !m.is_empty() => {}
+        Some(m) => *m = "text/plain",
+        None => params.push("text/plain"),
+    }
+    let mime = params.join(";");
+    if is_base64 {
+        let (bytes, detected) = decode_auto(data)?;
+        Ok(Decoded {
+            bytes,
+            detected: Some(detected),
+            mime: Some(mime),
+        })
+    } else {
+        Ok(Decoded {
+            bytes: percent_decode(data),
+            detected: None,
+            mime: Some(mime),
+        })
+    }
+}
+
+/// `%XX` 还原为字节；不合法的 `%` 原样保留
+fn percent_decode(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn decode_auto(text: &str) -> Result<(Vec<u8>, Detected), String> {
+    let trimmed = text.trim();
+    let compact: String = trimmed.split_whitespace().collect();
+    let wrapped = compact.len() != trimmed.len();
+    let url = compact.contains(['-', '_']);
+    let standard = compact.contains(['+', '/']);
+    let alphabet = match (standard, url) {
+        (true, true) => return Err("同时出现了 +/ 与 -_，无法确定字母表".into()),
+        (false, true) => Alphabet::UrlSafe,
+        _ => Alphabet::Standard,
+    };
+    let detected = Detected {
+        alphabet,
+        padded: if compact.ends_with('=') {
+            Some(true)
+        } else {
+            (!compact.len().is_multiple_of(4)).then_some(false)
+        },
+        wrapped,
+    };
+    engine(alphabet, Padding::Optional)
+        .decode(&compact)
+        .map(|bytes| (bytes, detected))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auto(text: &str) -> Decoded {
+        decode(text, &Options::default()).unwrap_or_else(|e| panic!("{}: {}", text, e))
+    }
+
+    #[test]
+    fn auto_detects_variants() {
+        let d = auto("SGk/Pz8=");
+        assert_eq!(d.bytes, b"Hi???");
+        assert_eq!(d.detected.unwrap().describe(), "标准 · 有填充");
+        let d = auto("SGk_Pz8");
+        assert_eq!(d.bytes, b"Hi???");
+        assert_eq!(d.detected.unwrap().describe(), "URL 安全 · 无填充");
+        // 长度是 4 的倍数时看不出是否省略了填充
+        assert_eq!(auto("SGVs").detected.unwrap().padded, None);
+        // MIME 按列换行
+        let d = auto("SGVs\r\nbG8=\n");
+        assert_eq!(d.bytes, b"Hello");
+        assert!(d.detected.unwrap().wrapped);
+        assert!(decode("ab+_", &Options::default()).is_err());
+    }
+
+    #[test]
+    fn data_uris() {
+        let d = auto("data:image/png;base64,iVBORw==");
+        assert_eq!(d.mime.as_deref(), Some("image/png"));
+        assert_eq!(d.bytes, [0x89, b'P', b'N', b'G']);
+        let d = auto("DATA:;charset=gbk;base64,1tDOxA==");
+        assert_eq!(d.mime.as_deref(), Some("text/plain;charset=gbk"));
+        assert_eq!(
+            auto("data:;base64,SGk=").mime.as_deref(),
+            Some("text/plain")
+        );
+        let d = auto("data:text/plain;charset=utf-8,a%20b%2");
+        assert_eq!(d.mime.as_deref(), Some("text/plain;charset=utf-8"));
+        assert_eq!(d.bytes, b"a b%2");
+        assert!(decode("data:text/plain", &Options::default()).is_err());
+
+        let opts = Options {
+            data_uri: true,
+            wrap: 4,
+            ..Options::default()
+        };
+        assert_eq!(
+            encode(b"Hello", &opts, "text/plain"),
+            "data:text/plain;base64,SGVsbG8="
+        );
+        assert_eq!(
+            data_uri("text/plain", b"Hello"),
+            "data:text/plain;base64,SGVsbG8="
+        );
+    }
+
+    #[test]
+    fn shape_check() {
+        assert!(looks_like_base64("SGVsbG8="));
+        assert!(looks_like_base64("SGVs\nbG8="));
+        assert!(looks_like_base64("data:,hi"));
+        assert!(!looks_like_base64("abc"));
+        assert!(!looks_like_base64("SGVsb"));
+        assert!(!looks_like_base64("SG=Vs"));
+        assert!(!looks_like_base64("hello world"));
+    }
+
+    #[test]
+    fn options_decode_is_strict() {
+        let opts = Options {
+            auto_detect: false,
+            ..Options::default()
+        };
+        assert_eq!(decode("SGk=", &opts).unwrap().bytes, b"Hi");
+        assert!(decode("SGk", &opts).is_err());
+        assert!(decode("SG\nk=", &opts).is_err());
+        let wrapped = Options { wrap: 64, ..opts };
+        assert_eq!(decode("SG\nk=", &wrapped).unwrap().bytes, b"Hi");
+        let stream = {
+            let mut e = StreamEncoder::new(&Options { wrap: 4, ..opts });
+            e.push(b"He");
+            e.push(b"llo");
+            e.finish("")
+        };
+        assert_eq!(stream, "SGVs\nbG8=");
+    }
+}
@@ -1,32 +1,304 @@
-use ::base64::engine::general_purpose;
-use ::base64::Engine;
+mod binary;
+mod charset;
+mod codec;
+mod text;
+
+use crate::component::persist;
+use crate::component::util::highlight::{highlight, Lang};
+use crate::component::util::offset::floor_grapheme;
+use charset::Charset;
+use codec::{Alphabet, Options, Padding, StreamEncoder, WRAP_WIDTHS};
+use dioxus::html::{FileEngine, HasFileData};
 use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use text::{Direction, Outcome, Settings};
+
+/// 输出区最多着色显示的字节数（大文件的编码结果很长，复制 / 保存不受此限制）
+const OUTPUT_DISPLAY_LIMIT: usize = 512 * 1024;
+/// 实时模式下超过此大小的输入先防抖再计算
+const LIVE_DEBOUNCE_BYTES: usize = 64 * 1024;
+const LIVE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 跨重启保留的会话状态
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Base64Session {
+    input: String,
+    options: Options,
+    /// 编码前输入文本转成字节所用的字符集
+    input_charset: Charset,
+    /// 解码后字节还原成文本所用的字符集
+    output_charset: Charset,
+    /// 实时模式：输入变化即重新计算
+    live: bool,
+    /// 实时模式下的方向
+    direction: Direction,
+    /// 逐行处理
+    batch: bool,
+}
+impl Default for Base64Session {
+    fn default() -> Self {
+        Self {
+            input: "Hello, World!".to_string(),
+            options: Options::default(),
+            input_charset: Charset::Utf8,
+            output_charset: Charset::Utf8,
+            live: false,
+            direction: Direction::Auto,
+            batch: false,
+        }
+    }
+}
 
 #[component]
 pub fn Base64Tool() -> Element {
+    let mut session = use_context::<Signal<Base64Session>>();
+
     // 状态
-    let mut input = use_signal(|| "Hello, World!".to_string());
+    let mut input = use_signal(|| session.peek().input.clone());
+    let mut options = use_signal(|| session.peek().options);
+    let mut input_charset = use_signal(|| session.peek().input_charset);
+    let mut output_charset = use_signal(|| session.peek().output_charset);
+    let mut live = use_signal(|| session.peek().live);
+    let mut direction = use_signal(|| session.peek().direction);
+    let mut batch = use_signal(|| session.peek().batch);
     let mut output = use_signal(String::new);
     let mut error_message = use_signal(String::new);
+    // 自动识别解码时识别出的变体
+    let mut detected = use_signal(String::new);
+    // 最近一次解码的原始字节（保存文件用）与 data URI 里声明的 MIME；编码后清空
+    let mut decoded = use_signal(|| None::<Vec<u8>>);
+    let mut decoded_mime = use_signal(|| None::<String>);
+    // 解码结果按二进制显示：不是 UTF-8，或按魔数识别出了文件类型
+    let mut is_binary = use_signal(|| false);
+    let mut saved_note = use_signal(String::new);
+    // 解码结果无法按所选字符集还原成文本时的说明（此时按十六进制显示）
+    let mut charset_note = use_signal(String::new);
+    // 自动判断出的方向 / 逐行统计
+    let mut summary = use_signal(String::new);
+    // 文件编码：来源说明、进度（百分比）、任务代号（新任务开始时旧任务自行退出）
+    let mut source_file = use_signal(String::new);
+    let mut progress = use_signal(|| None::<u64>);
+    let mut job = use_signal(|| 0u64);
+    let mut drag_over = use_signal(|| false);
+    let file_kind = use_memo(move || decoded.read().as_deref().and_then(binary::detect));
+    // 图片直接预览（data URI 声明的 image/* 或按魔数识别出的图片）
+    let preview = use_memo(move || {
+        let bytes = decoded.read();
+        let bytes = bytes.as_deref()?;
+        let mime = match (decoded_mime.read().as_deref(), file_kind()) {
+            (Some(m), _) if m.starts_with("image/") => m.to_string(),
+            (_, Some(k)) if k.is_image() => k.mime().to_string(),
+            _ => return None,
+        };
+        Some(codec::data_uri(&mime, bytes))
+    });
+    // 解码结果常是 JSON / XML / HTTP 报文等，按内容猜语言着色；十六进制转储不着色。
+    // 大文件的编码结果只显示开头一段，复制 / 保存仍是完整内容
+    let highlighted_output = use_memo(move || {
+        let text = output.read();
+        let shown = &text[..floor_grapheme(&text, text.len().min(OUTPUT_DISPLAY_LIMIT))];
+        let lang = if *is_binary.read() { Lang::Plain } else { Lang::detect(shown) };
+        highlight(shown, lang, &[], 0)
+    });
 
-    let encode_now = move |_| {
-        let src = input.read().clone();
+    // 写回会话状态（由 App 防抖落盘）
+    use_effect(move || {
+        let next = Base64Session {
+            input: persist::capped(&input.read()),
+            options: *options.read(),
+            input_charset: *input_charset.read(),
+            output_charset: *output_charset.read(),
+            live: *live.read(),
+            direction: *direction.read(),
+            batch: *batch.read(),
+        };
+        if *session.peek() != next {
+            session.set(next);
+        }
+    });
+
+    // 取消进行中的文件编码 / 实时计算（旧任务发现代号变了就自行退出）
+    let mut cancel = move || {
+        *job.write() += 1;
+        progress.set(None);
+    };
+
+    // 开始新的编码 / 解码：取消进行中的任务并清空上一次的结果说明
+    let mut start_job = move || {
+        cancel();
         error_message.set(String::new());
-        let encoded = general_purpose::STANDARD.encode(src.as_bytes());
-        output.set(encoded);
+        detected.set(String::new());
+        saved_note.set(String::new());
+        charset_note.set(String::new());
+        summary.set(String::new());
+        source_file.set(String::new());
+        decoded.set(None);
+        decoded_mime.set(None);
+        is_binary.set(false);
+    };
+
+    let settings = move || Settings {
+        options: *options.read(),
+        input_charset: *input_charset.read(),
+        output_charset: *output_charset.read(),
+        batch: *batch.read(),
+    };
+
+    let mut show = move |r: Outcome| {
+        output.set(r.output);
+        error_message.set(r.error);
+        detected.set(r.detected);
+        charset_note.set(r.charset_note);
+        summary.set(r.summary);
+        decoded.set(r.decoded);
+        decoded_mime.set(r.mime);
+        is_binary.set(r.is_binary);
+        saved_note.set(String::new());
+        source_file.set(String::new());
     };
 
-    let decode_now = move |_| {
+    let mut run_now = move |dir: Direction| {
+        cancel();
+        show(text::run(&input.read(), dir, &settings()));
+    };
+
+    // ========== 实时模式：输入 / 选项 / 方向任一变化即重算（后台线程，大输入先防抖）==========
+    use_effect(move || {
+        if !*live.read() {
+            return;
+        }
         let src = input.read().clone();
-        error_message.set(String::new());
-        match general_purpose::STANDARD.decode(&src) {
-            Ok(bytes) => match String::from_utf8(bytes) {
-                Ok(s) => output.set(s),
-                Err(e) => {
-                    error_message.set(format!("解码错误：不是有效的 UTF-8 文本（{}）", e))
-                }
-            },
-            Err(e) => error_message.set(format!("Base64 解码错误：输入无效（{}）", e)),
+        let dir = *direction.read();
+        let s = settings();
+        cancel();
+        let gen = *job.peek();
+        spawn(async move {
+            if src.len() > LIVE_DEBOUNCE_BYTES {
+                tokio::time::sleep(LIVE_DEBOUNCE).await;
+                if *job.peek() != gen {
+                    return;
+                }
+            }
+            let Ok(r) = tokio::task::spawn_blocking(move || text::run(&src, dir, &s)).await else {
+                return;
+            };
+            if *job.peek() == gen {
+                show(r);
+            }
+        });
+    });
+
+    // 输出移到输入（输入移到输出）；实时模式下方向随之反转，自动方向则重新判断
+    let swap = move |_| {
+        let out = output.read().clone();
+        let old = input.read().clone();
+        start_job();
+        input.set(out);
+        if *live.peek() {
+            let flipped = direction.peek().flipped();
+            direction.set(flipped);
+        } else {
+            output.set(old);
+        }
+    };
+
+    // 编码拖入 / 选择的文件：分块读取、分块编码，每块之间让出 UI 线程
+    let mut encode_file = move |files: Arc<dyn FileEngine>| {
+        let Some(name) = files.files().into_iter().next() else {
+            return;
+        };
+        start_job();
+        output.set(String::new());
+        let gen = *job.peek();
+        let opts = *options.peek();
+        spawn(async move {
+            let size = files.file_size(&name).await.unwrap_or(0);
+            let display = Path::new(&name)
+                .file_name()
+                .map_or(name.clone(), |n| n.to_string_lossy().into_owned());
+            source_file.set(format!("{}（{} 字节）", display, size));
+            progress.set(Some(0));
+            let mut encoder = StreamEncoder::new(&opts);
+            // 开头几个字节，用于按魔数判断 MIME
+            let mut head = Vec::new();
+            let native = files
+                .get_native_file(&name)
+                .await
+                .and_then(|f| f.downcast::<File>().ok());
+            if let Some(file) = native {
+                let mut file = *file;
+                let mut done = 0u64;
+                loop {
+                    let Ok((f, chunk)) = tokio::task::spawn_blocking(move || {
+                        let chunk = binary::read_chunk(&mut file);
+                        (file, chunk)
+                    })
+                    .await
+                    else {
+                        return;
+                    };
+                    file = f;
+                    if *job.peek() != gen {
+                        return;
+                    }
+                    let chunk = match chunk {
+                        Ok(c) if c.is_empty() => break,
+                        Ok(c) => c,
+                        Err(e) => {
+                            progress.set(None);
+                            error_message.set(format!("读取文件失败：{}", e));
+                            return;
+                        }
+                    };
+                    if head.is_empty() {
+                        head = chunk[..chunk.len().min(16)].to_vec();
+                    }
+                    encoder.push(&chunk);
+                    done += chunk.len() as u64;
+                    if let Some(pct) = (done * 100).checked_div(size) {
+                        progress.set(Some(pct.min(100)));
+                    }
+                }
+            } else {
+                // 非桌面环境拿不到本地文件句柄时一次读入
+                let Some(bytes) = files.read_file(&name).await else {
+                    progress.set(None);
+                    error_message.set(format!("读取文件失败：{}", display));
+                    return;
+                };
+                if *job.peek() != gen {
+                    return;
+                }
+                head = bytes[..bytes.len().min(16)].to_vec();
+                encoder.push(&bytes);
+            }
+            let mime = binary::mime_for(&display, &head);
+            source_file.set(format!("{}（{} 字节 · {}）", display, size, mime));
+            output.set(encoder.finish(mime));
+            progress.set(None);
+        });
+    };
+
+    let save_output = move |_| {
+        // 解码结果保存原始字节，编码结果保存 Base64 文本
+        let result = match decoded.read().as_deref() {
+            Some(bytes) => {
+                let ext = file_kind()
+                    .map(|k| k.extension())
+                    .or_else(|| decoded_mime.read().as_deref().and_then(binary::extension_for_mime))
+                    .unwrap_or("bin");
+                binary::save_to_downloads(bytes, ext)
+            }
+            None => binary::save_to_downloads(output.read().as_bytes(), "txt"),
+        };
+        match result {
+            Ok(path) => saved_note.set(format!("已保存到 {}", path.display())),
+            Err(e) => error_message.set(e),
         }
     };
 
@@ -43,6 +315,22 @@ pub fn Base64Tool() -> Element {
         dioxus::document::eval(&js);
     };
 
+    let opts = *options.read();
+    let output_info = if *is_binary.read() {
+        let len = decoded.read().as_ref().map_or(0, Vec::len);
+        match file_kind() {
+            Some(k) => format!("二进制 · {} 字节 · {}", len, k.label()),
+            None => format!("二进制 · {} 字节", len),
+        }
+    } else {
+        source_file.read().clone()
+    };
+    let truncated = output.read().len() > OUTPUT_DISPLAY_LIMIT;
+    let seg_active = "background:var(--accent-soft); color:var(--text-bright);";
+    let seg_normal = "";
+    // 自动识别只影响解码，此时字母表 / 填充两项对解码不起作用
+    let manual_dim = if opts.auto_detect { "opacity:.75;" } else { "" };
+
     rsx! {
         div {
             class: "tool-container",
@@ -51,24 +339,186 @@ pub fn Base64Tool() -> Element {
             // 输入 Panel
             div {
                 class: "tb-panel",
-                style: "flex:1; display:flex; flex-direction:column;",
+                style: if drag_over() { "flex:1; display:flex; flex-direction:column; outline:2px dashed var(--accent); outline-offset:-2px;" } else { "flex:1; display:flex; flex-direction:column;" },
+                ondragover: move |e| {
+                    e.prevent_default();
+                    drag_over.set(true);
+                },
+                ondragleave: move |_| drag_over.set(false),
+                ondrop: move |e| {
+                    e.prevent_default();
+                    drag_over.set(false);
+                    if let Some(files) = e.files() {
+                        encode_file(files);
+                    }
+                },
                 div {
                     class: "tb-panel-header",
                     span { style: "flex:1;", "输入" }
+                    if let Some(p) = progress() {
+                        span { style: "font-size:11px; font-weight:400; color:var(--text-dim);", "编码中 {p}%" }
+                        button {
+                            class: "tb-btn-ghost",
+                            style: "padding:3px 10px; font-size:11px;",
+                            onclick: move |_| start_job(),
+                            "取消"
+                        }
+                    }
+                    label {
+                        class: "tb-btn-ghost",
+                        style: "padding:3px 10px; font-size:11px; cursor:pointer;",
+                        title: "选择一个文件编码为 Base64（也可以把文件拖到这里）",
+                        "选择文件"
+                        input {
+                            r#type: "file",
+                            style: "display:none;",
+                            onchange: move |e| {
+                                if let Some(files) = e.files() {
+                                    encode_file(files);
+                                }
+                            },
+                        }
+                    }
                 }
                 textarea {
                     class: "tb-textarea tb-scroll",
                     value: "{input}",
                     oninput: move |e| input.set(e.value().clone()),
-                    placeholder: "在此输入待编码的文本，或粘贴一段 Base64 字符串用于解码…"
+                    placeholder: "在此输入待编码的文本，粘贴一段 Base64 / data URI 用于解码，或拖入文件编码…"
+                }
+            }
+
+            // 变体选项：字母表 / 填充 / 换行 / 解码时自动识别
+            div {
+                style: "display:flex; flex-wrap:wrap; align-items:center; justify-content:center; gap:6px 10px; font-size:12px; user-select:none;",
+                span { style: "color:var(--text-dim);", "字母表" }
+                div {
+                    style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden; {manual_dim}",
+                    for a in Alphabet::ALL {
+                        button {
+                            key: "{a.label()}",
+                            class: "tb-seg-btn",
+                            style: if opts.alphabet == a { seg_active } else { seg_normal },
+                            onclick: move |_| options.with_mut(|o| o.alphabet = a),
+                            "{a.label()}"
+                        }
+                    }
+                }
+                span { style: "color:var(--text-dim);", title: "编码时“必须”与“可选”都写填充；解码时分别要求有 / 不限 / 不能有", "填充" }
+                div {
+                    style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden; {manual_dim}",
+                    for p in Padding::ALL {
+                        button {
+                            key: "{p.label()}",
+                            class: "tb-seg-btn",
+                            style: if opts.padding == p { seg_active } else { seg_normal },
+                            onclick: move |_| options.with_mut(|o| o.padding = p),
+                            "{p.label()}"
+                        }
+                    }
+                }
+                span { style: "color:var(--text-dim);", title: "编码结果每行的字符数（64 = PEM，76 = MIME）", "换行" }
+                div {
+                    style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                    for w in WRAP_WIDTHS {
+                        button {
+                            key: "{w}",
+                            class: "tb-seg-btn",
+                            style: if opts.wrap == w { seg_active } else { seg_normal },
+                            onclick: move |_| options.with_mut(|o| o.wrap = w),
+                            if w == 0 { "关" } else { "{w}" }
+                        }
+                    }
+                }
+                button {
+                    class: "tb-seg-btn",
+                    style: if opts.auto_detect { seg_active } else { seg_normal },
+                    title: "解码时忽略空白，按内容判断字母表与填充",
+                    onclick: move |_| options.with_mut(|o| o.auto_detect = !o.auto_detect),
+                    "自动识别"
+                }
+                button {
+                    class: "tb-seg-btn",
+                    style: if opts.data_uri { seg_active } else { seg_normal },
+                    title: "编码结果输出为 data:<MIME>;base64,… （MIME 按文件内容识别）",
+                    onclick: move |_| options.with_mut(|o| o.data_uri = !o.data_uri),
+                    "data URI"
+                }
+                span { style: "color:var(--text-dim);", title: "编码前把输入文本转成字节所用的字符集", "输入字符集" }
+                select {
+                    class: "json-type-select",
+                    style: "font-size:12px; padding:2px 4px;",
+                    onchange: move |e: FormEvent| {
+                        if let Some(cs) = Charset::ALL.into_iter().find(|c| c.label() == e.value()) {
+                            input_charset.set(cs);
+                        }
+                    },
+                    for cs in Charset::ALL {
+                        option { key: "{cs.label()}", value: cs.label(), selected: cs == input_charset(), "{cs.label()}" }
+                    }
+                }
+                span { style: "color:var(--text-dim);", title: "解码后把字节还原成文本所用的字符集（data URI 声明了 charset 时以其为准）", "输出字符集" }
+                select {
+                    class: "json-type-select",
+                    style: "font-size:12px; padding:2px 4px;",
+                    onchange: move |e: FormEvent| {
+                        if let Some(cs) = Charset::ALL.into_iter().find(|c| c.label() == e.value()) {
+                            output_charset.set(cs);
+                        }
+                    },
+                    for cs in Charset::ALL {
+                        option { key: "{cs.label()}", value: cs.label(), selected: cs == output_charset(), "{cs.label()}" }
+                    }
                 }
             }
 
             // 中部操作区
             div {
                 style: "display:flex; align-items:center; justify-content:center; gap:12px; user-select:none;",
-                button { class: "tb-btn-primary", onclick: encode_now, "编码" }
-                button { class: "tb-btn", onclick: decode_now, "解码" }
+                if live() {
+                    div {
+                        style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden; font-size:12px;",
+                        for d in Direction::ALL {
+                            button {
+                                key: "{d.label()}",
+                                class: "tb-seg-btn",
+                                style: if direction() == d { seg_active } else { seg_normal },
+                                onclick: move |_| direction.set(d),
+                                "{d.label()}"
+                            }
+                        }
+                    }
+                } else {
+                    button { class: "tb-btn-primary", onclick: move |_| run_now(Direction::Encode), "编码" }
+                    button { class: "tb-btn", onclick: move |_| run_now(Direction::Decode), "解码" }
+                }
+                button {
+                    class: "tb-btn",
+                    title: "把输出移到输入，便于来回转换",
+                    disabled: output.read().is_empty() || is_binary() || progress().is_some(),
+                    onclick: swap,
+                    "⇅ 交换"
+                }
+                div {
+                    style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden; font-size:12px;",
+                    button {
+                        class: "tb-seg-btn",
+                        style: if live() { seg_active } else { seg_normal },
+                        title: "输入变化时自动重新计算，方向可自动判断",
+                        onclick: move |_| live.toggle(),
+                        "实时"
+                    }
+                    button {
+                        class: "tb-seg-btn",
+                        style: if batch() { seg_active } else { seg_normal },
+                        title: "每行单独编码 / 解码（编码不换行）",
+                        onclick: move |_| batch.toggle(),
+                        "逐行"
+                    }
+                }
+                if !summary.read().is_empty() {
+                    span { style: "font-size:12px; color:var(--text-dim);", "{summary}" }
+                }
             }
 
             // 输出 Panel
@@ -78,6 +528,21 @@ pub fn Base64Tool() -> Element {
                 div {
                     class: "tb-panel-header",
                     span { style: "flex:1;", "输出" }
+                    if !output_info.is_empty() {
+                        span { style: "font-size:11px; font-weight:400; color:var(--text-dim);", "{output_info}" }
+                    }
+                    if !detected.read().is_empty() {
+                        span { style: "font-size:11px; font-weight:400; color:var(--text-dim);", "{detected}" }
+                    }
+                    if !output.read().is_empty() {
+                        button {
+                            class: "tb-btn-ghost",
+                            style: "padding:3px 10px; font-size:11px;",
+                            title: "保存到下载目录：解码结果保存原始字节，编码结果保存 Base64 文本",
+                            onclick: save_output,
+                            "保存为文件"
+                        }
+                    }
                     button {
                         id: "b64-copy-btn",
                         class: "tb-btn-ghost",
@@ -86,10 +551,34 @@ pub fn Base64Tool() -> Element {
                         "复制"
                     }
                 }
+                if !saved_note.read().is_empty() {
+                    div {
+                        style: "padding:6px 14px; font-size:12px; color:var(--ok); background:var(--bg-header); border-bottom:1px solid var(--border); word-break:break-all;",
+                        "{saved_note}"
+                    }
+                }
+                if !charset_note.read().is_empty() {
+                    div {
+                        style: "padding:6px 14px; font-size:12px; color:var(--warn); background:var(--bg-header); border-bottom:1px solid var(--border); word-break:break-all;",
+                        "{charset_note}；已按十六进制显示"
+                    }
+                }
+                if truncated {
+                    div {
+                        style: "padding:6px 14px; font-size:12px; color:var(--text-dim); background:var(--bg-header); border-bottom:1px solid var(--border);",
+                        "结果较长，仅显示开头部分；复制与保存为完整内容"
+                    }
+                }
+                if let Some(uri) = preview() {
+                    div {
+                        style: "padding:10px 14px; background:var(--bg-input); border-bottom:1px solid var(--border);",
+                        img { src: "{uri}", style: "display:block; max-width:100%; max-height:240px; object-fit:contain;" }
+                    }
+                }
                 pre {
                     class: "tb-scroll",
                     style: "flex:1; background:var(--bg-input); color:var(--text); margin:0; padding:14px; overflow:auto; white-space:pre-wrap; word-break:break-word; font-family:'Menlo','Monaco','Consolas',monospace; font-size:13px; line-height:1.6;",
-                    "{output}"
+                    dangerous_inner_html: "{highlighted_output}",
                 }
             }
 
@@ -0,0 +1,321 @@
+// src/component/base64/text.rs
+//
+// 文本的编码 / 解码（按钮、实时模式与逐行模式共用），结果先汇总成 `Outcome` 再写回界面。
+//
+// 自动判断方向：输入只含 Base64 字符（允许换行，`=` 只在末尾）或是 data URI，
+// 且解码结果能按输出字符集还原成不含控制字符的文本（或识别出文件类型）时视为解码，否则编码。
+// UTF-8 的合法性本身就能筛掉大部分乱码；Latin-1 / GBK / Shift_JIS / UTF-16LE 下几乎任何字节
+// 都是“合法文本”，因此还要求输入带有 Base64 的特征（至少 8 个字符，或含 `=` `+` `/`），
+// 或解码结果基本是 ASCII。像 `test`、`user`、`1234` 这样碰巧合法的短单词在任何字符集下都按编码处理。
+//
+// 逐行模式下每行单独处理（自动方向也逐行判断），空行原样保留；出错的行输出留空并汇总报错。
+// 逐行时编码不换行，以保证输入输出行行对应。
+
+use serde::{Deserialize, Serialize};
+
+use super::binary;
+use super::charset::Charset;
+use super::codec::{self, Options};
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Direction {
+    #[default]
+    Auto,
+    Encode,
+    Decode,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 3] = [Direction::Auto, Direction::Encode, Direction::Decode];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Direction::Auto => "自动",
+            Direction::Encode => "编码",
+            Direction::Decode => "解码",
+        }
+    }
+
+    /// 交换输入输出后的方向
+    pub fn flipped(self) -> Direction {
+        match self {
+            Direction::Auto => Direction::Auto,
+            Direction::Encode => Direction::Decode,
+            Direction::Decode => Direction::Encode,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Settings {
+    pub options: Options,
+    pub input_charset: Charset,
+    pub output_charset: Charset,
+    /// 逐行处理
+    pub batch: bool,
+}
+
+/// 一次编码 / 解码的结果
+#[derive(Default)]
+pub struct Outcome {
+    pub output: String,
+    pub error: String,
+    /// 解码时识别出的变体 / data URI 说明
+    pub detected: String,
+    /// 无法按字符集还原成文本的说明（此时 output 为十六进制转储）
+    pub charset_note: String,
+    /// 解码得到的原始字节（逐行模式下为 None）
+    pub decoded: Option<Vec<u8>>,
+    /// data URI 里声明的 MIME
+    pub mime: Option<String>,
+    pub is_binary: bool,
+    /// 实际采用的方向（自动判断或逐行时的统计）
+    pub summary: String,
+}
+
+pub fn run(src: &str, dir: Direction, s: &Settings) -> Outcome {
+    if s.batch {
+        return run_lines(src, dir, s);
+    }
+    let decode = match dir {
+        Direction::Auto => looks_like_base64(src, s),
+        Direction::Encode => false,
+        Direction::Decode => true,
+    };
+    let mut r = if decode {
+        decode_one(src, s)
+    } else {
+        encode_one(src, s)
+    };
+    if dir == Direction::Auto && !src.trim().is_empty() {
+        r.summary = format!("自动：{}", if decode { "解码" } else { "编码" });
+    }
+    r
+}
+
+fn run_lines(src: &str, dir: Direction, s: &Settings) -> Outcome {
+    let mut s = *s;
+    s.options.wrap = 0;
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+    let (mut encoded, mut decoded) = (0, 0);
+    for (i, line) in src.lines().enumerate() {
+        if line.trim().is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        let decode = match dir {
+            Direction::Auto => looks_like_base64(line, &s),
+            Direction::Encode => false,
+            Direction::Decode => true,
+        };
+        let r = if decode {
+            decoded += 1;
+            decode_one(line, &s)
+        } else {
+            encoded += 1;
+            encode_one(line, &s)
+        };
+        let error = if !r.error.is_empty() {
+            r.error
+        } else if r.is_binary {
+            if r.charset_note.is_empty() {
+                "解码结果是二进制数据".to_string()
+            } else {
+                r.charset_note
+            }
+        } else {
+            lines.push(r.output);
+            continue;
+        };
+        errors.push(format!("第 {} 行：{}", i + 1, error));
+        lines.push(String::new());
+    }
+    let mut counts = Vec::new();
+    if encoded > 0 {
+        counts.push(format!("编码 {} 行", encoded));
+    }
+    if decoded > 0 {
+        counts.push(format!("解码 {} 行", decoded));
+    }
+    let error = match errors.len() {
+        0 => String::new(),
+        n => format!(
+            "{} 行处理失败（输出中留空）：{}{}",
+            n,
+            errors[..n.min(3)].join("；"),
+            if n > 3 { "；…" } else { "" }
+        ),
+    };
+    Outcome {
+        output: lines.join("\n"),
+        error,
+        summary: if counts.is_empty() {
+            String::new()
+        } else {
+            format!("逐行：{}", counts.join(" · "))
+        },
+        ..Outcome::default()
+    }
+}
+
+/// 自动方向：是否按 Base64 解码
+fn looks_like_base64(src: &str, s: &Settings) -> bool {
+    if !codec::looks_like_base64(src) {
+        return false;
+    }
+    let opts = Options {
+        auto_detect: true,
+        ..s.options
+    };
+    let Ok(d) = codec::decode(src, &opts) else {
+        return false;
+    };
+    let cs = d
+        .mime
+        .as_deref()
+        .and_then(Charset::from_mime)
+        .unwrap_or(s.output_charset);
+    if d.mime.is_some() || binary::detect(&d.bytes).is_some() {
+        return true;
+    }
+    cs.decode(&d.bytes)
+        .is_ok_and(|t| plausible_text(src, &t, cs))
+}
+
+/// 解码结果像是真正的文本（见文件头说明）
+fn plausible_text(src: &str, text: &str, cs: Charset) -> bool {
+    if text.is_empty() || text.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+        return false;
+    }
+    if cs == Charset::Utf8 {
+        return true;
+    }
+    let marked =
+        src.split_whitespace().map(str::len).sum::<usize>() >= 8 || src.contains(['=', '+', '/']);
+    let ascii = text
+        .chars()
+        .filter(|c| c.is_ascii_graphic() || c.is_ascii_whitespace())
+        .count();
+    marked || ascii * 10 >= text.chars().count() * 9
+}
+
+fn encode_one(src: &str, s: &Settings) -> Outcome {
+    let cs = s.input_charset;
+    match cs.encode(src) {
+        Ok(bytes) => {
+            let mime = format!("text/plain;charset={}", cs.label().to_ascii_lowercase());
+            Outcome {
+                output: codec::encode(&bytes, &s.options, &mime),
+                ..Outcome::default()
+            }
+        }
+        Err(e) => Outcome {
+            error: format!("无法按 {} 编码输入：{}", cs.label(), e),
+            ..Outcome::default()
+        },
+    }
+}
+
+fn decode_one(src: &str, s: &Settings) -> Outcome {
+    let result = match codec::decode(src, &s.options) {
+        Ok(r) => r,
+        Err(e) => {
+            return Outcome {
+                error: format!("Base64 解码错误：输入无效（{}）", e),
+                ..Outcome::default()
+            }
+        }
+    };
+    let mut note = Vec::new();
+    if let Some(m) = &result.mime {
+        note.push(format!("data URI · {}", m));
+    }
+    if let Some(d) = result.detected {
+        note.push(d.describe());
+    }
+    let mut r = Outcome::default();
+    if !note.is_empty() {
+        r.detected = format!("已识别：{}", note.join(" · "));
+    }
+    let bytes = result.bytes;
+    // data URI 声明了字符集时以声明为准
+    let cs = result
+        .mime
+        .as_deref()
+        .and_then(Charset::from_mime)
+        .unwrap_or(s.output_charset);
+    let text = match binary::detect(&bytes) {
+        Some(_) => None,
+        None => cs
+            .decode(&bytes)
+            .map_err(|e| r.charset_note = format!("无法按 {} 解码为文本：{}", cs.label(), e))
+            .ok(),
+    };
+    match text {
+        Some(t) => r.output = t,
+        None => {
+            r.output = binary::hex_dump(&bytes);
+            r.is_binary = true;
+        }
+    }
+    r.decoded = Some(bytes);
+    r.mime = result.mime;
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(output_charset: Charset) -> Settings {
+        Settings {
+            options: Options::default(),
+            input_charset: Charset::Utf8,
+            output_charset,
+            batch: false,
+        }
+    }
+
+    #[test]
+    fn short_words_are_encoded_under_every_charset() {
+        for cs in Charset::ALL {
+            for word in ["test", "user", "1234", "abcd"] {
+                let r = run(word, Direction::Auto, &settings(cs));
+                assert_eq!(r.summary, "自动：编码", "{} / {}", word, cs.label());
+            }
+        }
+    }
+
+    #[test]
+    fn real_base64_is_decoded() {
+        let s = settings(Charset::Utf8);
+        assert_eq!(
+            run("SGVsbG8sIFdvcmxkIQ==", Direction::Auto, &s).output,
+            "Hello, World!"
+        );
+        assert_eq!(run("5Lit5paH", Direction::Auto, &s).output, "中文");
+        // 无填充的 URL 安全变体（JWT 段）
+        assert_eq!(run("eyJhIjoxfQ", Direction::Auto, &s).output, r#"{"a":1}"#);
+        assert_eq!(run("data:,hi", Direction::Auto, &s).output, "hi");
+        // GBK 编码的“中文”，有填充
+        let gbk = settings(Charset::Gbk);
+        assert_eq!(run("1tDOxA==", Direction::Auto, &gbk).output, "中文");
+        let latin1 = settings(Charset::Latin1);
+        assert_eq!(run("Y2Fm6Q==", Direction::Auto, &latin1).output, "café");
+    }
+
+    #[test]
+    fn batch_keeps_line_alignment() {
+        let s = Settings {
+            batch: true,
+            ..settings(Charset::Utf8)
+        };
+        let r = run("hello\nSGk=\n\n!!!", Direction::Auto, &s);
+        assert_eq!(r.output, "aGVsbG8=\nHi\n\nISEh");
+        assert_eq!(r.summary, "逐行：编码 2 行 · 解码 1 行");
+        let r = run("SGk=\nzz", Direction::Decode, &s);
+        assert_eq!(r.output, "Hi\n");
+        assert!(r.error.starts_with("1 行处理失败"));
+    }
+}
@@ -0,0 +1,908 @@
+// src/component/converter/formats.rs
+//
+// JSON / YAML / TOML / XML / CSV 互转。
+//
+// 统一以 serde_json::Value 为中间表示（开启了 preserve_order），key 顺序按源文档保留；
+// 数字保留原文（arbitrary_precision），写出到 YAML / TOML 时才按目标格式的数值范围转换。
+// 中间表示或目标格式承载不了的信息不会被静默丢弃：每处有损转换都记录一条
+// “路径: 说明” 形式的警告（路径为 JSON Pointer），由界面列出。
+//
+// XML 映射约定：属性 -> "@名称"，元素文本与属性 / 子元素并存时 -> "#text"，
+// 同名子元素 -> 数组。CSV 只接受（平铺）对象数组，首行为表头。
+
+use crate::component::util::highlight::Lang;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DataFormat {
+    Json,
+    Yaml,
+    Toml,
+    Xml,
+    Csv,
+}
+
+impl DataFormat {
+    pub const ALL: [DataFormat; 5] = [
+        DataFormat::Json,
+        DataFormat::Yaml,
+        DataFormat::Toml,
+        DataFormat::Xml,
+        DataFormat::Csv,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DataFormat::Json => "JSON",
+            DataFormat::Yaml => "YAML",
+            DataFormat::Toml => "TOML",
+            DataFormat::Xml => "XML",
+            DataFormat::Csv => "CSV",
+        }
+    }
+
+    /// 输出高亮所用的语言
+    pub fn lang(&self) -> Lang {
+        match self {
+            DataFormat::Json => Lang::Json,
+            DataFormat::Yaml => Lang::Yaml,
+            DataFormat::Toml => Lang::Toml,
+            DataFormat::Xml => Lang::Xml,
+            DataFormat::Csv => Lang::Plain,
+        }
+    }
+}
+
+/// 转换结果：目标文本 + 有损转换警告
+#[derive(Clone, PartialEq, Default)]
+pub struct Converted {
+    pub text: String,
+    pub warnings: Vec<String>,
+}
+
+pub fn convert(src: &str, from: DataFormat, to: DataFormat) -> Result<Converted, String> {
+    let mut w = Warnings::default();
+    let v = match from {
+        DataFormat::Json => {
+            serde_json::from_str(src).map_err(|e| format!("JSON 解析错误: {}", e))?
+        }
+        DataFormat::Yaml => parse_yaml(src, &mut w)?,
+        DataFormat::Toml => parse_toml(src, &mut w)?,
+        DataFormat::Xml => parse_xml(src, &mut w)?,
+        DataFormat::Csv => parse_csv(src, &mut w)?,
+    };
+    let text = match to {
+        DataFormat::Json => {
+            serde_json::to_string_pretty(&v).map_err(|e| format!("序列化错误: {}", e))?
+        }
+        DataFormat::Yaml => {
+            serde_yaml::to_string(&json_to_yaml(&v, "", &mut w))
+                .map_err(|e| format!("YAML 序列化错误: {}", e))?
+        }
+        DataFormat::Toml => render_toml(&v, &mut w)?,
+        DataFormat::Xml => render_xml(&v, &mut w),
+        DataFormat::Csv => render_csv(&v, &mut w)?,
+    };
+    Ok(Converted {
+        text,
+        warnings: w.0,
+    })
+}
+
+#[derive(Default)]
+struct Warnings(Vec<String>);
+
+impl Warnings {
+    fn add(&mut self, path: &str, msg: impl AsRef<str>) {
+        let path = if path.is_empty() { "/" } else { path };
+        self.0.push(format!("{}: {}", path, msg.as_ref()));
+    }
+}
+
+/// JSON Pointer 追加一段（~ 与 / 按 RFC 6901 转义）
+fn child(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// 原文是整数写法（没有小数点与指数）
+fn is_integer_text(n: &Number) -> bool {
+    !n.to_string().contains(['.', 'e', 'E'])
+}
+
+/// 小数 / 指数写法的数字转成目标格式的浮点数。
+/// 超出 f64 范围时返回 None，由调用方写为字符串；有效数字多于 f64 能保存的照常转换，但记录警告
+fn to_float(n: &Number, target: &str, path: &str, w: &mut Warnings) -> Option<f64> {
+    let Some(f) = n.as_f64().filter(|f| f.is_finite()) else {
+        w.add(path, format!("{} 超出 {} 浮点数范围，写为字符串", n, target));
+        return None;
+    };
+    if decimal_digits(&n.to_string()) != decimal_digits(&format!("{:e}", f)) {
+        w.add(path, format!("{} 超出双精度浮点数的精度，写为 {:?}", n, f));
+    }
+    Some(f)
+}
+
+/// 十进制数的规范形式：(负号, 去掉首尾 0 的有效数字, 指数)，数值为 0.<有效数字> × 10^指数。
+/// 写法不同但数值相同的（`1.50`、`15e-1`）结果相同
+fn decimal_digits(text: &str) -> Option<(bool, String, i64)> {
+    let (neg, s) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (mantissa, exp) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], s[i + 1..].parse::<i64>().ok()?),
+        None => (s, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{}{}", int, frac);
+    let significant = digits.trim_start_matches('0');
+    let lead = digits.len() - significant.len();
+    let significant = significant.trim_end_matches('0');
+    if significant.is_empty() {
+        return Some((false, String::new(), 0));
+    }
+    Some((neg, significant.to_string(), exp + int.len() as i64 - lead as i64))
+}
+
+fn float_value(f: f64, path: &str, w: &mut Warnings) -> Value {
+    match Number::from_f64(f) {
+        Some(n) => Value::Number(n),
+        None => {
+            w.add(path, format!("{} 无法用 JSON 数字表示，改为 null", f));
+            Value::Null
+        }
+    }
+}
+
+// ================= YAML =================
+
+fn parse_yaml(src: &str, w: &mut Warnings) -> Result<Value, String> {
+    use serde::Deserialize as _;
+    // 多文档（--- 分隔）合并为数组
+    let mut docs = Vec::new();
+    for doc in serde_yaml::Deserializer::from_str(src) {
+        let mut v =
+            serde_yaml::Value::deserialize(doc).map_err(|e| format!("YAML 解析错误: {}", e))?;
+        // 展开 <<: *anchor 合并键
+        v.apply_merge()
+            .map_err(|e| format!("YAML 合并键错误: {}", e))?;
+        docs.push(v);
+    }
+    match docs.len() {
+        0 => Ok(Value::Null),
+        1 => Ok(yaml_to_json(docs.pop().unwrap_or_default(), "", w)),
+        n => {
+            w.add("", format!("包含 {} 个 YAML 文档，合并为数组", n));
+            Ok(Value::Array(
+                docs.into_iter()
+                    .enumerate()
+                    .map(|(i, d)| yaml_to_json(d, &child("", &i.to_string()), w))
+                    .collect(),
+            ))
+        }
+    }
+}
+
+fn yaml_to_json(v: serde_yaml::Value, path: &str, w: &mut Warnings) -> Value {
+    use serde_yaml::Value as Y;
+    match v {
+        Y::Null => Value::Null,
+        Y::Bool(b) => Value::Bool(b),
+        Y::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::from(u)
+            } else {
+                float_value(n.as_f64().unwrap_or(f64::NAN), path, w)
+            }
+        }
+        Y::String(s) => Value::String(s),
+        Y::Sequence(items) => Value::Array(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| yaml_to_json(item, &child(path, &i.to_string()), w))
+                .collect(),
+        ),
+        Y::Mapping(map) => {
+            let mut out = Map::new();
+            for (k, val) in map {
+                let key = match k {
+                    Y::String(s) => s,
+                    other => {
+                        let s = match &other {
+                            Y::Null => "null".to_string(),
+                            Y::Bool(b) => b.to_string(),
+                            Y::Number(n) => n.to_string(),
+                            _ => serde_yaml::to_string(&other)
+                                .unwrap_or_default()
+                                .trim_end()
+                                .to_string(),
+                        };
+                        w.add(path, format!("非字符串 key {} 转为字符串", s));
+                        s
+                    }
+                };
+                let p = child(path, &key);
+                let val = yaml_to_json(val, &p, w);
+                if out.insert(key, val).is_some() {
+                    w.add(&p, "key 重复，保留后者");
+                }
+            }
+            Value::Object(out)
+        }
+        Y::Tagged(t) => {
+            w.add(path, format!("忽略 YAML 标签 {}", t.tag));
+            yaml_to_json(t.value, path, w)
+        }
+    }
+}
+
+/// Number 在 arbitrary_precision 下序列化为内部结构，不能直接交给 serde_yaml，先逐个转换
+fn json_to_yaml(v: &Value, path: &str, w: &mut Warnings) -> serde_yaml::Value {
+    use serde_yaml::Value as Y;
+    match v {
+        Value::Null => Y::Null,
+        Value::Bool(b) => Y::Bool(*b),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Y::Number(i.into())
+            } else if let Some(u) = n.as_u64() {
+                Y::Number(u.into())
+            } else if is_integer_text(n) {
+                w.add(path, format!("{} 超出 YAML 整数范围（u64），写为字符串", n));
+                Y::String(n.to_string())
+            } else {
+                match to_float(n, "YAML", path, w) {
+                    Some(f) => Y::Number(f.into()),
+                    None => Y::String(n.to_string()),
+                }
+            }
+        }
+        Value::String(s) => Y::String(s.clone()),
+        Value::Array(items) => Y::Sequence(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| json_to_yaml(item, &child(path, &i.to_string()), w))
+                .collect(),
+        ),
+        Value::Object(map) => Y::Mapping(
+            map.iter()
+                .map(|(k, val)| (Y::String(k.clone()), json_to_yaml(val, &child(path, k), w)))
+                .collect(),
+        ),
+    }
+}
+
+// ================= TOML =================
+
+fn parse_toml(src: &str, w: &mut Warnings) -> Result<Value, String> {
+    let table: toml::Table = src.parse().map_err(|e| format!("TOML 解析错误: {}", e))?;
+    Ok(toml_to_json(toml::Value::Table(table), "", w))
+}
+
+fn toml_to_json(v: toml::Value, path: &str, w: &mut Warnings) -> Value {
+    use toml::Value as T;
+    match v {
+        T::String(s) => Value::String(s),
+        T::Integer(i) => Value::from(i),
+        T::Float(f) => float_value(f, path, w),
+        T::Boolean(b) => Value::Bool(b),
+        T::Datetime(d) => {
+            w.add(path, "TOML 日期时间转为字符串");
+            Value::String(d.to_string())
+        }
+        T::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| toml_to_json(item, &child(path, &i.to_string()), w))
+                .collect(),
+        ),
+        T::Table(t) => Value::Object(
+            t.into_iter()
+                .map(|(k, val)| {
+                    let val = toml_to_json(val, &child(path, &k), w);
+                    (k, val)
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn render_toml(v: &Value, w: &mut Warnings) -> Result<String, String> {
+    let Value::Object(_) = v else {
+        return Err(format!("TOML 顶层必须是表（对象），当前是{}", type_name(v)));
+    };
+    match json_to_toml(v, "", w) {
+        Some(toml::Value::Table(t)) => {
+            toml::to_string(&t).map_err(|e| format!("TOML 序列化错误: {}", e))
+        }
+        _ => Ok(String::new()),
+    }
+}
+
+/// 返回 None 表示该值在 TOML 中无法表示（null），由调用方省略
+fn json_to_toml(v: &Value, path: &str, w: &mut Warnings) -> Option<toml::Value> {
+    use toml::Value as T;
+    Some(match v {
+        Value::Null => {
+            w.add(path, "TOML 没有 null，已省略");
+            return None;
+        }
+        Value::Bool(b) => T::Boolean(*b),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                T::Integer(i)
+            } else if let Some(u) = n.as_u64() {
+                w.add(path, format!("{} 超出 TOML 整数范围（i64），写为字符串", u));
+                T::String(u.to_string())
+            } else if is_integer_text(n) {
+                w.add(path, format!("{} 超出 TOML 整数范围（i64），写为字符串", n));
+                T::String(n.to_string())
+            } else {
+                match to_float(n, "TOML", path, w) {
+                    Some(f) => T::Float(f),
+                    None => T::String(n.to_string()),
+                }
+            }
+        }
+        Value::String(s) => T::String(s.clone()),
+        Value::Array(items) => T::Array(
+            items
+                .iter()
+                .enumerate()
+                .filter_map(|(i, item)| json_to_toml(item, &child(path, &i.to_string()), w))
+                .collect(),
+        ),
+        Value::Object(map) => T::Table(
+            map.iter()
+                .filter_map(|(k, val)| {
+                    json_to_toml(val, &child(path, k), w).map(|t| (k.clone(), t))
+                })
+                .collect(),
+        ),
+    })
+}
+
+// ================= XML =================
+
+/// 解析中的元素：名称、属性与子元素、文本
+struct XmlFrame {
+    name: String,
+    map: Map<String, Value>,
+    text: String,
+    has_children_before_text: bool,
+}
+
+fn parse_xml(src: &str, w: &mut Warnings) -> Result<Value, String> {
+    use quick_xml::events::{BytesStart, Event};
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_str(src);
+    reader.config_mut().trim_text(true);
+
+    let open = |e: &BytesStart| -> Result<XmlFrame, String> {
+        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+        let mut map = Map::new();
+        for attr in e.attributes() {
+            let attr = attr.map_err(|e| format!("XML 属性错误: {}", e))?;
+            let key = format!("@{}", String::from_utf8_lossy(attr.key.as_ref()));
+            let value = attr
+                .unescape_value()
+                .map_err(|e| format!("XML 属性错误: {}", e))?;
+            map.insert(key, Value::String(value.into_owned()));
+        }
+        Ok(XmlFrame {
+            name,
+            map,
+            text: String::new(),
+            has_children_before_text: false,
+        })
+    };
+
+    let mut root = XmlFrame {
+        name: String::new(),
+        map: Map::new(),
+        text: String::new(),
+        has_children_before_text: false,
+    };
+    let mut stack: Vec<XmlFrame> = Vec::new();
+    let mut comments = 0usize;
+    let mut mixed_warned = false;
+
+    loop {
+        let pos = reader.buffer_position();
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("XML 解析错误（字节 {}）: {}", pos, e))?;
+        match event {
+            Event::Start(e) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.has_children_before_text = true;
+                }
+                stack.push(open(&e)?);
+            }
+            Event::Empty(e) => {
+                let frame = open(&e)?;
+                let parent = stack.last_mut().unwrap_or(&mut root);
+                parent.has_children_before_text = true;
+                let (name, value) = finish_xml_frame(frame);
+                push_xml_child(&mut parent.map, name, value);
+            }
+            Event::End(_) => {
+                let Some(frame) = stack.pop() else {
+                    return Err(format!("XML 解析错误（字节 {}）: 多余的结束标签", pos));
+                };
+                let (name, value) = finish_xml_frame(frame);
+                let parent = stack.last_mut().unwrap_or(&mut root);
+                push_xml_child(&mut parent.map, name, value);
+            }
+            Event::Text(t) => {
+                let text = t.unescape().map_err(|e| format!("XML 文本错误: {}", e))?;
+                append_xml_text(stack.last_mut(), &text, &mut mixed_warned, w);
+            }
+            Event::CData(t) => {
+                let text = String::from_utf8_lossy(&t.into_inner()).into_owned();
+                append_xml_text(stack.last_mut(), &text, &mut mixed_warned, w);
+            }
+            Event::Comment(_) => comments += 1,
+            Event::Decl(_) | Event::PI(_) | Event::DocType(_) => {}
+            Event::Eof => break,
+        }
+    }
+    if let Some(frame) = stack.last() {
+        return Err(format!("XML 解析错误: 元素 <{}> 未闭合", frame.name));
+    }
+    if comments > 0 {
+        w.add("", format!("忽略 {} 条 XML 注释", comments));
+    }
+    if !root.text.trim().is_empty() {
+        w.add("", "忽略根元素之外的文本");
+    }
+    Ok(Value::Object(root.map))
+}
+
+fn append_xml_text(
+    frame: Option<&mut XmlFrame>,
+    text: &str,
+    mixed_warned: &mut bool,
+    w: &mut Warnings,
+) {
+    let Some(frame) = frame else {
+        return;
+    };
+    if frame.has_children_before_text && !*mixed_warned {
+        *mixed_warned = true;
+        w.add(
+            "",
+            "XML 混合内容中文本与子元素的相对顺序不保留，文本合并到 #text",
+        );
+    }
+    frame.text.push_str(text);
+}
+
+fn finish_xml_frame(frame: XmlFrame) -> (String, Value) {
+    let XmlFrame {
+        name,
+        mut map,
+        text,
+        ..
+    } = frame;
+    let value = if map.is_empty() {
+        Value::String(text)
+    } else {
+        if !text.is_empty() {
+            map.insert("#text".to_string(), Value::String(text));
+        }
+        Value::Object(map)
+    };
+    (name, value)
+}
+
+/// 同名子元素聚合为数组
+fn push_xml_child(map: &mut Map<String, Value>, name: String, value: Value) {
+    match map.get_mut(&name) {
+        Some(Value::Array(items)) => items.push(value),
+        Some(existing) => {
+            let first = std::mem::take(existing);
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            map.insert(name, value);
+        }
+    }
+}
+
+fn render_xml(v: &Value, w: &mut Warnings) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let mut typed = 0usize;
+    match v {
+        Value::Object(map)
+            if map.len() == 1
+                && !map.keys().any(|k| k.starts_with('@') || k == "#text")
+                && !map.values().any(Value::is_array) =>
+        {
+            for (k, val) in map {
+                write_xml_element(&mut out, k, val, &child("", k), 0, &mut typed, w);
+            }
+        }
+        _ => {
+            w.add("", "XML 只能有一个根元素，已包一层 <root>");
+            write_xml_element(&mut out, "root", v, "", 0, &mut typed, w);
+        }
+    }
+    if typed > 0 {
+        w.add(
+            "",
+            format!(
+                "XML 没有数据类型：{} 个数字 / 布尔值写为文本，读回时为字符串",
+                typed
+            ),
+        );
+    }
+    out
+}
+
+fn write_xml_element(
+    out: &mut String,
+    key: &str,
+    v: &Value,
+    path: &str,
+    depth: usize,
+    typed: &mut usize,
+    w: &mut Warnings,
+) {
+    let indent = "  ".repeat(depth);
+    let name = xml_name(key);
+    if name != key {
+        w.add(
+            path,
+            format!("“{}” 不是合法的 XML 元素名，改为 {}", key, name),
+        );
+    }
+    match v {
+        Value::Array(items) => {
+            if items.is_empty() {
+                w.add(path, "空数组在 XML 中无法表示，已省略");
+            }
+            for (i, item) in items.iter().enumerate() {
+                let p = child(path, &i.to_string());
+                if item.is_array() {
+                    w.add(&p, "嵌套数组在 XML 中无法直接表示，已用 <item> 包裹");
+                    let wrapped =
+                        Value::Object(Map::from_iter([("item".to_string(), item.clone())]));
+                    write_xml_element(out, key, &wrapped, &p, depth, typed, w);
+                } else {
+                    write_xml_element(out, key, item, &p, depth, typed, w);
+                }
+            }
+        }
+        Value::Object(map) => {
+            let mut attrs = String::new();
+            let mut text: Option<String> = None;
+            let mut children = Vec::new();
+            for (k, val) in map {
+                let p = child(path, k);
+                if let Some(attr) = k.strip_prefix('@') {
+                    if let Some(s) = xml_scalar(val, &p, typed, w) {
+                        let attr_name = xml_name(attr);
+                        if attr_name != attr {
+                            w.add(
+                                &p,
+                                format!("“{}” 不是合法的 XML 属性名，改为 {}", attr, attr_name),
+                            );
+                        }
+                        let _ = write!(
+                            attrs,
+                            " {}=\"{}\"",
+                            attr_name,
+                            html_escape::encode_double_quoted_attribute(&s)
+                        );
+                        continue;
+                    }
+                    if !val.is_null() {
+                        w.add(&p, "属性值不是标量，改为子元素");
+                    }
+                } else if k == "#text" {
+                    if let Some(s) = xml_scalar(val, &p, typed, w) {
+                        text = Some(s);
+                        continue;
+                    }
+                }
+                children.push((k, val, p));
+            }
+            if children.is_empty() {
+                match text {
+                    Some(t) => {
+                        let _ = writeln!(
+                            out,
+                            "{indent}<{name}{attrs}>{}</{name}>",
+                            html_escape::encode_text(&t)
+                        );
+                    }
+                    None => {
+                        let _ = writeln!(out, "{indent}<{name}{attrs}/>");
+                    }
+                }
+                return;
+            }
+            let _ = writeln!(out, "{indent}<{name}{attrs}>");
+            if let Some(t) = text {
+                let _ = writeln!(out, "{indent}  {}", html_escape::encode_text(&t));
+            }
+            for (k, val, p) in children {
+                write_xml_element(out, k, val, &p, depth + 1, typed, w);
+            }
+            let _ = writeln!(out, "{indent}</{name}>");
+        }
+        scalar => match xml_scalar(scalar, path, typed, w) {
+            Some(s) if !s.is_empty() => {
+                let _ = writeln!(
+                    out,
+                    "{indent}<{name}>{}</{name}>",
+                    html_escape::encode_text(&s)
+                );
+            }
+            _ => {
+                let _ = writeln!(out, "{indent}<{name}/>");
+            }
+        },
+    }
+}
+
+/// 标量转为 XML 文本；null 与空字符串写法相同，报告为有损
+fn xml_scalar(v: &Value, path: &str, typed: &mut usize, w: &mut Warnings) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => {
+            *typed += 1;
+            Some(n.to_string())
+        }
+        Value::Bool(b) => {
+            *typed += 1;
+            Some(b.to_string())
+        }
+        Value::Null => {
+            w.add(path, "null 写为空元素 / 空属性，读回时为空字符串");
+            Some(String::new())
+        }
+        _ => None,
+    }
+}
+
+/// 把任意 key 修正为合法的 XML 名称（非法字符替换为 _）
+fn xml_name(key: &str) -> Cow<'_, str> {
+    let valid_start = |c: char| c.is_alphabetic() || c == '_' || c == ':';
+    let valid = |c: char| valid_start(c) || c.is_numeric() || matches!(c, '-' | '.');
+    if key.chars().next().is_some_and(valid_start) && key.chars().all(valid) {
+        return Cow::Borrowed(key);
+    }
+    let mut s: String = key
+        .chars()
+        .map(|c| if valid(c) { c } else { '_' })
+        .collect();
+    if !s.chars().next().is_some_and(valid_start) {
+        s.insert(0, '_');
+    }
+    Cow::Owned(s)
+}
+
+// ================= CSV =================
+
+fn parse_csv(src: &str, w: &mut Warnings) -> Result<Value, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(src.as_bytes());
+    let mut headers: Vec<String> = Vec::new();
+    for h in reader
+        .headers()
+        .map_err(|e| format!("CSV 解析错误: {}", e))?
+        .iter()
+    {
+        let mut name = h.to_string();
+        if headers.contains(&name) {
+            let mut n = 2;
+            while headers.contains(&format!("{}_{}", h, n)) {
+                n += 1;
+            }
+            name = format!("{}_{}", h, n);
+            w.add("", format!("列名 {} 重复，改为 {}", h, name));
+        }
+        headers.push(name);
+    }
+
+    let mut rows = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("CSV 解析错误: {}", e))?;
+        if record.len() > headers.len() {
+            w.add(
+                &child("", &i.to_string()),
+                format!(
+                    "该行有 {} 列，超出表头的 {} 列，多余列命名为 column_N",
+                    record.len(),
+                    headers.len()
+                ),
+            );
+        }
+        let mut row = Map::new();
+        for (j, cell) in record.iter().enumerate() {
+            let key = headers
+                .get(j)
+                .cloned()
+                .unwrap_or_else(|| format!("column_{}", j + 1));
+            row.insert(key, Value::String(cell.to_string()));
+        }
+        rows.push(Value::Object(row));
+    }
+    Ok(Value::Array(rows))
+}
+
+fn render_csv(v: &Value, w: &mut Warnings) -> Result<String, String> {
+    let rows: Vec<Cow<'_, Map<String, Value>>> = match v {
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| match item {
+                Value::Object(m) => Cow::Borrowed(m),
+                other => {
+                    w.add(
+                        &child("", &i.to_string()),
+                        format!("数组元素是{}而不是对象，写入 value 列", type_name(other)),
+                    );
+                    Cow::Owned(Map::from_iter([("value".to_string(), other.clone())]))
+                }
+            })
+            .collect(),
+        Value::Object(m) => vec![Cow::Borrowed(m)],
+        other => {
+            return Err(format!(
+                "CSV 需要对象数组（或单个对象），当前是{}",
+                type_name(other)
+            ));
+        }
+    };
+
+    // 表头：按首次出现顺序合并所有行的 key
+    let mut headers: Vec<&str> = Vec::new();
+    for row in &rows {
+        for k in row.keys() {
+            if !headers.contains(&k.as_str()) {
+                headers.push(k);
+            }
+        }
+    }
+
+    // 按列汇总有损情况，避免逐格刷屏
+    let mut missing = vec![0usize; headers.len()];
+    let mut nulls = vec![0usize; headers.len()];
+    let mut nested = vec![0usize; headers.len()];
+    let mut typed = 0usize;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(&headers)
+        .map_err(|e| format!("CSV 写入错误: {}", e))?;
+    for row in &rows {
+        let mut record = Vec::with_capacity(headers.len());
+        for (j, h) in headers.iter().enumerate() {
+            record.push(match row.get(*h) {
+                None => {
+                    missing[j] += 1;
+                    String::new()
+                }
+                Some(Value::Null) => {
+                    nulls[j] += 1;
+                    String::new()
+                }
+                Some(Value::String(s)) => s.clone(),
+                Some(v @ (Value::Number(_) | Value::Bool(_))) => {
+                    typed += 1;
+                    v.to_string()
+                }
+                Some(v) => {
+                    nested[j] += 1;
+                    serde_json::to_string(v).unwrap_or_default()
+                }
+            });
+        }
+        writer
+            .write_record(&record)
+            .map_err(|e| format!("CSV 写入错误: {}", e))?;
+    }
+
+    for (j, h) in headers.iter().enumerate() {
+        let p = child("", h);
+        if missing[j] > 0 {
+            w.add(&p, format!("{} 行缺少该列，写为空单元格", missing[j]));
+        }
+        if nulls[j] > 0 {
+            w.add(
+                &p,
+                format!("{} 个 null 写为空单元格，读回时为空字符串", nulls[j]),
+            );
+        }
+        if nested[j] > 0 {
+            w.add(
+                &p,
+                format!("{} 个嵌套对象 / 数组以 JSON 文本写入单元格", nested[j]),
+            );
+        }
+    }
+    if typed > 0 {
+        w.add(
+            "",
+            format!(
+                "CSV 没有数据类型：{} 个数字 / 布尔值写为文本，读回时为字符串",
+                typed
+            ),
+        );
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("CSV 写入错误: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("CSV 写入错误: {}", e))
+}
+
+fn type_name(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "布尔值",
+        Value::Number(_) => "数字",
+        Value::String(_) => "字符串",
+        Value::Array(_) => "数组",
+        Value::Object(_) => "对象",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to(src: &str, target: DataFormat) -> Converted {
+        convert(src, DataFormat::Json, target).unwrap()
+    }
+
+    #[test]
+    fn exact_floats_convert_without_warning() {
+        for target in [DataFormat::Yaml, DataFormat::Toml] {
+            let c = to(r#"{"a": 1.5, "b": 0.1, "c": 1.50, "d": 2.5e-3}"#, target);
+            assert!(c.warnings.is_empty(), "{:?}", c.warnings);
+        }
+    }
+
+    #[test]
+    fn out_of_range_float_is_written_as_string() {
+        let y = to(r#"{"a": 1E400}"#, DataFormat::Yaml);
+        assert_eq!(y.warnings.len(), 1);
+        assert!(y.warnings[0].starts_with("/a: "));
+        assert!(!y.text.contains("0.0"));
+        let t = to(r#"{"a": -1e400}"#, DataFormat::Toml);
+        assert_eq!(t.warnings.len(), 1);
+        assert!(t.text.contains('"'));
+    }
+
+    #[test]
+    fn excess_precision_is_reported() {
+        for target in [DataFormat::Yaml, DataFormat::Toml] {
+            let c = to(r#"{"a": 0.1000000000000000000001}"#, target);
+            assert_eq!(c.warnings.len(), 1, "{:?}", c.warnings);
+            assert!(c.warnings[0].contains("精度"));
+        }
+    }
+
+    #[test]
+    fn decimal_digits_ignores_notation() {
+        assert_eq!(decimal_digits("1.50"), decimal_digits("15e-1"));
+        assert_eq!(decimal_digits("120"), decimal_digits("1.2E+2"));
+        assert_eq!(decimal_digits("0.05"), decimal_digits("5e-2"));
+        assert_ne!(decimal_digits("-1"), decimal_digits("1"));
+        assert_eq!(decimal_digits("0.000"), decimal_digits("0e0"));
+    }
+}
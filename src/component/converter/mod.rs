@@ -0,0 +1,400 @@
+mod formats;
+
+use crate::component::persist;
+use crate::component::util::{
+    highlight::highlight,
+    offset::utf16_range,
+    search::{find_matches, replace_all_matches, replace_match, SearchBar, SearchOptions},
+};
+use dioxus::prelude::*;
+use formats::{convert, DataFormat};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// 跨重启保留的会话状态
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConverterSession {
+    input: String,
+    from: DataFormat,
+    to: DataFormat,
+}
+impl Default for ConverterSession {
+    fn default() -> Self {
+        Self {
+            input: String::new(),
+            from: DataFormat::Json,
+            to: DataFormat::Yaml,
+        }
+    }
+}
+
+/// 在指定 textarea 中选中 [start, end)（字节位置）并滚动到该行
+fn select_range(id: &str, text: &str, start: usize, end: usize) {
+    let Range { start: s, end: e } = utf16_range(text, start..end);
+    let js = format!(
+        "(function(){{var el=document.getElementById('{id}');if(!el)return;el.focus();try{{el.setSelectionRange({s},{e});}}catch(_){{}}var lh=parseFloat(getComputedStyle(el).lineHeight)||20;var lines=el.value.slice(0,{s}).split('\\n').length-1;el.scrollTop=Math.max(0,lines*lh-el.clientHeight/3);}})();"
+    );
+    dioxus::document::eval(&js);
+}
+
+/// 把高亮输出区滚动到 `pos`（字节位置）所在行
+fn scroll_to_line(id: &str, text: &str, pos: usize) {
+    let line = text[..pos].matches('\n').count();
+    dioxus::document::eval(&format!(
+        "(function(){{var el=document.getElementById('{id}');if(!el)return;var lh=parseFloat(getComputedStyle(el).lineHeight)||20;el.scrollTop=Math.max(0,{line}*lh-el.clientHeight/3);}})();"
+    ));
+}
+
+#[component]
+pub fn FormatConverterTool() -> Element {
+    let mut session = use_context::<Signal<ConverterSession>>();
+    let restored = session.peek().clone();
+
+    // ------- 输入与格式 -------
+    let mut input = use_signal(|| restored.input.clone());
+    let mut from = use_signal(|| restored.from);
+    let mut to = use_signal(|| restored.to);
+
+    // ------- 输出 / 错误 / 有损警告 -------
+    let mut output = use_signal(String::new);
+    let mut error_message = use_signal(String::new);
+    let mut warnings = use_signal(Vec::<String>::new);
+
+    // ------- 查找状态（输入可替换；输出只读） -------
+    let mut in_show_find = use_signal(|| false);
+    let mut in_show_replace = use_signal(|| false);
+    let mut in_query = use_signal(String::new);
+    let in_replace = use_signal(String::new);
+    let in_options = use_signal(SearchOptions::default);
+    let mut in_positions = use_signal(Vec::<Range<usize>>::new);
+    let mut in_idx = use_signal(|| 0usize);
+
+    let mut out_show_find = use_signal(|| false);
+    let mut out_show_replace = use_signal(|| false);
+    let mut out_query = use_signal(String::new);
+    let out_replace = use_signal(String::new);
+    let out_options = use_signal(SearchOptions::default);
+    let mut out_positions = use_signal(Vec::<Range<usize>>::new);
+    let mut out_idx = use_signal(|| 0usize);
+
+    // 当前活动侧："input" 或 "output"
+    let mut active_panel = use_signal(|| "input".to_string());
+
+    // 写回会话状态（由 App 防抖落盘）
+    use_effect(move || {
+        let next = ConverterSession {
+            input: persist::capped(&input.read()),
+            from: *from.read(),
+            to: *to.read(),
+        };
+        if *session.peek() != next {
+            session.set(next);
+        }
+    });
+
+    // ========== 转换：依赖 input / from / to ==========
+    use_effect(move || {
+        let src = input.read().clone();
+        let (f, t) = (*from.read(), *to.read());
+        if src.trim().is_empty() {
+            output.set(String::new());
+            error_message.set(String::new());
+            warnings.set(Vec::new());
+            return;
+        }
+        match convert(&src, f, t) {
+            Ok(c) => {
+                output.set(c.text);
+                warnings.set(c.warnings);
+                error_message.set(String::new());
+            }
+            Err(e) => {
+                output.set(String::new());
+                warnings.set(Vec::new());
+                error_message.set(e);
+            }
+        }
+    });
+
+    // ========== 查找：位置随文本 / 关键字 / 选项自动重算，选中当前匹配 ==========
+    use_effect(move || {
+        let pos = find_matches(&input.read(), &in_query.read(), *in_options.read());
+        let cur = *in_idx.peek();
+        if cur >= pos.len() {
+            in_idx.set(0);
+        }
+        in_positions.set(pos);
+    });
+    use_effect(move || {
+        let pos = find_matches(&output.read(), &out_query.read(), *out_options.read());
+        let cur = *out_idx.peek();
+        if cur >= pos.len() {
+            out_idx.set(0);
+        }
+        out_positions.set(pos);
+    });
+    // 输出：语法高亮 + 查找高亮（CSV 不着色）
+    let highlighted_output = use_memo(move || {
+        highlight(
+            &output.read(),
+            to.read().lang(),
+            &out_positions.read(),
+            *out_idx.read(),
+        )
+    });
+    use_effect(move || {
+        let positions = in_positions.read().clone();
+        let idx = *in_idx.read();
+        if let Some(m) = positions.get(idx) {
+            select_range("conv-input", &input.peek(), m.start, m.end);
+        }
+    });
+    use_effect(move || {
+        let positions = out_positions.read().clone();
+        let idx = *out_idx.read();
+        if let Some(m) = positions.get(idx) {
+            scroll_to_line("conv-output", &output.peek(), m.start);
+        }
+    });
+
+    let step = move |mut idx: Signal<usize>, positions: Signal<Vec<Range<usize>>>, forward: bool| {
+        let total = positions.read().len();
+        if total == 0 {
+            return;
+        }
+        let i = *idx.read();
+        idx.set(if forward {
+            (i + 1) % total
+        } else {
+            (i + total - 1) % total
+        });
+    };
+
+    // 输入面板替换
+    let mut replace_one = move || {
+        let positions = in_positions.read().clone();
+        let Some(m) = positions.get(*in_idx.read()).cloned() else {
+            return;
+        };
+        let text = input.read().clone();
+        let opts = *in_options.read();
+        if let Some(next) = replace_match(&text, &in_query.read(), opts, m, &in_replace.read()) {
+            input.set(next);
+        }
+    };
+    let mut replace_all = move || {
+        let text = input.read().clone();
+        let opts = *in_options.read();
+        if let Some(next) = replace_all_matches(&text, &in_query.read(), opts, &in_replace.read()) {
+            if next != text {
+                input.set(next);
+            }
+        }
+    };
+
+    // ========== 键盘快捷键（同 JSON 格式化） ==========
+    let on_keydown = move |e: Event<KeyboardData>| {
+        let meta =
+            e.modifiers().contains(Modifiers::META) || e.modifiers().contains(Modifiers::CONTROL);
+        let shift = e.modifiers().contains(Modifiers::SHIFT);
+        let on_input = *active_panel.read() == "input";
+        match e.code() {
+            Code::KeyF if meta => {
+                e.stop_propagation();
+                in_show_find.set(on_input);
+                out_show_find.set(!on_input);
+                in_show_replace.set(false);
+                out_show_replace.set(false);
+            }
+            Code::KeyH if meta && on_input => {
+                e.stop_propagation();
+                in_show_find.set(true);
+                in_show_replace.set(true);
+            }
+            Code::KeyG if meta => {
+                e.stop_propagation();
+                if on_input {
+                    step(in_idx, in_positions, !shift);
+                } else {
+                    step(out_idx, out_positions, !shift);
+                }
+            }
+            _ => {}
+        }
+    };
+
+    // 互换：输出作为新输入，源 / 目标格式对调
+    let swap = move |_| {
+        let text = output.read().clone();
+        let (f, t) = (*from.read(), *to.read());
+        if !text.is_empty() {
+            input.set(text);
+        }
+        from.set(t);
+        to.set(f);
+    };
+
+    let copy_output = move |_| {
+        let text = output.read().clone();
+        if text.is_empty() {
+            return;
+        }
+        let escaped = serde_json::to_string(&text).unwrap_or_else(|_| "\"\"".to_string());
+        let js = format!(
+            "navigator.clipboard.writeText({t}).then(function(){{var b=document.getElementById('conv-copy-btn');if(b){{var o=b.textContent;b.textContent='已复制 ✓';setTimeout(function(){{b.textContent=o;}},1200);}}}}).catch(function(){{var b=document.getElementById('conv-copy-btn');if(b){{b.textContent='复制失败';setTimeout(function(){{b.textContent='复制';}},1200);}}}});",
+            t = escaped
+        );
+        dioxus::document::eval(&js);
+    };
+
+    let seg_active = "background:var(--accent-soft); color:var(--text-bright);";
+    let seg_normal = "";
+    let warning_count = warnings.read().len();
+
+    rsx! {
+        div {
+            class: "tool-container",
+            tabindex: "0",
+            onkeydown: on_keydown,
+            style: "display:flex; flex-direction:column; height:100%; background:var(--bg-app); color:var(--text);",
+
+            div {
+                style: "display:flex; flex:1; padding:14px; gap:14px; overflow:hidden;",
+
+                // 左侧：输入
+                div {
+                    class: "tb-panel",
+                    style: "flex:1; display:flex; flex-direction:column; min-width:0;",
+                    SearchBar {
+                        show: in_show_find,
+                        show_replace: in_show_replace,
+                        query: in_query,
+                        replace: in_replace,
+                        options: in_options,
+                        on_prev: move |_| step(in_idx, in_positions, false),
+                        on_next: move |_| step(in_idx, in_positions, true),
+                        on_close: move |_| { in_show_find.set(false); in_show_replace.set(false); },
+                        on_query_input: move |q| { in_query.set(q); in_idx.set(0); },
+                        on_replace_one: Some(EventHandler::new(move |_| replace_one())),
+                        on_replace_all: Some(EventHandler::new(move |_| replace_all())),
+                        replace_disabled: false,
+                        match_current: *in_idx.read(),
+                        match_total: in_positions.read().len(),
+                    }
+                    div {
+                        class: "tb-panel-header",
+                        span { "输入" }
+                        div {
+                            style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                            for f in DataFormat::ALL {
+                                button {
+                                    key: "{f.label()}",
+                                    class: "tb-seg-btn",
+                                    style: if *from.read() == f { seg_active } else { seg_normal },
+                                    onclick: move |_| from.set(f),
+                                    "{f.label()}"
+                                }
+                            }
+                        }
+                        span { style: "flex:1;" }
+                    }
+                    textarea {
+                        id: "conv-input",
+                        class: "tb-textarea tb-scroll",
+                        value: "{input}",
+                        oninput: move |e| input.set(e.value()),
+                        onfocus: move |_| active_panel.set("input".to_string()),
+                        onmouseenter: move |_| active_panel.set("input".to_string()),
+                        placeholder: "粘贴 JSON / YAML / TOML / XML / CSV 数据..."
+                    }
+                }
+
+                // 右侧：输出
+                div {
+                    class: "tb-panel",
+                    style: "flex:1; display:flex; flex-direction:column; min-width:0;",
+                    SearchBar {
+                        show: out_show_find,
+                        show_replace: out_show_replace,
+                        query: out_query,
+                        replace: out_replace,
+                        options: out_options,
+                        on_prev: move |_| step(out_idx, out_positions, false),
+                        on_next: move |_| step(out_idx, out_positions, true),
+                        on_close: move |_| { out_show_find.set(false); out_show_replace.set(false); },
+                        on_query_input: move |q| { out_query.set(q); out_idx.set(0); },
+                        on_replace_one: None,
+                        on_replace_all: None,
+                        replace_disabled: true,
+                        match_current: *out_idx.read(),
+                        match_total: out_positions.read().len(),
+                    }
+                    div {
+                        class: "tb-panel-header",
+                        span { "输出" }
+                        div {
+                            style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                            for f in DataFormat::ALL {
+                                button {
+                                    key: "{f.label()}",
+                                    class: "tb-seg-btn",
+                                    style: if *to.read() == f { seg_active } else { seg_normal },
+                                    onclick: move |_| to.set(f),
+                                    "{f.label()}"
+                                }
+                            }
+                        }
+                        button {
+                            class: "tb-btn-ghost",
+                            style: "padding:3px 10px; font-size:11px;",
+                            title: "把输出作为输入，并对调源 / 目标格式",
+                            onclick: swap,
+                            "⇄ 互换"
+                        }
+                        span { style: "flex:1;" }
+                        button {
+                            id: "conv-copy-btn",
+                            class: "tb-btn-ghost",
+                            style: "padding:3px 10px; font-size:11px;",
+                            onclick: copy_output,
+                            "复制"
+                        }
+                    }
+                    div {
+                        id: "conv-output",
+                        class: "tb-scroll",
+                        tabindex: "0",
+                        style: "flex:1; background:var(--bg-input); color:var(--text); margin:0; padding:14px; overflow:auto; white-space:pre-wrap; word-break:break-word; font-family:'Menlo','Monaco','Consolas',monospace; font-size:13px; line-height:1.6;",
+                        dangerous_inner_html: "{highlighted_output}",
+                        onfocus: move |_| active_panel.set("output".to_string()),
+                        onmouseenter: move |_| active_panel.set("output".to_string()),
+                    }
+                }
+            }
+
+            // 有损转换：逐条列出，而不是静默丢弃
+            if warning_count > 0 {
+                div {
+                    style: "padding:10px 12px; color:var(--warn); background:var(--warn-soft); border:1px solid var(--warn); border-radius:var(--radius-sm); margin:0 14px 14px; font-size:13px; display:flex; flex-direction:column; gap:4px;",
+                    span { style: "font-weight:600;", "有损转换（{warning_count} 处）：" }
+                    div {
+                        class: "tb-scroll",
+                        style: "max-height:140px; overflow:auto; font-size:12px; color:var(--text);",
+                        for (i, w) in warnings.read().iter().cloned().enumerate() {
+                            div { key: "{i}", "{w}" }
+                        }
+                    }
+                }
+            }
+
+            if !error_message().is_empty() {
+                div {
+                    class: "error-message",
+                    style: "padding:10px 12px; color:var(--danger); background:rgba(244,135,113,.1); border:1px solid var(--danger); border-radius:var(--radius-sm); margin:0 14px 14px; font-size:13px;",
+                    "{error_message}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,775 @@
+// src/component/json/codegen.rs
+//
+// 由 JSON 样本生成类型定义：Rust（serde）、TypeScript、Go、Java record、Python dataclass。
+//
+// - 所有样本先合并为一个 Shape：数组元素、NDJSON 各条记录都并到同一形状上；
+//   并非每个对象都有的成员生成为可选字段，出现过 null 的生成为可空。
+// - 同一位置出现多种类型时生成联合类型（Rust 为 untagged enum；Go / Java 没有联合，退化为 any / Object）。
+//   integer 与 number 同时出现时合并为浮点数。
+// - 嵌套对象的类型名取自 key 的 PascalCase；数组元素取单数形式（items -> Item），重名时追加序号。
+
+use super::path::is_identifier;
+use super::shape::Shape;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TargetLang {
+    Rust,
+    TypeScript,
+    Go,
+    Java,
+    Python,
+}
+
+impl TargetLang {
+    pub const ALL: [TargetLang; 5] = [
+        TargetLang::Rust,
+        TargetLang::TypeScript,
+        TargetLang::Go,
+        TargetLang::Java,
+        TargetLang::Python,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TargetLang::Rust => "Rust",
+            TargetLang::TypeScript => "TS",
+            TargetLang::Go => "Go",
+            TargetLang::Java => "Java",
+            TargetLang::Python => "Python",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            TargetLang::Rust => "serde 结构体",
+            TargetLang::TypeScript => "TypeScript interface",
+            TargetLang::Go => "带 json tag 的 struct",
+            TargetLang::Java => "Java 16+ record（Jackson 注解）",
+            TargetLang::Python => "dataclass",
+        }
+    }
+}
+
+/// 字段 / 元素的类型
+#[derive(Clone, PartialEq)]
+enum Ty {
+    /// 未观察到具体类型（只见过 null 或空数组）
+    Any,
+    Bool,
+    Int,
+    Float,
+    Str,
+    List(Box<Ty>),
+    Nullable(Box<Ty>),
+    /// defs 中的下标
+    Def(usize),
+}
+
+struct Field {
+    key: String,
+    ty: Ty,
+    /// 并非所有对象都有该成员
+    optional: bool,
+}
+
+enum DefKind {
+    Struct(Vec<Field>),
+    Union(Vec<Ty>),
+}
+
+struct Def {
+    name: String,
+    kind: DefKind,
+}
+
+#[derive(Default)]
+struct Builder {
+    defs: Vec<Def>,
+    used: HashSet<String>,
+}
+
+impl Builder {
+    fn unique_name(&mut self, base: String) -> String {
+        let name = if self.used.contains(&base) {
+            (2..)
+                .map(|n| format!("{}{}", base, n))
+                .find(|n| !self.used.contains(n))
+                .unwrap_or_default()
+        } else {
+            base
+        };
+        self.used.insert(name.clone());
+        name
+    }
+
+    fn resolve(&mut self, shape: &Shape, hint: &str) -> Ty {
+        let has_float = shape.types.contains(&"number");
+        let mut tys = Vec::new();
+        for t in &shape.types {
+            let ty = match *t {
+                "boolean" => Ty::Bool,
+                "integer" if has_float => continue,
+                "integer" => Ty::Int,
+                "number" => Ty::Float,
+                "string" => Ty::Str,
+                "array" => {
+                    let item = match &shape.items {
+                        Some(items) if !items.types.is_empty() => {
+                            self.resolve(items, &singular(hint))
+                        }
+                        _ => Ty::Any,
+                    };
+                    Ty::List(Box::new(item))
+                }
+                "object" => Ty::Def(self.object(shape, hint)),
+                _ => continue,
+            };
+            tys.push(ty);
+        }
+        let ty = match tys.len() {
+            0 => return Ty::Any,
+            1 => tys.pop().unwrap_or(Ty::Any),
+            _ => {
+                let name = self.unique_name(format!("{}Value", pascal(hint, "Field")));
+                self.defs.push(Def {
+                    name,
+                    kind: DefKind::Union(tys),
+                });
+                Ty::Def(self.defs.len() - 1)
+            }
+        };
+        if shape.types.contains(&"null") {
+            Ty::Nullable(Box::new(ty))
+        } else {
+            ty
+        }
+    }
+
+    fn object(&mut self, shape: &Shape, hint: &str) -> usize {
+        // 先占位再填字段，外层类型排在内层之前
+        let name = self.unique_name(pascal(hint, "Type"));
+        let idx = self.defs.len();
+        self.defs.push(Def {
+            name,
+            kind: DefKind::Struct(Vec::new()),
+        });
+        let fields = shape
+            .properties
+            .iter()
+            .map(|(key, child, count)| Field {
+                key: key.clone(),
+                ty: self.resolve(child, key),
+                optional: *count < shape.objects,
+            })
+            .collect();
+        self.defs[idx].kind = DefKind::Struct(fields);
+        idx
+    }
+}
+
+/// 由样本生成 `lang` 的类型定义；根类型名为 `root_name`。
+/// 顶层是数组时为元素生成类型，另加一个列表别名（Java 无别名）。
+pub fn generate<'a>(
+    samples: impl IntoIterator<Item = &'a Value>,
+    lang: TargetLang,
+    root_name: &str,
+) -> String {
+    let mut shape = Shape::default();
+    for v in samples {
+        shape.observe(v);
+    }
+    let mut b = Builder::default();
+    let root = match (&shape.types[..], &shape.items) {
+        (["array"], Some(items)) if !items.types.is_empty() => {
+            let item = b.resolve(items, root_name);
+            Ty::List(Box::new(item))
+        }
+        _ => b.resolve(&shape, root_name),
+    };
+    let alias = match root {
+        Ty::Def(_) => None,
+        Ty::List(_) => Some((
+            b.unique_name(format!("{}List", pascal(root_name, "Root"))),
+            root,
+        )),
+        other => Some((b.unique_name(pascal(root_name, "Root")), other)),
+    };
+    let r = Render { defs: &b.defs };
+    match lang {
+        TargetLang::Rust => r.rust(alias),
+        TargetLang::TypeScript => r.typescript(alias),
+        TargetLang::Go => r.go(alias),
+        TargetLang::Java => r.java(),
+        TargetLang::Python => r.python(alias),
+    }
+}
+
+struct Render<'a> {
+    defs: &'a [Def],
+}
+
+impl Render<'_> {
+    fn name(&self, i: usize) -> &str {
+        &self.defs[i].name
+    }
+
+    fn is_union(&self, ty: &Ty) -> bool {
+        matches!(ty, Ty::Def(i) if matches!(self.defs[*i].kind, DefKind::Union(_)))
+    }
+
+    // ---------- Rust ----------
+
+    fn rust_ty(&self, ty: &Ty) -> String {
+        match ty {
+            Ty::Any => "serde_json::Value".into(),
+            Ty::Bool => "bool".into(),
+            Ty::Int => "i64".into(),
+            Ty::Float => "f64".into(),
+            Ty::Str => "String".into(),
+            Ty::List(t) => format!("Vec<{}>", self.rust_ty(t)),
+            Ty::Nullable(t) => format!("Option<{}>", self.rust_ty(t)),
+            Ty::Def(i) => self.name(*i).to_string(),
+        }
+    }
+
+    fn rust_variant(&self, ty: &Ty) -> String {
+        match ty {
+            Ty::Any => "Any".into(),
+            Ty::Bool => "Bool".into(),
+            Ty::Int => "Integer".into(),
+            Ty::Float => "Float".into(),
+            Ty::Str => "String".into(),
+            Ty::List(_) => "Array".into(),
+            Ty::Nullable(t) => self.rust_variant(t),
+            Ty::Def(i) => self.name(*i).to_string(),
+        }
+    }
+
+    fn rust(&self, alias: Option<(String, Ty)>) -> String {
+        let mut out = String::from("use serde::{Deserialize, Serialize};\n");
+        if let Some((name, ty)) = alias {
+            out.push_str(&format!("\npub type {} = {};\n", name, self.rust_ty(&ty)));
+        }
+        for def in self.defs {
+            out.push('\n');
+            match &def.kind {
+                DefKind::Struct(fields) => {
+                    out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+                    out.push_str(&format!("pub struct {} {{\n", def.name));
+                    let mut seen = HashSet::new();
+                    for f in fields {
+                        let base = unique_field(&mut seen, snake(&f.key, "field"));
+                        let ident = if RUST_KEYWORDS.contains(&base.as_str()) {
+                            if matches!(base.as_str(), "self" | "super" | "crate") {
+                                format!("{}_", base)
+                            } else {
+                                format!("r#{}", base)
+                            }
+                        } else {
+                            base
+                        };
+                        let mut attrs = Vec::new();
+                        // serde 会去掉 r# 前缀
+                        if ident.trim_start_matches("r#") != f.key {
+                            attrs.push(format!("rename = {:?}", f.key));
+                        }
+                        if f.optional {
+                            attrs.push("default, skip_serializing_if = \"Option::is_none\"".into());
+                        }
+                        if !attrs.is_empty() {
+                            out.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
+                        }
+                        let ty = if f.optional {
+                            match &f.ty {
+                                Ty::Nullable(_) => self.rust_ty(&f.ty),
+                                other => format!("Option<{}>", self.rust_ty(other)),
+                            }
+                        } else {
+                            self.rust_ty(&f.ty)
+                        };
+                        out.push_str(&format!("    pub {}: {},\n", ident, ty));
+                    }
+                    out.push_str("}\n");
+                }
+                DefKind::Union(tys) => {
+                    out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+                    out.push_str("#[serde(untagged)]\n");
+                    out.push_str(&format!("pub enum {} {{\n", def.name));
+                    for t in tys {
+                        out.push_str(&format!(
+                            "    {}({}),\n",
+                            self.rust_variant(t),
+                            self.rust_ty(t)
+                        ));
+                    }
+                    out.push_str("}\n");
+                }
+            }
+        }
+        out
+    }
+
+    // ---------- TypeScript ----------
+
+    fn ts_ty(&self, ty: &Ty) -> String {
+        match ty {
+            Ty::Any => "unknown".into(),
+            Ty::Bool => "boolean".into(),
+            Ty::Int | Ty::Float => "number".into(),
+            Ty::Str => "string".into(),
+            Ty::List(t) if matches!(**t, Ty::Nullable(_)) => format!("({})[]", self.ts_ty(t)),
+            Ty::List(t) => format!("{}[]", self.ts_ty(t)),
+            Ty::Nullable(t) => format!("{} | null", self.ts_ty(t)),
+            Ty::Def(i) => self.name(*i).to_string(),
+        }
+    }
+
+    fn typescript(&self, alias: Option<(String, Ty)>) -> String {
+        let mut blocks = Vec::new();
+        if let Some((name, ty)) = alias {
+            blocks.push(format!("export type {} = {};\n", name, self.ts_ty(&ty)));
+        }
+        for def in self.defs {
+            blocks.push(match &def.kind {
+                DefKind::Struct(fields) => {
+                    let mut s = format!("export interface {} {{\n", def.name);
+                    for f in fields {
+                        let key = if is_identifier(&f.key, true) {
+                            f.key.clone()
+                        } else {
+                            serde_json::to_string(&f.key).unwrap_or_default()
+                        };
+                        let opt = if f.optional { "?" } else { "" };
+                        s.push_str(&format!("    {}{}: {};\n", key, opt, self.ts_ty(&f.ty)));
+                    }
+                    s.push_str("}\n");
+                    s
+                }
+                DefKind::Union(tys) => {
+                    let parts: Vec<String> = tys.iter().map(|t| self.ts_ty(t)).collect();
+                    format!("export type {} = {};\n", def.name, parts.join(" | "))
+                }
+            });
+        }
+        blocks.join("\n")
+    }
+
+    // ---------- Go ----------
+
+    fn go_ty(&self, ty: &Ty) -> String {
+        match ty {
+            Ty::Any => "any".into(),
+            Ty::Bool => "bool".into(),
+            Ty::Int => "int64".into(),
+            Ty::Float => "float64".into(),
+            Ty::Str => "string".into(),
+            Ty::List(t) => format!("[]{}", self.go_ty(t)),
+            // 切片与 any 本身可为 nil，不再加指针
+            Ty::Nullable(t) if matches!(**t, Ty::List(_) | Ty::Any) || self.is_union(t) => {
+                self.go_ty(t)
+            }
+            Ty::Nullable(t) => format!("*{}", self.go_ty(t)),
+            Ty::Def(i) if self.is_union(ty) => format!("any /* {} */", self.name(*i)),
+            Ty::Def(i) => self.name(*i).to_string(),
+        }
+    }
+
+    fn go(&self, alias: Option<(String, Ty)>) -> String {
+        let mut blocks = Vec::new();
+        if let Some((name, ty)) = alias {
+            blocks.push(format!("type {} {}\n", name, self.go_ty(&ty)));
+        }
+        for def in self.defs {
+            let DefKind::Struct(fields) = &def.kind else {
+                continue;
+            };
+            let mut seen = HashSet::new();
+            let rows: Vec<(String, String, String)> = fields
+                .iter()
+                .map(|f| {
+                    let name = unique_field(&mut seen, go_name(&f.key));
+                    let ty = match &f.ty {
+                        t if f.optional && !matches!(t, Ty::Nullable(_)) => {
+                            self.go_ty(&Ty::Nullable(Box::new(t.clone())))
+                        }
+                        t => self.go_ty(t),
+                    };
+                    let omit = if f.optional { ",omitempty" } else { "" };
+                    let tag = format!("`json:\"{}{}\"`", go_tag_escape(&f.key), omit);
+                    (name, ty, tag)
+                })
+                .collect();
+            // 按 gofmt 对齐字段名与类型列
+            let w_name = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+            let w_ty = rows.iter().map(|r| r.1.chars().count()).max().unwrap_or(0);
+            let mut s = format!("type {} struct {{\n", def.name);
+            for (name, ty, tag) in rows {
+                s.push_str(&format!("\t{:<w_name$} {:<w_ty$} {}\n", name, ty, tag));
+            }
+            s.push_str("}\n");
+            blocks.push(s);
+        }
+        blocks.join("\n")
+    }
+
+    // ---------- Java ----------
+
+    fn java_ty(&self, ty: &Ty, boxed: bool) -> String {
+        match ty {
+            Ty::Any => "Object".into(),
+            Ty::Bool if boxed => "Boolean".into(),
+            Ty::Bool => "boolean".into(),
+            Ty::Int if boxed => "Long".into(),
+            Ty::Int => "long".into(),
+            Ty::Float if boxed => "Double".into(),
+            Ty::Float => "double".into(),
+            Ty::Str => "String".into(),
+            Ty::List(t) => format!("List<{}>", self.java_ty(t, true)),
+            Ty::Nullable(t) => self.java_ty(t, true),
+            Ty::Def(_) if self.is_union(ty) => "Object".into(),
+            Ty::Def(i) => self.name(*i).to_string(),
+        }
+    }
+
+    fn java(&self) -> String {
+        let mut blocks = Vec::new();
+        let mut uses_list = false;
+        let mut uses_property = false;
+        for def in self.defs {
+            let DefKind::Struct(fields) = &def.kind else {
+                continue;
+            };
+            let mut seen = HashSet::new();
+            let params: Vec<String> = fields
+                .iter()
+                .map(|f| {
+                    let mut name = unique_field(&mut seen, camel(&f.key, "field"));
+                    if JAVA_KEYWORDS.contains(&name.as_str()) {
+                        name.push('_');
+                    }
+                    let ty = self.java_ty(&f.ty, f.optional);
+                    uses_list |= ty.contains("List<");
+                    let annot = if name != f.key {
+                        uses_property = true;
+                        format!("@JsonProperty({}) ", java_string(&f.key))
+                    } else {
+                        String::new()
+                    };
+                    format!("        {}{} {}", annot, ty, name)
+                })
+                .collect();
+            if params.is_empty() {
+                blocks.push(format!("public record {}() {{}}\n", def.name));
+            } else {
+                blocks.push(format!(
+                    "public record {}(\n{}\n) {{}}\n",
+                    def.name,
+                    params.join(",\n")
+                ));
+            }
+        }
+        let mut imports = Vec::new();
+        if uses_property {
+            imports.push("import com.fasterxml.jackson.annotation.JsonProperty;\n");
+        }
+        if uses_list {
+            imports.push("import java.util.List;\n");
+        }
+        if !imports.is_empty() {
+            blocks.insert(0, imports.concat());
+        }
+        blocks.join("\n")
+    }
+
+    // ---------- Python ----------
+
+    fn py_ty(&self, ty: &Ty) -> String {
+        match ty {
+            Ty::Any => "Any".into(),
+            Ty::Bool => "bool".into(),
+            Ty::Int => "int".into(),
+            Ty::Float => "float".into(),
+            Ty::Str => "str".into(),
+            Ty::List(t) => format!("list[{}]", self.py_ty(t)),
+            Ty::Nullable(t) => format!("Optional[{}]", self.py_ty(t)),
+            Ty::Def(i) => self.name(*i).to_string(),
+        }
+    }
+
+    fn python(&self, alias: Option<(String, Ty)>) -> String {
+        let mut blocks = Vec::new();
+        // 联合别名在运行时求值，放到所有类之后
+        let mut aliases = Vec::new();
+        for def in self.defs {
+            match &def.kind {
+                DefKind::Struct(fields) => {
+                    let mut s = format!("@dataclass\nclass {}:\n", def.name);
+                    if fields.is_empty() {
+                        s.push_str("    pass\n");
+                    }
+                    // dataclass 要求有默认值的字段排在后面
+                    let mut seen = HashSet::new();
+                    let mut optional = Vec::new();
+                    for f in fields {
+                        let mut name = unique_field(&mut seen, snake(&f.key, "field"));
+                        if PY_RESERVED.contains(&name.as_str()) {
+                            name.push('_');
+                        }
+                        let note = if name != f.key {
+                            format!(
+                                "  # JSON: {}",
+                                serde_json::to_string(&f.key).unwrap_or_default()
+                            )
+                        } else {
+                            String::new()
+                        };
+                        if f.optional {
+                            let ty = match &f.ty {
+                                Ty::Nullable(_) => self.py_ty(&f.ty),
+                                other => format!("Optional[{}]", self.py_ty(other)),
+                            };
+                            optional.push(format!("    {}: {} = None{}\n", name, ty, note));
+                        } else {
+                            s.push_str(&format!("    {}: {}{}\n", name, self.py_ty(&f.ty), note));
+                        }
+                    }
+                    s.push_str(&optional.concat());
+                    blocks.push(s);
+                }
+                DefKind::Union(tys) => {
+                    let parts: Vec<String> = tys.iter().map(|t| self.py_ty(t)).collect();
+                    aliases.push(format!("{} = Union[{}]\n", def.name, parts.join(", ")));
+                }
+            }
+        }
+        if let Some((name, ty)) = alias {
+            aliases.push(format!("{} = {}\n", name, self.py_ty(&ty)));
+        }
+        if !aliases.is_empty() {
+            blocks.push(aliases.concat());
+        }
+        let body = blocks.join("\n\n");
+        let typing: Vec<&str> = ["Any", "Optional", "Union"]
+            .into_iter()
+            .filter(|t| contains_word(&body, t))
+            .collect();
+        let mut header = String::from(
+            "from __future__ import annotations\n\nfrom dataclasses import dataclass\n",
+        );
+        if !typing.is_empty() {
+            header.push_str(&format!("from typing import {}\n", typing.join(", ")));
+        }
+        format!("{}\n\n{}", header, body)
+    }
+}
+
+// ================= 命名 =================
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "record",
+    "var",
+    "yield",
+];
+
+/// Python 关键字，外加 dataclass 生成的 `__init__` 首参 `self`
+const PY_RESERVED: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield", "self",
+];
+
+/// Go 惯用的全大写缩写
+const GO_INITIALISMS: &[&str] = &[
+    "id", "url", "uri", "api", "http", "https", "json", "xml", "html", "uuid", "ip", "sql", "css",
+];
+
+/// 把 key 拆成小写单词：非字母数字处断开，camelCase 的大小写边界处断开
+fn words(key: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut prev: Option<char> = None;
+    for c in key.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !cur.is_empty() {
+                out.push(std::mem::take(&mut cur));
+            }
+            prev = None;
+            continue;
+        }
+        let boundary = match prev {
+            Some(p) => (p.is_ascii_lowercase() || p.is_ascii_digit()) && c.is_ascii_uppercase(),
+            None => false,
+        };
+        if boundary && !cur.is_empty() {
+            out.push(std::mem::take(&mut cur));
+        }
+        cur.push(c.to_ascii_lowercase());
+        prev = Some(c);
+    }
+    if !cur.is_empty() {
+        out.push(cur);
+    }
+    out
+}
+
+fn capitalize(w: &str) -> String {
+    let mut c = w.chars();
+    match c.next() {
+        Some(f) => f.to_ascii_uppercase().to_string() + c.as_str(),
+        None => String::new(),
+    }
+}
+
+/// 以数字开头的标识符加前缀；没有可用字符时用 `fallback`
+fn fix_start(s: String, prefix: &str, fallback: &str) -> String {
+    match s.chars().next() {
+        None => fallback.to_string(),
+        Some(c) if c.is_ascii_digit() => format!("{}{}", prefix, s),
+        _ => s,
+    }
+}
+
+fn pascal(key: &str, fallback: &str) -> String {
+    let s: String = words(key).iter().map(|w| capitalize(w)).collect();
+    fix_start(s, fallback, fallback)
+}
+
+fn camel(key: &str, fallback: &str) -> String {
+    let ws = words(key);
+    let s: String = ws
+        .iter()
+        .enumerate()
+        .map(|(i, w)| if i == 0 { w.clone() } else { capitalize(w) })
+        .collect();
+    fix_start(s, "_", fallback)
+}
+
+fn snake(key: &str, fallback: &str) -> String {
+    fix_start(words(key).join("_"), "_", fallback)
+}
+
+fn go_name(key: &str) -> String {
+    let s: String = words(key)
+        .iter()
+        .map(|w| {
+            if GO_INITIALISMS.contains(&w.as_str()) {
+                w.to_ascii_uppercase()
+            } else {
+                capitalize(w)
+            }
+        })
+        .collect();
+    fix_start(s, "F", "Field")
+}
+
+/// 同一类型内字段名去重：name、name2、name3…
+fn unique_field(seen: &mut HashSet<String>, base: String) -> String {
+    let name = if seen.contains(&base) {
+        (2..)
+            .map(|n| format!("{}{}", base, n))
+            .find(|n| !seen.contains(n))
+            .unwrap_or_default()
+    } else {
+        base
+    };
+    seen.insert(name.clone());
+    name
+}
+
+/// 数组元素的类型名：items -> Item，categories -> Category
+fn singular(key: &str) -> String {
+    if let Some(stem) = key.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if key.ends_with("ss") || key.ends_with("us") {
+        key.to_string()
+    } else if let Some(stem) = key.strip_suffix('s').filter(|s| !s.is_empty()) {
+        stem.to_string()
+    } else {
+        format!("{}Item", key)
+    }
+}
+
+/// struct tag 中 key 里的 `"` 与 `\` 需要转义，反引号无法表示，替换为下划线
+fn go_tag_escape(key: &str) -> String {
+    key.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('`', "_")
+}
+
+/// `word` 作为完整标识符出现（前后都不是标识符字符）
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_id = |c: char| c == '_' || c.is_ascii_alphanumeric();
+    text.match_indices(word).any(|(i, _)| {
+        !text[..i].chars().next_back().is_some_and(is_id)
+            && !text[i + word.len()..].chars().next().is_some_and(is_id)
+    })
+}
+
+fn java_string(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
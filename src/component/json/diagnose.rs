@@ -0,0 +1,319 @@
+// src/component/json/diagnose.rs
+//
+// JSON 解析错误诊断 + 常见损坏的自动修复。
+//
+// - `diagnose`：把解析器报告的 (line, column) 换算成字节偏移，并生成带 ^ 指示的上下文片段。
+// - `repair`：单遍扫描，修复尾随 / 多余逗号、单引号字符串、未加引号的 key、注释、
+//   Python 的 True/False/None、未闭合的字符串与括号；每处修改记录行号与说明，
+//   由界面先展示给用户确认后再应用。
+
+use crate::component::util::offset::byte_to_grapheme;
+
+/// 一次解析失败的定位信息
+#[derive(Clone, PartialEq)]
+pub struct Diagnostic {
+    /// 1 起行号 / 列号（列按字素簇计，emoji、带组合符的字母都算一列）
+    pub line: usize,
+    pub column: usize,
+    /// 出错位置的字节偏移（已对齐到字符边界）
+    pub offset: usize,
+    /// 出错行及前一行 + ^ 指示
+    pub snippet: String,
+}
+
+/// `line` 为 1 起行号；`column` 为该行内的字节数，指向出错字符之后（serde_json 的约定，
+/// 各输入方言的解析器也沿用它）。
+pub fn diagnose(src: &str, line: usize, column: usize) -> Diagnostic {
+    let line = line.max(1);
+    let line_start = src
+        .split_inclusive('\n')
+        .take(line - 1)
+        .map(str::len)
+        .sum::<usize>()
+        .min(src.len());
+    let line_text = src[line_start..].split('\n').next().unwrap_or("");
+    let mut col_bytes = column.min(line_text.len());
+    while !line_text.is_char_boundary(col_bytes) {
+        col_bytes -= 1;
+    }
+    // 指向出错字符本身（column 为 0 时就是行首）
+    let err_char_start = line_text[..col_bytes]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(i, _)| i);
+    let column = byte_to_grapheme(line_text, err_char_start) + 1;
+
+    let gutter = (line + 1).to_string().len();
+    let mut snippet = String::new();
+    if line > 1 {
+        let prev = src[..line_start.saturating_sub(1)]
+            .rsplit('\n')
+            .next()
+            .unwrap_or("");
+        snippet.push_str(&format!("{:>w$} | {}\n", line - 1, prev, w = gutter));
+    }
+    snippet.push_str(&format!("{:>w$} | {}\n", line, line_text, w = gutter));
+    snippet.push_str(&format!(
+        "{:>w$} | {}^",
+        "",
+        " ".repeat(column - 1),
+        w = gutter
+    ));
+
+    Diagnostic {
+        line,
+        column,
+        offset: line_start + err_char_start,
+        snippet,
+    }
+}
+
+/// 修复结果：新文本 + 每处修改（行号，说明）
+#[derive(Clone, PartialEq)]
+pub struct Repair {
+    pub text: String,
+    pub fixes: Vec<(usize, String)>,
+}
+
+pub fn repair(src: &str) -> Repair {
+    let chars: Vec<char> = src.chars().collect();
+    let mut out = String::with_capacity(src.len() + 16);
+    let mut fixes: Vec<(usize, String)> = Vec::new();
+    let mut stack: Vec<char> = Vec::new();
+    let mut line = 1usize;
+    let mut i = 0usize;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\n' => {
+                out.push(c);
+                line += 1;
+                i += 1;
+            }
+            '/' if next == Some('/') => {
+                fixes.push((line, "删除 // 注释".into()));
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                fixes.push((line, "删除 /* */ 注释".into()));
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    // 保留换行，使后面的行号不变
+                    if chars[i] == '\n' {
+                        out.push('\n');
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i = (i + 2).min(chars.len());
+            }
+            '"' => {
+                out.push('"');
+                i += 1;
+                let mut closed = false;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    out.push(ch);
+                    i += 1;
+                    if ch == '\\' {
+                        if let Some(&esc) = chars.get(i) {
+                            out.push(esc);
+                            i += 1;
+                        }
+                    } else if ch == '"' {
+                        closed = true;
+                        break;
+                    } else if ch == '\n' {
+                        line += 1;
+                    }
+                }
+                if !closed {
+                    out.push('"');
+                    fixes.push((line, "补全未闭合的字符串".into()));
+                }
+            }
+            '\'' => {
+                let start_line = line;
+                out.push('"');
+                i += 1;
+                let mut closed = false;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    i += 1;
+                    match ch {
+                        '\'' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' if chars.get(i) == Some(&'\'') => {
+                            out.push('\'');
+                            i += 1;
+                        }
+                        '\\' => {
+                            out.push('\\');
+                            if let Some(&esc) = chars.get(i) {
+                                out.push(esc);
+                                i += 1;
+                            }
+                        }
+                        '"' => out.push_str("\\\""),
+                        '\n' => {
+                            out.push('\n');
+                            line += 1;
+                        }
+                        other => out.push(other),
+                    }
+                }
+                out.push('"');
+                fixes.push((start_line, "单引号字符串改为双引号".into()));
+                if !closed {
+                    fixes.push((line, "补全未闭合的字符串".into()));
+                }
+            }
+            '{' | '[' => {
+                stack.push(c);
+                out.push(c);
+                i += 1;
+            }
+            '}' | ']' => {
+                if strip_trailing_comma(&mut out) {
+                    fixes.push((line, "删除尾随逗号".into()));
+                }
+                let want = if c == '}' { '{' } else { '[' };
+                if let Some(depth) = stack.iter().rposition(|&o| o == want) {
+                    // 中间还有未闭合的容器：先补上它们的闭合符
+                    while stack.len() > depth + 1 {
+                        let open = stack.pop().unwrap_or('{');
+                        out.push(closer(open));
+                        fixes.push((line, format!("补全缺失的 {}", closer(open))));
+                    }
+                    stack.pop();
+                    out.push(c);
+                } else {
+                    fixes.push((line, format!("删除多余的 {}", c)));
+                }
+                i += 1;
+            }
+            ',' => {
+                // 连续逗号 / 容器开头的逗号
+                if matches!(out.trim_end().chars().last(), Some(',' | '{' | '[')) {
+                    fixes.push((line, "删除多余的逗号".into()));
+                } else {
+                    out.push(c);
+                }
+                i += 1;
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$'))
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let mut j = i;
+                while j < chars.len() && matches!(chars[j], ' ' | '\t' | '\r') {
+                    j += 1;
+                }
+                let is_key = chars.get(j) == Some(&':') && stack.last() == Some(&'{');
+                match word.as_str() {
+                    _ if is_key => {
+                        out.push_str(&serde_json::to_string(&word).unwrap_or_default());
+                        fixes.push((line, format!("为 key {} 加引号", word)));
+                    }
+                    "true" | "false" | "null" => out.push_str(&word),
+                    "True" | "False" | "None" => {
+                        let fixed = match word.as_str() {
+                            "True" => "true",
+                            "False" => "false",
+                            _ => "null",
+                        };
+                        out.push_str(fixed);
+                        fixes.push((line, format!("{} 改为 {}", word, fixed)));
+                    }
+                    "NaN" | "Infinity" | "undefined" => {
+                        // `-Infinity` / `+Infinity`：符号已原样写出，一并替换
+                        let sign = start
+                            .checked_sub(1)
+                            .map(|p| chars[p])
+                            .filter(|s| matches!(s, '-' | '+') && out.ends_with(*s));
+                        if sign.is_some() {
+                            out.pop();
+                        }
+                        out.push_str("null");
+                        let shown: String = sign.into_iter().chain(word.chars()).collect();
+                        fixes.push((line, format!("{} 改为 null", shown)));
+                    }
+                    _ => out.push_str(&word),
+                }
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    // 截断的文档：去掉悬空的逗号 / 冒号，再补齐所有未闭合的括号
+    if !stack.is_empty() {
+        if strip_trailing_comma(&mut out) {
+            fixes.push((line, "删除尾随逗号".into()));
+        }
+        if out.trim_end().ends_with(':') {
+            out.truncate(out.trim_end().len());
+            out.push_str(" null");
+            fixes.push((line, "为缺失的值补 null".into()));
+        }
+        while let Some(open) = stack.pop() {
+            out.push(closer(open));
+            fixes.push((line, format!("补全缺失的 {}", closer(open))));
+        }
+    }
+
+    Repair { text: out, fixes }
+}
+
+fn closer(open: char) -> char {
+    if open == '{' {
+        '}'
+    } else {
+        ']'
+    }
+}
+
+/// 若 `out` 末尾（忽略空白）是逗号则删除它，保留其后的空白
+fn strip_trailing_comma(out: &mut String) -> bool {
+    let trimmed_len = out.trim_end().len();
+    if out[..trimmed_len].ends_with(',') {
+        let tail = out[trimmed_len..].to_string();
+        out.truncate(trimmed_len - 1);
+        out.push_str(&tail);
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_numbers_become_null() {
+        let r = repair("[-Infinity, NaN, Infinity, +Infinity]");
+        assert_eq!(r.text, "[null, null, null, null]");
+        assert!(serde_json::from_str::<serde_json::Value>(&r.text).is_ok());
+        assert_eq!(r.fixes[0].1, "-Infinity 改为 null");
+    }
+
+    #[test]
+    fn minus_before_other_words_is_kept() {
+        // 只有紧挨着 Infinity / NaN 的符号才会被替换
+        let r = repair("{a: -1, b: NaN}");
+        assert_eq!(r.text, r#"{"a": -1, "b": null}"#);
+    }
+}
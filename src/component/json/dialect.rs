@@ -0,0 +1,572 @@
+// src/component/json/dialect.rs
+//
+// 输入方言：严格 JSON / JSONC / JSON5 / NDJSON（JSON Lines）。
+//
+// - Strict：直接 serde_json。
+// - JSONC：把注释与尾随逗号**原位替换为空格**后交给 serde_json，
+//   因此报错的行列号与原文完全一致。
+// - JSON5：手写递归下降解析器（单引号、未加引号的 key、十六进制、`.5` / `5.`、`+1`、
+//   Infinity / NaN、行尾续行等）。serde_json 无法表示 Infinity / NaN，解析为 null，
+//   并逐处返回警告（界面上提示，不静默丢值）。
+// - NDJSON：逐行独立解析，空行跳过，每行的错误单独报告。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InputDialect {
+    Strict,
+    Jsonc,
+    Json5,
+    Ndjson,
+}
+
+impl InputDialect {
+    pub const ALL: [InputDialect; 4] = [
+        InputDialect::Strict,
+        InputDialect::Jsonc,
+        InputDialect::Json5,
+        InputDialect::Ndjson,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputDialect::Strict => "JSON",
+            InputDialect::Jsonc => "JSONC",
+            InputDialect::Json5 => "JSON5",
+            InputDialect::Ndjson => "NDJSON",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            InputDialect::Strict => "严格 RFC 8259",
+            InputDialect::Jsonc => "允许注释与尾随逗号",
+            InputDialect::Json5 => "允许注释、单引号、无引号 key、十六进制等",
+            InputDialect::Ndjson => "每行一个 JSON，逐行格式化",
+        }
+    }
+}
+
+/// 解析错误：1 起行号 + 行内字节列（与 serde_json 一致，指向出错字符之后）
+#[derive(Clone, PartialEq)]
+pub struct DialectError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl DialectError {
+    fn from_serde(e: &serde_json::Error, line_offset: usize) -> Self {
+        // serde_json 的 Display 自带 " at line X column Y"，这里只取描述部分
+        let msg = e.to_string();
+        let message = msg
+            .rsplit_once(" at line ")
+            .map_or(msg.as_str(), |(head, _)| head)
+            .to_string();
+        Self {
+            message,
+            line: e.line() + line_offset,
+            column: e.column(),
+        }
+    }
+}
+
+/// 解析单文档方言（Strict / JSONC / JSON5）。NDJSON 请用 `parse_ndjson`。
+/// 第二项是有损转换的警告（目前只有 JSON5 的 Infinity / NaN 改为 null）。
+pub fn parse_document(
+    src: &str,
+    dialect: InputDialect,
+) -> Result<(Value, Vec<String>), DialectError> {
+    match dialect {
+        InputDialect::Jsonc => serde_json::from_str(&strip_jsonc(src))
+            .map(|v| (v, Vec::new()))
+            .map_err(|e| DialectError::from_serde(&e, 0)),
+        InputDialect::Json5 => Json5Parser::new(src).parse(),
+        InputDialect::Strict | InputDialect::Ndjson => serde_json::from_str(src)
+            .map(|v| (v, Vec::new()))
+            .map_err(|e| DialectError::from_serde(&e, 0)),
+    }
+}
+
+/// NDJSON：返回每个非空行的（1 起行号，解析结果）
+pub fn parse_ndjson(src: &str) -> Vec<(usize, Result<Value, DialectError>)> {
+    src.split('\n')
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            let l = l.strip_suffix('\r').unwrap_or(l);
+            (
+                i + 1,
+                serde_json::from_str(l).map_err(|e| DialectError::from_serde(&e, i)),
+            )
+        })
+        .collect()
+}
+
+/// JSONC -> JSON：注释和尾随逗号按字节替换为空格（换行保留），不改变任何位置。
+fn strip_jsonc(src: &str) -> String {
+    let bytes = src.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+    // 最近一个未被消费的逗号位置（其后只有空白 / 注释）
+    let mut pending_comma: Option<usize> = None;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                pending_comma = None;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let start = i;
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    i += 1;
+                }
+                let end = (i + 2).min(bytes.len());
+                for b in &mut out[start..end] {
+                    if *b != b'\n' {
+                        *b = b' ';
+                    }
+                }
+                i = end;
+            }
+            b',' => {
+                pending_comma = Some(i);
+                i += 1;
+            }
+            b'}' | b']' => {
+                if let Some(p) = pending_comma.take() {
+                    out[p] = b' ';
+                }
+                i += 1;
+            }
+            b' ' | b'\t' | b'\r' | b'\n' => i += 1,
+            _ => {
+                pending_comma = None;
+                i += 1;
+            }
+        }
+    }
+    // 只替换了 ASCII 字节或整段注释（注释内的多字节字符全部被替换为空格），仍是合法 UTF-8
+    String::from_utf8(out).unwrap_or_else(|_| src.to_string())
+}
+
+// ================= JSON5 =================
+
+struct Json5Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    /// Infinity / NaN 改为 null 的位置说明
+    warnings: Vec<String>,
+}
+
+impl<'a> Json5Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<(Value, Vec<String>), DialectError> {
+        self.skip_ws()?;
+        let v = self.value()?;
+        self.skip_ws()?;
+        if self.pos < self.src.len() {
+            return Err(self.error("文档结束后还有多余内容"));
+        }
+        Ok((v, self.warnings))
+    }
+
+    fn error(&self, msg: &str) -> DialectError {
+        let before = &self.src[..self.pos.min(self.src.len())];
+        let line_start = before.rfind('\n').map_or(0, |p| p + 1);
+        let at = self.src[self.pos.min(self.src.len())..]
+            .chars()
+            .next()
+            .map_or(0, char::len_utf8);
+        DialectError {
+            message: msg.to_string(),
+            line: before.matches('\n').count() + 1,
+            column: self.pos - line_start + at,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_ws(&mut self) -> Result<(), DialectError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() || c == '\u{feff}' => {
+                    self.bump();
+                }
+                Some('/') if self.src[self.pos..].starts_with("//") => {
+                    let rest = &self.src[self.pos..];
+                    self.pos += rest.find('\n').unwrap_or(rest.len());
+                }
+                Some('/') if self.src[self.pos..].starts_with("/*") => {
+                    match self.src[self.pos + 2..].find("*/") {
+                        Some(end) => self.pos += end + 4,
+                        None => return Err(self.error("块注释未闭合")),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, DialectError> {
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') | Some('\'') => Ok(Value::String(self.string()?)),
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'I' | 'N') => {
+                self.number()
+            }
+            Some(c) if is_ident_start(c) => {
+                let start = self.pos;
+                let word = self.identifier();
+                match word.as_str() {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "null" => Ok(Value::Null),
+                    _ => {
+                        self.pos = start;
+                        Err(self.error(&format!("无法识别的值 {}", word)))
+                    }
+                }
+            }
+            Some(_) => Err(self.error("此处应为一个值")),
+            None => Err(self.error("文档意外结束")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, DialectError> {
+        self.bump(); // {
+        let mut map = serde_json::Map::new();
+        loop {
+            self.skip_ws()?;
+            if self.eat('}') {
+                return Ok(Value::Object(map));
+            }
+            let key = match self.peek() {
+                Some('"') | Some('\'') => self.string()?,
+                Some(c) if is_ident_start(c) => self.identifier(),
+                _ => return Err(self.error("此处应为 key")),
+            };
+            self.skip_ws()?;
+            if !self.eat(':') {
+                return Err(self.error("key 后应为 :"));
+            }
+            self.skip_ws()?;
+            let v = self.value()?;
+            map.insert(key, v);
+            self.skip_ws()?;
+            if self.eat(',') {
+                continue;
+            }
+            if self.eat('}') {
+                return Ok(Value::Object(map));
+            }
+            return Err(self.error("此处应为 , 或 }"));
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, DialectError> {
+        self.bump(); // [
+        let mut arr = Vec::new();
+        loop {
+            self.skip_ws()?;
+            if self.eat(']') {
+                return Ok(Value::Array(arr));
+            }
+            arr.push(self.value()?);
+            self.skip_ws()?;
+            if self.eat(',') {
+                continue;
+            }
+            if self.eat(']') {
+                return Ok(Value::Array(arr));
+            }
+            return Err(self.error("此处应为 , 或 ]"));
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if is_ident_start(c) || c.is_ascii_digit() || c == '\u{200c}' || c == '\u{200d}' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        self.src[start..self.pos].to_string()
+    }
+
+    fn string(&mut self) -> Result<String, DialectError> {
+        let quote = self.bump().unwrap_or('"');
+        let mut s = String::new();
+        loop {
+            let Some(c) = self.bump() else {
+                return Err(self.error("字符串未闭合"));
+            };
+            match c {
+                c if c == quote => return Ok(s),
+                '\n' => return Err(self.error("字符串中不能直接换行（可用 \\ 续行）")),
+                '\\' => {
+                    let Some(e) = self.bump() else {
+                        return Err(self.error("字符串未闭合"));
+                    };
+                    match e {
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'n' => s.push('\n'),
+                        'r' => {
+                            // 续行：\ + CRLF
+                            if !self.eat('\n') {
+                                s.push('\r');
+                            }
+                        }
+                        't' => s.push('\t'),
+                        'v' => s.push('\u{b}'),
+                        '0' => s.push('\0'),
+                        '\n' | '\u{2028}' | '\u{2029}' => {}
+                        'x' => {
+                            let code = self.hex_digits(2)?;
+                            s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        'u' => {
+                            let hi = self.hex_digits(4)?;
+                            // 代理对
+                            if (0xD800..0xDC00).contains(&hi)
+                                && self.src[self.pos..].starts_with("\\u")
+                            {
+                                self.pos += 2;
+                                let lo = self.hex_digits(4)?;
+                                let code = 0x10000
+                                    + ((hi - 0xD800) << 10)
+                                    + (lo.wrapping_sub(0xDC00) & 0x3FF);
+                                s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                            } else {
+                                s.push(char::from_u32(hi).unwrap_or('\u{fffd}'));
+                            }
+                        }
+                        other => s.push(other),
+                    }
+                }
+                other => s.push(other),
+            }
+        }
+    }
+
+    fn hex_digits(&mut self, n: usize) -> Result<u32, DialectError> {
+        let digits = self.src.get(self.pos..self.pos + n).unwrap_or("");
+        match u32::from_str_radix(digits, 16) {
+            Ok(v) if digits.len() == n => {
+                self.pos += n;
+                Ok(v)
+            }
+            _ => Err(self.error("无效的十六进制转义")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, DialectError> {
+        let start = self.pos;
+        let negative = match self.peek() {
+            Some('-') => {
+                self.bump();
+                true
+            }
+            Some('+') => {
+                self.bump();
+                false
+            }
+            _ => false,
+        };
+        let rest = &self.src[self.pos..];
+        if let Some(word) = ["Infinity", "NaN"]
+            .into_iter()
+            .find(|w| rest.starts_with(w))
+        {
+            self.pos += word.len();
+            let line = self.src[..start].matches('\n').count() + 1;
+            self.warnings.push(format!(
+                "第 {} 行：{} 无法用 JSON 表示，已改为 null",
+                line,
+                &self.src[start..self.pos]
+            ));
+            return Ok(Value::Null);
+        }
+        if rest.starts_with("0x") || rest.starts_with("0X") {
+            self.pos += 2;
+            let digits_start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                self.bump();
+            }
+            let digits = &self.src[digits_start..self.pos];
+            let Ok(n) = u64::from_str_radix(digits, 16) else {
+                self.pos = start;
+                return Err(self.error("无效的十六进制数字"));
+            };
+            return Ok(if negative {
+                i64::try_from(n)
+                    .map(|v| Value::from(-v))
+                    .unwrap_or_else(|_| Value::from(-(n as f64)))
+            } else {
+                Value::from(n)
+            });
+        }
+
+        // 十进制：整数部分、小数部分都可以省略其一
+        let mut text = String::new();
+        if negative {
+            text.push('-');
+        }
+        let int_start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        let int_part = &self.src[int_start..self.pos];
+        text.push_str(if int_part.is_empty() { "0" } else { int_part });
+        if self.eat('.') {
+            let frac_start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+            }
+            let frac = &self.src[frac_start..self.pos];
+            if int_part.is_empty() && frac.is_empty() {
+                self.pos = start;
+                return Err(self.error("无效的数字"));
+            }
+            text.push('.');
+            text.push_str(if frac.is_empty() { "0" } else { frac });
+        } else if int_part.is_empty() {
+            self.pos = start;
+            return Err(self.error("无效的数字"));
+        }
+        if matches!(self.peek(), Some('e') | Some('E')) {
+            let exp_start = self.pos;
+            self.bump();
+            if matches!(self.peek(), Some('+') | Some('-')) {
+                self.bump();
+            }
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+            }
+            text.push_str(&self.src[exp_start..self.pos]);
+        }
+        match serde_json::from_str::<Value>(&text) {
+            Ok(v) => Ok(v),
+            Err(_) => {
+                self.pos = start;
+                Err(self.error("无效的数字"))
+            }
+        }
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn json5(src: &str) -> (Value, Vec<String>) {
+        match parse_document(src, InputDialect::Json5) {
+            Ok(r) => r,
+            Err(e) => panic!("{}:{} {}", e.line, e.column, e.message),
+        }
+    }
+
+    #[test]
+    fn json5_syntax() {
+        let src = "// 注释\n{\n  unquoted: 'single \\'q\\'',\n  $id_1: 0x1F, neg: -0xA,\n  lead: .5, trail: 5., plus: +1,\n  /* 块注释 */ list: [1, 2,],\n  cont: 'a\\\n b',\n}\n";
+        let (v, warnings) = json5(src);
+        assert_eq!(
+            v,
+            json!({
+                "unquoted": "single 'q'",
+                "$id_1": 31, "neg": -10,
+                "lead": 0.5, "trail": 5.0, "plus": 1,
+                "list": [1, 2],
+                "cont": "a b",
+            })
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn non_finite_numbers_become_null_with_warnings() {
+        let (v, warnings) = json5("{a: Infinity,\n b: -Infinity, c: NaN}");
+        assert_eq!(v, json!({"a": null, "b": null, "c": null}));
+        assert_eq!(
+            warnings,
+            [
+                "第 1 行：Infinity 无法用 JSON 表示，已改为 null",
+                "第 2 行：-Infinity 无法用 JSON 表示，已改为 null",
+                "第 2 行：NaN 无法用 JSON 表示，已改为 null",
+            ]
+        );
+    }
+
+    #[test]
+    fn json5_errors_report_position() {
+        let e = parse_document("{a: 1,\n  b: }", InputDialect::Json5)
+            .err()
+            .unwrap();
+        assert_eq!(e.line, 2);
+        let e = parse_document("{a: 1} x", InputDialect::Json5)
+            .err()
+            .unwrap();
+        assert_eq!(e.message, "文档结束后还有多余内容");
+        let e = parse_document("/* 未闭合", InputDialect::Json5)
+            .err()
+            .unwrap();
+        assert_eq!(e.message, "块注释未闭合");
+    }
+
+    #[test]
+    fn jsonc_keeps_positions() {
+        let (v, _) = parse_document("{\"a\": 1, // x\n}", InputDialect::Jsonc)
+            .ok()
+            .unwrap();
+        assert_eq!(v, json!({"a": 1}));
+        let e = parse_document("/* c */ {\"a\": }", InputDialect::Jsonc)
+            .err()
+            .unwrap();
+        assert_eq!((e.line, e.column), (1, 15));
+    }
+}
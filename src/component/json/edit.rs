@@ -0,0 +1,240 @@
+// src/component/json/edit.rs
+//
+// 树形视图的编辑操作。每个操作以“源文档中的路径”定位节点，作用在解析后（排序 / 查询之前）
+// 的值上，再由界面序列化写回输入框，因此文本视图与树形视图始终一致。
+
+use serde_json::{Map, Value};
+
+/// 路径中的一段：对象 key 或数组下标
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Seg {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Clone, PartialEq)]
+pub enum TreeEdit {
+    /// 替换节点的值（编辑标量或切换类型）
+    Set(Vec<Seg>, Value),
+    /// 重命名对象成员（保持原位置）
+    Rename(Vec<Seg>, String),
+    Delete(Vec<Seg>),
+    /// 复制节点，插入到原节点之后
+    Duplicate(Vec<Seg>),
+    /// 在容器末尾追加成员 / 元素（值为 null）
+    Add(Vec<Seg>),
+    /// 数组内移动元素：容器路径、原下标、目标下标
+    Move(Vec<Seg>, usize, usize),
+}
+
+/// 类型切换下拉框中的选项
+pub const TYPES: [&str; 6] = ["string", "number", "boolean", "null", "object", "array"];
+
+pub fn type_name(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// 把值转换为另一类型，尽量保留内容（"12" -> 12，1 -> true，标量 -> 单元素数组等）
+pub fn convert_type(v: &Value, to: &str) -> Value {
+    if type_name(v) == to {
+        return v.clone();
+    }
+    let as_text = match v {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    };
+    match to {
+        "string" => Value::String(as_text),
+        "number" => match v {
+            Value::Bool(b) => Value::from(*b as u8),
+            _ => match serde_json::from_str::<Value>(as_text.trim()) {
+                Ok(n @ Value::Number(_)) => n,
+                _ => Value::from(0),
+            },
+        },
+        "boolean" => Value::Bool(match v {
+            Value::String(s) => matches!(s.trim(), "true" | "1" | "yes"),
+            Value::Number(n) => n.as_f64().is_some_and(|f| f != 0.0),
+            Value::Array(a) => !a.is_empty(),
+            Value::Object(m) => !m.is_empty(),
+            _ => false,
+        }),
+        "object" => match v {
+            Value::Array(a) => Value::Object(
+                a.iter()
+                    .enumerate()
+                    .map(|(i, x)| (i.to_string(), x.clone()))
+                    .collect(),
+            ),
+            Value::Null => Value::Object(Map::new()),
+            other => Value::Object(Map::from_iter([("value".to_string(), other.clone())])),
+        },
+        "array" => match v {
+            Value::Object(m) => Value::Array(m.values().cloned().collect()),
+            Value::Null => Value::Array(Vec::new()),
+            other => Value::Array(vec![other.clone()]),
+        },
+        _ => Value::Null,
+    }
+}
+
+/// 按路径取节点
+pub fn get<'a>(root: &'a Value, path: &[Seg]) -> Option<&'a Value> {
+    path.iter().try_fold(root, |cur, seg| match (seg, cur) {
+        (Seg::Key(k), Value::Object(m)) => m.get(k),
+        (Seg::Index(i), Value::Array(a)) => a.get(*i),
+        _ => None,
+    })
+}
+
+pub fn get_mut<'a>(root: &'a mut Value, path: &[Seg]) -> Result<&'a mut Value, String> {
+    let mut cur = root;
+    for seg in path {
+        cur = match (seg, cur) {
+            (Seg::Key(k), Value::Object(m)) => m.get_mut(k),
+            (Seg::Index(i), Value::Array(a)) => a.get_mut(*i),
+            _ => None,
+        }
+        .ok_or_else(|| "节点已不存在，请刷新后重试".to_string())?;
+    }
+    Ok(cur)
+}
+
+/// 在对象中 `after` 之后插入 key（serde_json 的 Map 不支持按位置插入，只能重建）
+fn insert_after(m: &mut Map<String, Value>, after: &str, key: String, value: Value) {
+    let old = std::mem::take(m);
+    let mut pending = Some((key, value));
+    for (k, v) in old {
+        let hit = k == after;
+        m.insert(k, v);
+        if hit {
+            if let Some((nk, nv)) = pending.take() {
+                m.insert(nk, nv);
+            }
+        }
+    }
+    if let Some((nk, nv)) = pending {
+        m.insert(nk, nv);
+    }
+}
+
+/// 生成不与现有 key 冲突的名字：base、base_2、base_3…
+fn unique_key(m: &Map<String, Value>, base: &str) -> String {
+    if !m.contains_key(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}_{}", base, n))
+        .find(|k| !m.contains_key(k))
+        .unwrap_or_default()
+}
+
+pub fn apply(root: &mut Value, edit: &TreeEdit) -> Result<(), String> {
+    match edit {
+        TreeEdit::Set(path, v) => {
+            *get_mut(root, path)? = v.clone();
+        }
+        TreeEdit::Add(path) => match get_mut(root, path)? {
+            Value::Object(m) => {
+                let k = unique_key(m, "newKey");
+                m.insert(k, Value::Null);
+            }
+            Value::Array(a) => a.push(Value::Null),
+            _ => return Err("只能向对象或数组添加成员".into()),
+        },
+        TreeEdit::Rename(path, _) | TreeEdit::Delete(path) | TreeEdit::Duplicate(path) => {
+            let Some((last, parent_path)) = path.split_last() else {
+                return Err("不能对根节点执行此操作".into());
+            };
+            match (get_mut(root, parent_path)?, last) {
+                (Value::Object(m), Seg::Key(k)) if !m.contains_key(k) => {
+                    return Err("键不存在".into());
+                }
+                (Value::Object(m), Seg::Key(k)) => match edit {
+                    TreeEdit::Rename(_, new_key) => {
+                        if new_key == k {
+                            return Ok(());
+                        }
+                        if m.contains_key(new_key) {
+                            return Err(format!("key \"{}\" 已存在", new_key));
+                        }
+                        let old = std::mem::take(m);
+                        *m = old
+                            .into_iter()
+                            .map(|(ok, v)| {
+                                if &ok == k {
+                                    (new_key.clone(), v)
+                                } else {
+                                    (ok, v)
+                                }
+                            })
+                            .collect();
+                    }
+                    TreeEdit::Delete(_) => {
+                        m.shift_remove(k);
+                    }
+                    _ => {
+                        let v = m[k].clone();
+                        let nk = unique_key(m, &format!("{}_copy", k));
+                        insert_after(m, k, nk, v);
+                    }
+                },
+                (Value::Array(a), Seg::Index(i)) if *i < a.len() => match edit {
+                    TreeEdit::Rename(..) => return Err("数组元素没有 key".into()),
+                    TreeEdit::Delete(_) => {
+                        a.remove(*i);
+                    }
+                    _ => {
+                        let v = a[*i].clone();
+                        a.insert(*i + 1, v);
+                    }
+                },
+                _ => return Err("节点已不存在，请刷新后重试".into()),
+            }
+        }
+        TreeEdit::Move(path, from, to) => {
+            let Value::Array(a) = get_mut(root, path)? else {
+                return Err("只能在数组内拖动排序".into());
+            };
+            if *from >= a.len() || *to >= a.len() {
+                return Err("下标越界".into());
+            }
+            let v = a.remove(*from);
+            a.insert(*to, v);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rename_keeps_order_and_rejects_missing_keys() {
+        let mut v = json!({"a": 1, "b": 2});
+        apply(
+            &mut v,
+            &TreeEdit::Rename(vec![Seg::Key("a".into())], "c".into()),
+        )
+        .unwrap();
+        assert_eq!(serde_json::to_string(&v).unwrap(), r#"{"c":1,"b":2}"#);
+        for edit in [
+            TreeEdit::Rename(vec![Seg::Key("x".into())], "y".into()),
+            TreeEdit::Delete(vec![Seg::Key("x".into())]),
+            TreeEdit::Duplicate(vec![Seg::Key("x".into())]),
+        ] {
+            assert_eq!(apply(&mut v, &edit), Err("键不存在".to_string()));
+        }
+        assert_eq!(v, json!({"c": 1, "b": 2}));
+    }
+}
@@ -0,0 +1,123 @@
+// src/component/json/embed.rs
+//
+// 嵌入的 JSON 字符串：日志里常把 JSON 序列化后再作为字符串值写入（`"{\"a\":1}"`），可能套好几层。
+//
+// - 展开：字符串内容本身能解析为对象 / 数组时替换为解析结果，并继续展开其中的字符串；
+//   多次转义的（解析出来仍是字符串）逐层剥开。每个展开点记录路径与原始字符串。
+// - 还原：按记录把展开点换回原始字符串，复制时得到与输入一致的内容。
+// - 整体转义 / 去转义：把整个文档写成 JSON 字符串字面量，或反过来。
+
+use super::edit::Seg;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 展开点：路径 -> 原始字符串
+pub type Embedded = HashMap<Vec<Seg>, String>;
+
+/// 字符串逐层剥开的最多层数
+const MAX_LAYERS: usize = 8;
+
+/// 字符串内容是 JSON 对象 / 数组（可能多次转义）时返回解析结果
+fn parse_embedded(s: &str) -> Option<Value> {
+    let mut text = s.trim().to_string();
+    for _ in 0..MAX_LAYERS {
+        if !(text.starts_with(['{', '[', '"'])) {
+            return None;
+        }
+        match serde_json::from_str::<Value>(&text).ok()? {
+            v @ (Value::Object(_) | Value::Array(_)) => return Some(v),
+            Value::String(inner) => text = inner.trim().to_string(),
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// 原地展开 `v` 中的嵌入 JSON；`path` 为 `v` 在文档中的路径
+pub fn expand(v: &mut Value, path: &mut Vec<Seg>, out: &mut Embedded) {
+    match v {
+        Value::String(s) => {
+            if let Some(mut inner) = parse_embedded(s) {
+                out.insert(path.clone(), std::mem::take(s));
+                expand(&mut inner, path, out);
+                *v = inner;
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                path.push(Seg::Index(i));
+                expand(item, path, out);
+                path.pop();
+            }
+        }
+        Value::Object(map) => {
+            for (k, item) in map.iter_mut() {
+                path.push(Seg::Key(k.clone()));
+                expand(item, path, out);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 文档中 `base` 处节点的副本，其中展开过的节点换回原始字符串
+pub fn collapse(v: &Value, base: &[Seg], embedded: &Embedded) -> Value {
+    if let Some(s) = embedded.get(base) {
+        return Value::String(s.clone());
+    }
+    let mut path = base.to_vec();
+    match v {
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    path.push(Seg::Index(i));
+                    let c = collapse(item, &path, embedded);
+                    path.pop();
+                    c
+                })
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, item)| {
+                    path.push(Seg::Key(k.clone()));
+                    let c = collapse(item, &path, embedded);
+                    path.pop();
+                    (k.clone(), c)
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// 整个文档压缩后写成 JSON 字符串字面量
+pub fn stringify(v: &Value) -> String {
+    let compact = serde_json::to_string(v).unwrap_or_default();
+    serde_json::to_string(&compact).unwrap_or_default()
+}
+
+/// 把 JSON 字符串字面量还原为其内容；多次转义时剥到不再是字符串为止。
+/// 也接受日志里常见的去掉了首尾引号的形式（`{\"a\":1}`）。
+pub fn unstringify(text: &str) -> Result<String, String> {
+    let t = text.trim();
+    let literal = if t.starts_with('"') {
+        t.to_string()
+    } else {
+        format!("\"{}\"", t)
+    };
+    let mut s = match serde_json::from_str::<Value>(&literal) {
+        Ok(Value::String(s)) => s,
+        _ => return Err("不是有效的 JSON 字符串字面量".into()),
+    };
+    for _ in 0..MAX_LAYERS {
+        match serde_json::from_str::<Value>(s.trim()) {
+            Ok(Value::String(inner)) => s = inner,
+            _ => break,
+        }
+    }
+    Ok(s)
+}
@@ -0,0 +1,123 @@
+// src/component/json/find.rs
+//
+// 树形视图查找：在树形视图显示的文档上按 key 和 / 或标量值匹配。
+//
+// 结果包括：匹配节点（文档顺序，供上一处 / 下一处导航）、每个节点匹配的部分（key 或值，用于高亮），
+// 以及匹配节点的所有祖先。祖先需要自动展开；一并记下要挂载到第几个子节点，
+// 这样超出分页的匹配也能通过放开“显示更多”挂载出来。
+
+use super::edit::Seg;
+use crate::component::util::search::Matcher;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 匹配范围
+#[derive(Clone, Copy, PartialEq)]
+pub enum FindScope {
+    Keys,
+    Values,
+    Both,
+}
+
+impl FindScope {
+    pub const ALL: [FindScope; 3] = [FindScope::Both, FindScope::Keys, FindScope::Values];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FindScope::Keys => "键",
+            FindScope::Values => "值",
+            FindScope::Both => "键和值",
+        }
+    }
+
+    fn keys(&self) -> bool {
+        matches!(self, FindScope::Keys | FindScope::Both)
+    }
+
+    fn values(&self) -> bool {
+        matches!(self, FindScope::Values | FindScope::Both)
+    }
+}
+
+/// 节点中匹配的部分
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct Hit {
+    pub key: bool,
+    pub value: bool,
+}
+
+#[derive(Clone, PartialEq, Default)]
+pub struct TreeMatches {
+    /// 是否进行了查找（查找词为空时为 false，过滤模式不生效）
+    pub active: bool,
+    /// 匹配节点，文档顺序
+    pub hits: Vec<Vec<Seg>>,
+    pub parts: HashMap<Vec<Seg>, Hit>,
+    /// 匹配节点的祖先 -> 通往匹配的子节点中最大的序号
+    pub reveal: HashMap<Vec<Seg>, usize>,
+}
+
+impl TreeMatches {
+    /// 过滤模式下是否保留该节点：自身匹配、是匹配的祖先，或位于匹配节点之下
+    pub fn keeps(&self, path: &[Seg]) -> bool {
+        self.reveal.contains_key(path)
+            || (0..=path.len()).any(|n| self.parts.contains_key(&path[..n]))
+    }
+}
+
+/// 在 `doc` 中查找。NDJSON 时 `doc` 为记录数组，根节点本身不显示
+pub fn find_in_tree(doc: &Value, matcher: &Matcher, scope: FindScope) -> TreeMatches {
+    let mut out = TreeMatches {
+        active: true,
+        ..Default::default()
+    };
+    walk(doc, &mut Vec::new(), matcher, scope, &mut out);
+    out
+}
+
+/// 返回子树中是否有匹配
+fn walk(
+    v: &Value,
+    path: &mut Vec<Seg>,
+    matcher: &Matcher,
+    scope: FindScope,
+    out: &mut TreeMatches,
+) -> bool {
+    let mut hit = Hit::default();
+    if let (true, Some(Seg::Key(k))) = (scope.keys(), path.last()) {
+        hit.key = matcher.is_match(k);
+    }
+    if scope.values() {
+        hit.value = match v {
+            Value::String(s) => matcher.is_match(s),
+            Value::Number(n) => matcher.is_match(&n.to_string()),
+            Value::Bool(b) => matcher.is_match(if *b { "true" } else { "false" }),
+            Value::Null => matcher.is_match("null"),
+            _ => false,
+        };
+    }
+    let mut found = hit.key || hit.value;
+    if found {
+        out.hits.push(path.clone());
+        out.parts.insert(path.clone(), hit);
+    }
+
+    let children: Box<dyn Iterator<Item = (Seg, &Value)>> = match v {
+        Value::Object(m) => Box::new(m.iter().map(|(k, c)| (Seg::Key(k.clone()), c))),
+        Value::Array(a) => Box::new(a.iter().enumerate().map(|(i, c)| (Seg::Index(i), c))),
+        _ => return found,
+    };
+    let mut last = None;
+    for (i, (seg, child)) in children.enumerate() {
+        path.push(seg);
+        if walk(child, path, matcher, scope, out) {
+            last = Some(i);
+        }
+        path.pop();
+    }
+    if let Some(i) = last {
+        out.reveal.insert(path.clone(), i);
+        found = true;
+    }
+    found
+}
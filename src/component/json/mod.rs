@@ -1,48 +1,432 @@
-use crate::component::util::{highlight::highlight_json_with_search, search::SearchBar};
+mod codegen;
+mod diagnose;
+mod dialect;
+mod edit;
+mod embed;
+mod find;
+mod number;
+mod path;
+mod printer;
+mod query;
+mod replace;
+mod schema;
+mod shape;
+mod window;
+mod writeback;
+
+use crate::component::persist;
+use crate::component::util::{
+    highlight::{highlight_json_from, highlight_json_with_search},
+    offset::utf16_range,
+    search::{
+        find_matches, replace_all_matches, replace_match, Matcher, SearchBar, SearchOptions,
+    },
+};
+use codegen::{generate, TargetLang};
 use dioxus::prelude::*;
+use diagnose::{diagnose, repair, Diagnostic, Repair};
+use dialect::{parse_document, parse_ndjson, InputDialect};
+use edit::{apply, convert_type, get, type_name, Seg, TreeEdit, TYPES};
+use embed::{collapse, expand, stringify, unstringify, Embedded};
+use find::{find_in_tree, FindScope, TreeMatches};
+use number::{count_unsafe, exceeds_js_safe};
+use path::{crumb, to_json_macro, PathSyntax};
+use printer::{Indent, PrintOptions, COMPACT_WIDTHS};
+use query::run_query;
+use replace::{replace_in, ReplaceTarget};
+use schema::{detect_draft, infer, validate, Violation};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::cmp::Ordering;
+use std::ops::{Deref, Range};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use window::{RowIndex, MAX_SCROLL_PX, OVERSCAN, ROW_HEIGHT, VIRTUAL_MIN_BYTES};
+use writeback::{reformat, splice};
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum FormatMode {
     Pretty,
     Minified,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum ViewMode {
     Text,
     Tree,
+    /// 由输出生成的类型定义
+    Types,
+}
+
+/// 对象 key 的排序方式
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum KeyOrder {
+    /// 按字节（码点）序
+    Byte,
+    /// 数字段按数值比较：item2 < item10
+    Natural,
+    /// 忽略大小写，相同时再按字节序
+    CaseInsensitive,
+    /// 字节序倒序
+    Reverse,
+}
+
+impl KeyOrder {
+    const ALL: [KeyOrder; 4] = [
+        KeyOrder::Byte,
+        KeyOrder::Natural,
+        KeyOrder::CaseInsensitive,
+        KeyOrder::Reverse,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            KeyOrder::Byte => "字典序",
+            KeyOrder::Natural => "自然序",
+            KeyOrder::CaseInsensitive => "忽略大小写",
+            KeyOrder::Reverse => "倒序",
+        }
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            KeyOrder::Byte => a.cmp(b),
+            KeyOrder::Natural => natural_cmp(a, b).then_with(|| a.cmp(b)),
+            KeyOrder::CaseInsensitive => a
+                .chars()
+                .flat_map(char::to_lowercase)
+                .cmp(b.chars().flat_map(char::to_lowercase))
+                .then_with(|| a.cmp(b)),
+            KeyOrder::Reverse => b.cmp(a),
+        }
+    }
+}
+
+/// 自然序：连续数字作为整体按数值比较（前导零不计），其余字符按码点比较
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut x, mut y) = (a, b);
+    loop {
+        let (Some(cx), Some(cy)) = (x.chars().next(), y.chars().next()) else {
+            return x.len().cmp(&y.len());
+        };
+        if cx.is_ascii_digit() && cy.is_ascii_digit() {
+            let nx = x.find(|c: char| !c.is_ascii_digit()).unwrap_or(x.len());
+            let ny = y.find(|c: char| !c.is_ascii_digit()).unwrap_or(y.len());
+            let (dx, dy) = (x[..nx].trim_start_matches('0'), y[..ny].trim_start_matches('0'));
+            let o = dx.len().cmp(&dy.len()).then_with(|| dx.cmp(dy));
+            if o != Ordering::Equal {
+                return o;
+            }
+            x = &x[nx..];
+            y = &y[ny..];
+        } else {
+            if cx != cy {
+                return cx.cmp(&cy);
+            }
+            x = &x[cx.len_utf8()..];
+            y = &y[cy.len_utf8()..];
+        }
+    }
+}
+
+/// 跨重启保留的会话状态：输入缓冲区 + 输出选项。
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonSession {
+    input: String,
+    format_mode: FormatMode,
+    sort_keys: bool,
+    key_order: KeyOrder,
+    print: PrintOptions,
+    /// 展开值为 JSON 的字符串
+    expand_embedded: bool,
+    view_mode: ViewMode,
+    query: String,
+    dialect: InputDialect,
+    schema: String,
+    show_schema: bool,
+    codegen_lang: TargetLang,
+}
+impl Default for JsonSession {
+    fn default() -> Self {
+        Self {
+            input: String::new(),
+            format_mode: FormatMode::Pretty,
+            sort_keys: false,
+            key_order: KeyOrder::Byte,
+            print: PrintOptions::default(),
+            expand_embedded: false,
+            view_mode: ViewMode::Text,
+            query: String::new(),
+            dialect: InputDialect::Strict,
+            schema: String::new(),
+            show_schema: false,
+            codegen_lang: TargetLang::Rust,
+        }
+    }
 }
 
-/// 递归对 JSON 对象的 key 按字典序排序（数组元素递归排序，但数组顺序不变）。
-fn sort_value(v: Value) -> Value {
+/// 递归对 JSON 对象的 key 按 `order` 排序（数组元素递归排序，但数组顺序不变）。
+/// 值原样移动，数字保留原文。
+pub(crate) fn sort_value(v: Value, order: KeyOrder) -> Value {
     match v {
         Value::Object(map) => {
             let mut entries: Vec<(String, Value)> =
-                map.into_iter().map(|(k, val)| (k, sort_value(val))).collect();
-            entries.sort_by(|a, b| a.0.cmp(&b.0));
+                map.into_iter().map(|(k, val)| (k, sort_value(val, order))).collect();
+            entries.sort_by(|a, b| order.compare(&a.0, &b.0));
             Value::Object(entries.into_iter().collect())
         }
-        Value::Array(arr) => Value::Array(arr.into_iter().map(sort_value).collect()),
+        Value::Array(arr) => Value::Array(arr.into_iter().map(|v| sort_value(v, order)).collect()),
         other => other,
     }
 }
 
+/// 按当前格式化模式与输出选项序列化；NDJSON 的多条记录各自序列化后按行拼接。
+fn serialize_output(v: &Value, records: bool, mode: FormatMode, opts: &PrintOptions) -> String {
+    let one = |v: &Value| match mode {
+        FormatMode::Pretty => printer::pretty(v, opts),
+        FormatMode::Minified => printer::minified(v, opts),
+    };
+    let mut out = match v {
+        Value::Array(items) if records => items.iter().map(one).collect::<Vec<_>>().join("\n"),
+        _ => one(v),
+    };
+    if opts.trailing_newline {
+        out.push('\n');
+    }
+    out
+}
+
+/// 树形编辑撤销栈上限（每项为一份完整输入快照）：条数与总字节数
+const UNDO_LIMIT: usize = 100;
+const UNDO_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// 输入超过该字节数时防抖解析，树形视图默认只展开前 LARGE_EXPAND_DEPTH 层
+const LARGE_INPUT_BYTES: usize = 256 * 1024;
+const PARSE_DEBOUNCE: Duration = Duration::from_millis(300);
+const LARGE_EXPAND_DEPTH: usize = 2;
+/// 树形视图中容器一次挂载的子节点数，其余点“显示更多”再挂载；超过它的容器默认折叠
+const TREE_PAGE: usize = 200;
+
+/// 共享的文档值：克隆只增加引用计数，比较按指针，避免大文档在渲染中被深拷贝 / 深比较
+#[derive(Clone)]
+struct SharedValue(Rc<Value>);
+
+impl PartialEq for SharedValue {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for SharedValue {
+    type Target = Value;
+    fn deref(&self) -> &Value {
+        &self.0
+    }
+}
+
+/// 一次输出计算的参数（在后台线程执行）
+struct ComputeJob {
+    src: String,
+    dialect: InputDialect,
+    expand_embedded: bool,
+    /// None = 不排序
+    sort: Option<KeyOrder>,
+    query: String,
+    text_view: bool,
+    /// 类型视图：输出为生成的类型定义
+    codegen: Option<TargetLang>,
+    format: FormatMode,
+    print: PrintOptions,
+}
+
+/// 后台计算结果；`value` 为 None 表示解析或查询失败
+#[derive(Default)]
+struct Computed {
+    /// 排序 / 查询之前的文档；None 表示与 `value` 相同（未排序、未查询时不必再拷贝一份）
+    source: Option<Value>,
+    value: Option<Value>,
+    record_lines: Vec<usize>,
+    output: String,
+    /// 输出超过 VIRTUAL_MIN_BYTES 时的行索引（窗口化渲染）
+    rows: Option<RowIndex>,
+    error: String,
+    diag: Option<Diagnostic>,
+    /// 展开过的嵌入 JSON 字符串（路径相对于 `value`；有查询时路径无法对应，为空）
+    embedded: Embedded,
+    /// 超出 JS 安全整数范围的数字个数
+    unsafe_numbers: usize,
+    /// 解析时的有损转换（JSON5 的 Infinity / NaN 改为 null）
+    warnings: Vec<String>,
+}
+
+/// 输出栏右侧显示的大小与耗时
+#[derive(Clone, Copy, PartialEq)]
+struct ComputeStats {
+    input_bytes: usize,
+    output_bytes: usize,
+    elapsed: Duration,
+    unsafe_numbers: usize,
+}
+
+/// 字节数 -> "512 B" / "12.3 KB" / "4.5 MB"
+fn format_size(n: usize) -> String {
+    if n < 1024 {
+        format!("{} B", n)
+    } else if n < 1024 * 1024 {
+        format!("{:.1} KB", n as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", n as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// 解析 -> 排序 -> 查询 -> 序列化。
+/// NDJSON：每行一条记录，排序与查询逐条进行；个别行解析失败不影响其余行的输出。
+fn compute(job: ComputeJob) -> Computed {
+    let ComputeJob { src, dialect: d, expand_embedded, sort, query: q, text_view, format, print, codegen } = job;
+    let mut out = Computed::default();
+
+    // 1. 解析
+    let mut records: Vec<(usize, Value)> = Vec::new();
+    let is_records = d == InputDialect::Ndjson;
+    if is_records {
+        let mut failed = Vec::new();
+        for (line, r) in parse_ndjson(&src) {
+            match r {
+                Ok(v) => records.push((line, v)),
+                Err(e) => failed.push(e),
+            }
+        }
+        if let Some(first) = failed.first() {
+            let detail: Vec<String> = failed
+                .iter()
+                .take(3)
+                .map(|e| format!("第 {} 行: {}", e.line, e.message))
+                .collect();
+            let more = if failed.len() > 3 { "；…" } else { "" };
+            out.error = format!("{} 行解析失败：{}{}", failed.len(), detail.join("；"), more);
+            out.diag = Some(diagnose(&src, first.line, first.column));
+        }
+    } else {
+        match parse_document(&src, d) {
+            Ok((v, warnings)) => {
+                records.push((0, v));
+                out.warnings = warnings;
+            }
+            Err(e) => {
+                out.error = format!("{} 解析错误: {}", d.label(), e.message);
+                out.diag = Some(diagnose(&src, e.line, e.column));
+                return out;
+            }
+        }
+    }
+    if expand_embedded || sort.is_some() || !q.trim().is_empty() {
+        out.source = Some(match records.as_slice() {
+            [(_, v)] if !is_records => v.clone(),
+            _ => Value::Array(records.iter().map(|(_, v)| v.clone()).collect()),
+        });
+    }
+
+    // 2. 展开嵌入的 JSON 字符串（在查询之前，查询可以深入其中）
+    if expand_embedded {
+        for (i, (_, v)) in records.iter_mut().enumerate() {
+            let mut path = if is_records { vec![Seg::Index(i)] } else { Vec::new() };
+            expand(v, &mut path, &mut out.embedded);
+        }
+        if !q.trim().is_empty() {
+            out.embedded.clear();
+        }
+    }
+
+    // 3. 按 key 排序 / 4. 查询：输出面板只展示查询结果
+    for (_, v) in records.iter_mut() {
+        if let Some(order) = sort {
+            *v = sort_value(std::mem::take(v), order);
+        }
+        if !q.trim().is_empty() {
+            match run_query(&q, v) {
+                Ok(r) => *v = r,
+                Err(e) => {
+                    out.error = format!("查询错误: {}", e);
+                    return out;
+                }
+            }
+        }
+    }
+
+    let value = if is_records {
+        out.record_lines = records.iter().map(|(l, _)| *l).collect();
+        Value::Array(records.into_iter().map(|(_, v)| v).collect())
+    } else {
+        records.pop().map(|(_, v)| v).unwrap_or_default()
+    };
+
+    // 5. 文本模式序列化；类型视图生成类型定义（NDJSON 各条记录合并）；树形模式由 value 渲染，无需 output
+    if let Some(lang) = codegen {
+        out.output = match (&value, is_records) {
+            (Value::Array(items), true) => generate(items, lang, "Root"),
+            _ => generate([&value], lang, "Root"),
+        };
+    } else if text_view {
+        let s = serialize_output(&value, is_records, format, &print);
+        if s.len() > VIRTUAL_MIN_BYTES {
+            out.rows = Some(RowIndex::build(&s));
+        }
+        out.output = s;
+    }
+    out.unsafe_numbers = count_unsafe(&value);
+    out.value = Some(value);
+    out
+}
+
+/// 写入剪贴板，并在 `button_id` 对应的按钮上短暂显示结果
+fn copy_to_clipboard(text: &str, button_id: &str) {
+    let escaped = serde_json::to_string(text).unwrap_or_else(|_| "\"\"".to_string());
+    let js = format!(
+        "navigator.clipboard.writeText({t}).then(function(){{var b=document.getElementById('{id}');if(b){{var o=b.textContent;b.textContent='已复制 ✓';setTimeout(function(){{b.textContent=o;}},1200);}}}}).catch(function(){{var b=document.getElementById('{id}');if(b){{var o=b.textContent;b.textContent='复制失败';setTimeout(function(){{b.textContent=o;}},1200);}}}});",
+        t = escaped,
+        id = button_id
+    );
+    dioxus::document::eval(&js);
+}
+
+/// 在输入 textarea 中选中 [start, end)（字节位置）并把该行滚到可视区上部。
+fn select_input_range(text: &str, start: usize, end: usize) {
+    // 字节位置 -> UTF-16 索引（JS setSelectionRange 的单位），并扩到完整的字素簇
+    let Range { start: s, end: e } = utf16_range(text, start..end);
+    let js = format!(
+        "(function(){{var el=document.getElementById('json-input');if(!el)return;el.focus();try{{el.setSelectionRange({s},{e});}}catch(_){{}}var lh=parseFloat(getComputedStyle(el).lineHeight)||20;var lines=el.value.slice(0,{s}).split('\\n').length-1;el.scrollTop=Math.max(0,lines*lh-el.clientHeight/3);}})();",
+        s = s, e = e
+    );
+    dioxus::document::eval(&js);
+}
+
 #[component]
 pub fn JsonFormatterTool() -> Element {
+    // 上次会话保存的输入与选项
+    let mut session = use_context::<Signal<JsonSession>>();
+    let restored = session.peek().clone();
+
     // ------- 编辑缓冲区（左侧输入） -------
-    let mut input = use_signal(String::new);
+    let mut input = use_signal(|| restored.input.clone());
 
     // ------- 输出与错误 -------
     let mut output = use_signal(String::new);
     let mut error_message = use_signal(String::new);
+    // 解析失败时的定位信息，以及待确认的自动修复
+    let mut parse_diag = use_signal(|| Option::<Diagnostic>::None);
+    let mut repair_preview = use_signal(|| Option::<Repair>::None);
 
     // ------- 查找/替换状态（输入面板） -------
     let mut input_show_find = use_signal(|| false);
     let mut input_show_replace = use_signal(|| false);
     let mut input_find_query = use_signal(String::new);
     let input_replace_query = use_signal(String::new);
-    let mut input_match_positions = use_signal(Vec::<usize>::new);
+    let input_search_options = use_signal(SearchOptions::default);
+    // 替换范围：原始文本，或按结构只替换键 / 字符串值 / JSONPath 选中的值
+    let mut replace_target = use_signal(|| ReplaceTarget::Text);
+    let mut replace_path = use_signal(String::new);
+    let mut input_match_positions = use_signal(Vec::<Range<usize>>::new);
     let mut input_current_match_idx = use_signal(|| 0usize);
 
     // ------- 查找/替换状态（输出面板） -------
@@ -50,7 +434,8 @@ pub fn JsonFormatterTool() -> Element {
     let mut output_show_replace = use_signal(|| false);
     let mut output_find_query = use_signal(String::new);
     let output_replace_query = use_signal(String::new);
-    let mut output_match_positions = use_signal(Vec::<usize>::new);
+    let output_search_options = use_signal(SearchOptions::default);
+    let mut output_match_positions = use_signal(Vec::<Range<usize>>::new);
     let mut output_current_match_idx = use_signal(|| 0usize);
 
     // 在“输出区（格式化后）”上叠加语法高亮 + 搜索高亮
@@ -60,107 +445,432 @@ pub fn JsonFormatterTool() -> Element {
     let mut active_panel = use_signal(|| "input".to_string());
 
     // ------- 输出控制 -------
-    let mut format_mode = use_signal(|| FormatMode::Pretty); // 格式化 / 压缩
-    let mut sort_keys = use_signal(|| false); // 是否按 key 排序
-    let mut view_mode = use_signal(|| ViewMode::Text); // 文本 / 树形
-    // 解析 + 排序后的值（树形视图用）
-    let mut processed_value = use_signal(|| Option::<Value>::None);
-
-    // ========== 输出计算：解析 -> 排序 -> 序列化 ==========
-    // 依赖 input / sort_keys / format_mode / view_mode，任一变化自动重算。
-    {
-        use_effect(move || {
-            let src = input.read().clone();
-            if src.trim().is_empty() {
-                output.set(String::new());
-                error_message.set(String::new());
-                processed_value.set(None);
-                return;
-            }
+    let mut format_mode = use_signal(|| restored.format_mode); // 格式化 / 压缩
+    let mut sort_keys = use_signal(|| restored.sort_keys); // 是否按 key 排序
+    let mut key_order = use_signal(|| restored.key_order); // key 排序方式
+    let mut print_options = use_signal(|| restored.print); // 缩进 / 单行合并 / 转义 / 末尾换行
+    let mut show_print_options = use_signal(|| false);
+    let mut view_mode = use_signal(|| restored.view_mode); // 文本 / 树形 / 类型
+    let mut codegen_lang = use_signal(|| restored.codegen_lang); // 类型视图的目标语言
+    let mut query = use_signal(|| restored.query.clone()); // JSONPath / jq 查询（空=不过滤）
+    let mut dialect = use_signal(|| restored.dialect); // 输入方言
+    // 解析 + 排序后的值（树形视图用）；NDJSON 时为各条记录组成的数组
+    let mut processed_value = use_signal(|| Option::<SharedValue>::None);
+    // NDJSON 各条记录在输入中的行号（非空 = processed_value 是记录数组）
+    let mut record_lines = use_signal(Vec::<usize>::new);
+    // 超大输出的行索引（Some = 文本视图窗口化渲染）
+    let mut output_rows = use_signal(|| Option::<RowIndex>::None);
+    // 后台计算：进行中标记、最近一次的大小与耗时、代号（丢弃过期结果）
+    let mut computing = use_signal(|| false);
+    let mut stats = use_signal(|| Option::<ComputeStats>::None);
+    let mut parse_warnings = use_signal(Vec::<String>::new);
+    let mut compute_gen = use_signal(|| 0u64);
+    // NDJSON 树形视图已挂载的记录数
+    let mut tree_records_shown = use_signal(|| TREE_PAGE);
 
-            // 1. 解析
-            let mut v: Value = match serde_json::from_str(&src) {
-                Ok(v) => v,
-                Err(e) => {
-                    output.set(String::new());
-                    error_message.set(format!("JSON 解析错误: {}", e));
-                    processed_value.set(None);
+    // ------- JSON Schema 面板 -------
+    let mut show_schema = use_signal(|| restored.show_schema);
+    let mut schema_text = use_signal(|| restored.schema.clone());
+    // (NDJSON 记录下标, 违规)；单文档时下标为 None
+    let mut schema_issues = use_signal(Vec::<(Option<usize>, Violation)>::new);
+    let mut schema_error = use_signal(String::new);
+
+    // ------- 树形编辑 -------
+    let mut edit_mode = use_signal(|| false);
+    // 解析后、排序 / 查询之前的文档（NDJSON 为各条成功记录组成的数组），编辑在其上进行
+    let mut source_value = use_signal(|| Option::<SharedValue>::None);
+    // 编辑前的输入快照
+    let mut undo_stack = use_signal(Vec::<String>::new);
+    let mut redo_stack = use_signal(Vec::<String>::new);
+    // 最近一次可撤销的改动之后是否在输入框里手动键入过；是则输入侧的 Ctrl+Z 交给输入框自己处理
+    let mut input_typed = use_signal(|| false);
+    use_context_provider(|| Signal::new(Option::<DragItem>::None));
+    // ------- 嵌入 JSON 字符串 -------
+    let mut expand_embedded = use_signal(|| restored.expand_embedded);
+    // 展开点（路径 -> 原始字符串），树形视图据此加标记，复制时据此还原
+    let EmbeddedNodes(mut embedded) = use_context_provider(|| EmbeddedNodes(Signal::new(Embedded::new())));
+    let cursor = use_context_provider(|| TreeCursor {
+        hovered: Signal::new(None),
+        selected: Signal::new(None),
+        menu: Signal::new(None),
+    });
+    // ------- 树形视图查找 -------
+    let mut tree_scope = use_signal(|| FindScope::Both);
+    let mut tree_find = use_context_provider(|| TreeFind {
+        matches: Signal::new(TreeMatches::default()),
+        current: Signal::new(None),
+        filter: Signal::new(false),
+    });
+
+    // 写回会话状态（由 App 防抖落盘）
+    use_effect(move || {
+        let next = JsonSession {
+            input: persist::capped(&input.read()),
+            format_mode: *format_mode.read(),
+            sort_keys: *sort_keys.read(),
+            key_order: *key_order.read(),
+            print: *print_options.read(),
+            expand_embedded: *expand_embedded.read(),
+            view_mode: *view_mode.read(),
+            query: query.read().clone(),
+            dialect: *dialect.read(),
+            schema: persist::capped(&schema_text.read()),
+            show_schema: *show_schema.read(),
+            codegen_lang: *codegen_lang.read(),
+        };
+        if *session.peek() != next {
+            session.set(next);
+        }
+    });
+
+    // ========== 输出计算（后台线程）：解析 -> 排序 -> 查询 -> 序列化 ==========
+    // 依赖 input / dialect / 排序 / query / 格式与输出选项 / view_mode，任一变化自动重算。
+    // 大输入先防抖；每次触发递增 compute_gen，过期的结果直接丢弃。
+    use_effect(move || {
+        let job = ComputeJob {
+            src: input.read().clone(),
+            dialect: *dialect.read(),
+            expand_embedded: *expand_embedded.read(),
+            sort: sort_keys.read().then(|| *key_order.read()),
+            query: query.read().clone(),
+            text_view: *view_mode.read() == ViewMode::Text,
+            codegen: (*view_mode.read() == ViewMode::Types).then(|| *codegen_lang.read()),
+            format: *format_mode.read(),
+            print: *print_options.read(),
+        };
+        *compute_gen.write() += 1;
+        let gen = *compute_gen.peek();
+        repair_preview.set(None);
+        if job.src.trim().is_empty() {
+            computing.set(false);
+            stats.set(None);
+            parse_warnings.set(Vec::new());
+            output.set(String::new());
+            output_rows.set(None);
+            error_message.set(String::new());
+            parse_diag.set(None);
+            processed_value.set(None);
+            source_value.set(None);
+            record_lines.set(Vec::new());
+            embedded.set(Embedded::new());
+            return;
+        }
+        spawn(async move {
+            if job.src.len() > LARGE_INPUT_BYTES {
+                tokio::time::sleep(PARSE_DEBOUNCE).await;
+                if *compute_gen.peek() != gen {
                     return;
                 }
+            }
+            computing.set(true);
+            let input_bytes = job.src.len();
+            let started = Instant::now();
+            let Ok(r) = tokio::task::spawn_blocking(move || compute(job)).await else {
+                return;
             };
+            if *compute_gen.peek() != gen {
+                return;
+            }
+            stats.set(Some(ComputeStats {
+                input_bytes,
+                output_bytes: r.output.len(),
+                elapsed: started.elapsed(),
+                unsafe_numbers: r.unsafe_numbers,
+            }));
+            parse_warnings.set(r.warnings);
+            let value = r.value.map(|v| SharedValue(Rc::new(v)));
+            source_value.set(r.source.map(|v| SharedValue(Rc::new(v))).or_else(|| value.clone()));
+            processed_value.set(value);
+            record_lines.set(r.record_lines);
+            embedded.set(r.embedded);
+            output_rows.set(r.rows);
+            output.set(r.output);
+            error_message.set(r.error);
+            parse_diag.set(r.diag);
+            computing.set(false);
+        });
+    });
 
-            // 2. 按 key 排序
-            if *sort_keys.read() {
-                v = sort_value(v);
+    // ========== Schema 校验：对 processed_value（NDJSON 为每条记录）校验 ==========
+    use_effect(move || {
+        let text = schema_text.read().clone();
+        let value = processed_value.read().clone();
+        let records = !record_lines.read().is_empty();
+        if !*show_schema.read() || text.trim().is_empty() {
+            schema_issues.set(Vec::new());
+            schema_error.set(String::new());
+            return;
+        }
+        let schema: Value = match serde_json::from_str(&text) {
+            Ok(s) => s,
+            Err(e) => {
+                schema_issues.set(Vec::new());
+                schema_error.set(format!("Schema 解析错误: {}", e));
+                return;
+            }
+        };
+        let Some(value) = value else {
+            schema_issues.set(Vec::new());
+            schema_error.set(String::new());
+            return;
+        };
+        let result = match (&*value, records) {
+            (Value::Array(items), true) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    validate(&schema, item).map(|vs| vs.into_iter().map(|v| (Some(i), v)).collect::<Vec<_>>())
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|all| all.into_iter().flatten().collect()),
+            _ => validate(&schema, &value).map(|vs| vs.into_iter().map(|v| (None, v)).collect()),
+        };
+        match result {
+            Ok(issues) => {
+                schema_issues.set(issues);
+                schema_error.set(String::new());
             }
+            Err(e) => {
+                schema_issues.set(Vec::new());
+                schema_error.set(e);
+            }
+        }
+    });
 
-            processed_value.set(Some(v.clone()));
-            error_message.set(String::new());
+    // 由当前输出推断 schema（NDJSON 合并所有记录）
+    let infer_schema = move |_| {
+        let Some(v) = processed_value.read().clone() else {
+            return;
+        };
+        let inferred = match (&*v, record_lines.read().is_empty()) {
+            (Value::Array(items), false) => infer(items),
+            _ => infer([&*v]),
+        };
+        schema_text.set(serde_json::to_string_pretty(&inferred).unwrap_or_default());
+    };
 
-            // 4. 文本模式序列化；树形模式由 processed_value 渲染，无需 output
-            if *view_mode.read() == ViewMode::Text {
-                let serialized = match *format_mode.read() {
-                    FormatMode::Pretty => serde_json::to_string_pretty(&v),
-                    FormatMode::Minified => serde_json::to_string(&v),
-                };
-                match serialized {
-                    Ok(s) => output.set(s),
-                    Err(e) => {
-                        output.set(String::new());
-                        error_message.set(format!("序列化错误: {}", e));
+    // 点击违规项：切到格式化文本视图，滚动到实例路径所在行
+    let mut jump_to_instance = move |record: Option<usize>, pointer: String| {
+        view_mode.set(ViewMode::Text);
+        format_mode.set(FormatMode::Pretty);
+        let Some(v) = processed_value.read().clone() else {
+            return;
+        };
+        // NDJSON：各记录 pretty 后按行拼接，先累加前面记录的行数
+        let opts = *print_options.peek();
+        let (base, target) = match (record, &*v) {
+            (Some(i), Value::Array(items)) => {
+                let base: usize = items[..i.min(items.len())]
+                    .iter()
+                    .map(|it| printer::pretty(it, &opts).lines().count())
+                    .sum();
+                (base, items.get(i).cloned().unwrap_or_default())
+            }
+            (_, v) => (0, v.clone()),
+        };
+        let (_, lines) = printer::pretty_with_lines(&target, &opts);
+        // 路径不存在（如缺少的属性）时退到最近的祖先
+        let mut p = pointer;
+        while !lines.contains_key(&p) && !p.is_empty() {
+            p.truncate(p.rfind('/').unwrap_or(0));
+        }
+        let line = base + lines.get(&p).map_or(0, |r| r.0);
+        dioxus::document::eval(&format!(
+            "setTimeout(function(){{var el=document.getElementById('json-output');if(!el)return;var lh=parseFloat(getComputedStyle(el).lineHeight)||20;var k=parseFloat(el.dataset.scale)||1;el.scrollTop=Math.max(0,({line}*lh-el.clientHeight/3)/k);}},30);"
+        ));
+    };
+
+    // ========== 树形编辑：作用于源文档后写回输入，文本 / 树形视图随之重算 ==========
+    // 写回时只改写变化的部分（见 writeback.rs），输入的缩进、注释与 JSON5 写法不受影响。
+    // 以可撤销的方式替换输入（树形编辑、整体转义 / 去转义、输入面板的替换）
+    let mut commit_input = move |next: String| {
+        let old = input.peek().clone();
+        if next == old {
+            return;
+        }
+        undo_stack.with_mut(|s| {
+            s.push(old);
+            // 大文档的快照很占内存：同时按条数和总字节数裁掉最早的记录
+            while s.len() > 1 && (s.len() > UNDO_LIMIT || s.iter().map(String::len).sum::<usize>() > UNDO_MAX_BYTES) {
+                s.remove(0);
+            }
+        });
+        redo_stack.write().clear();
+        input_typed.set(false);
+        input.set(next);
+    };
+    // 把改动后的源文档写回输入
+    let mut commit_doc = move |before: &Value, doc: &Value| {
+        let old = input.peek().clone();
+        let lines = record_lines.peek().clone();
+        let next = match (before, doc) {
+            // NDJSON：只改写变化的记录所在行，其余行（含解析失败的行）原样保留
+            (Value::Array(a), Value::Array(b)) if !lines.is_empty() => {
+                let mut text: Vec<String> = old.split('\n').map(str::to_string).collect();
+                for ((line, x), y) in lines.iter().zip(a).zip(b) {
+                    if x == y {
+                        continue;
+                    }
+                    if let Some(t) = text.get_mut(line - 1) {
+                        *t = splice(t, x, y).unwrap_or_else(|| {
+                            let cr = if t.ends_with('\r') { "\r" } else { "" };
+                            serde_json::to_string(y).unwrap_or_default() + cr
+                        });
                     }
                 }
-            } else {
-                output.set(String::new());
+                text.join("\n")
             }
-        });
-    }
+            _ => match splice(&old, before, doc) {
+                Some(next) => next,
+                // 定位不到（如重复 key）：严格 JSON 按输入自身的缩进重排；JSONC / JSON5 整体重排会丢掉注释，不写回
+                None if *dialect.peek() == InputDialect::Strict => reformat(&old, doc),
+                None => {
+                    error_message.set(format!(
+                        "无法在 {} 原文中定位这处改动（可能有重复的 key），为免丢失注释已取消编辑",
+                        dialect.peek().label()
+                    ));
+                    return;
+                }
+            },
+        };
+        commit_input(next);
+    };
+    let mut apply_edit = move |edit: TreeEdit| {
+        let Some(before) = source_value.peek().clone() else {
+            return;
+        };
+        let mut doc = (*before).clone();
+        if let Err(e) = apply(&mut doc, &edit) {
+            error_message.set(e);
+            return;
+        }
+        commit_doc(&before, &doc);
+    };
+    let mut undo = move || {
+        let Some(prev) = undo_stack.write().pop() else {
+            return;
+        };
+        redo_stack.write().push(input.peek().clone());
+        input_typed.set(false);
+        input.set(prev);
+    };
+    let mut redo = move || {
+        let Some(next) = redo_stack.write().pop() else {
+            return;
+        };
+        undo_stack.write().push(input.peek().clone());
+        input_typed.set(false);
+        input.set(next);
+    };
+
+    // ========== 整体转义 / 去转义 ==========
+    // 转义：文档压缩后写成字符串字面量（NDJSON 逐行）；用解析后的源文档，展开的字符串不受影响
+    let stringify_input = move |_: MouseEvent| {
+        let Some(doc) = source_value.peek().clone() else {
+            error_message.set("输入不是有效的 JSON，无法转义".into());
+            return;
+        };
+        let lines = record_lines.peek().clone();
+        let next = match &*doc {
+            Value::Array(items) if !lines.is_empty() => {
+                let old = input.peek().clone();
+                let mut text: Vec<String> = old.split('\n').map(str::to_string).collect();
+                for (line, item) in lines.iter().zip(items) {
+                    if let Some(t) = text.get_mut(line - 1) {
+                        let cr = t.ends_with('\r');
+                        *t = stringify(item);
+                        if cr {
+                            t.push('\r');
+                        }
+                    }
+                }
+                text.join("\n")
+            }
+            v => stringify(v),
+        };
+        commit_input(next);
+    };
+    // 去转义：把字符串字面量（可多层转义）还原为其中的 JSON 文本
+    let unstringify_input = move |_: MouseEvent| {
+        let text = input.peek().clone();
+        match unstringify(&text) {
+            Ok(next) => commit_input(next),
+            Err(e) => error_message.set(e),
+        }
+    };
 
     // ========== 输出高亮 effect ==========
-    // 依赖 output / output_find_query / output_current_match_idx：
+    // 依赖 output / output_find_query / 查找选项 / output_current_match_idx：
     // 任一变化都重算匹配位置并重新高亮，因此“上一个/下一个”无需手动 repaint。
     // 注意：内部用本地 pos/cur，不回读 output_match_positions，避免循环。
+    // 窗口化渲染时只算匹配位置，高亮由 VirtualText 按可视行进行。
     {
         use_effect(move || {
-            let text = output.read().clone();
+            let text = output.read();
             let q = output_find_query.read().clone();
             let idx = *output_current_match_idx.read();
 
-            let mut pos = Vec::<usize>::new();
-            if !q.is_empty() && !text.is_empty() {
-                let mut start = 0usize;
-                while let Some(p) = text[start..].find(&q) {
-                    pos.push(start + p);
-                    start = start + p + q.len();
-                }
-            }
+            let pos = find_matches(&text, &q, *output_search_options.read());
             let cur = if pos.is_empty() { 0 } else { idx.min(pos.len() - 1) };
 
-            let html = highlight_json_with_search(&text, &q, &pos, cur);
+            let html = if output_rows.read().is_some() || *view_mode.read() != ViewMode::Text {
+                String::new()
+            } else {
+                highlight_json_with_search(&text, &pos, cur)
+            };
             output_match_positions.set(pos);
             highlighted_output.set(html);
         });
     }
 
-    // ========== 输入面板查找逻辑 ==========
-    let mut input_recompute_matches = {
-        move || {
-            let text = input.read().clone();
-            let q = input_find_query.read().clone();
-            let mut pos = Vec::<usize>::new();
-
-            if !q.is_empty() && !text.is_empty() {
-                let mut start = 0usize;
-                while let Some(p) = text[start..].find(&q) {
-                    pos.push(start + p);
-                    start = start + p + q.len();
+    // ========== 树形视图查找 ==========
+    // 查找条打开时在树形视图的文档上匹配 key / 值；查找词、选项、范围或文档变化时重算并回到第一个匹配
+    use_effect(move || {
+        let active = *view_mode.read() == ViewMode::Tree && *output_show_find.read();
+        let q = output_find_query.read().clone();
+        let opts = *output_search_options.read();
+        let scope = *tree_scope.read();
+        let found = match (active, processed_value.read().as_ref(), Matcher::new(&q, opts)) {
+            (true, Some(doc), Ok(Some(m))) => find_in_tree(doc, &m, scope),
+            _ => TreeMatches::default(),
+        };
+        // NDJSON：匹配所在的记录超出已挂载的条数时放开
+        if !record_lines.peek().is_empty() {
+            if let Some(&last) = found.reveal.get(&Vec::new()) {
+                if last >= *tree_records_shown.peek() {
+                    tree_records_shown.set((last / TREE_PAGE + 1) * TREE_PAGE);
                 }
             }
-            input_current_match_idx.set(0);
-            input_match_positions.set(pos);
+        }
+        output_current_match_idx.set(0);
+        tree_find.matches.set(found);
+    });
+    use_effect(move || {
+        let idx = *output_current_match_idx.read();
+        let current = tree_find.matches.read().hits.get(idx).cloned();
+        tree_find.current.set(current);
+    });
+    // 输出侧的匹配总数：树形视图按节点计，文本视图按文本匹配计
+    let output_match_total = move || {
+        if *view_mode.read() == ViewMode::Tree {
+            tree_find.matches.read().hits.len()
+        } else {
+            output_match_positions.read().len()
         }
     };
 
+    // ========== 输入面板查找逻辑 ==========
+    // 输入、查找词或选项变化时重算匹配，并回到第一个匹配
+    use_effect(move || {
+        let pos = find_matches(
+            &input.read(),
+            &input_find_query.read(),
+            *input_search_options.read(),
+        );
+        input_current_match_idx.set(0);
+        input_match_positions.set(pos);
+    });
+
     // 输入面板下一个/上一个匹配
     let mut input_next_match = {
         move |_| {
@@ -187,63 +897,83 @@ pub fn JsonFormatterTool() -> Element {
     use_effect(move || {
         let positions = input_match_positions.read().clone();
         let idx = *input_current_match_idx.read();
-        let q = input_find_query.read().clone();
-        if positions.is_empty() || q.is_empty() {
+        if positions.is_empty() {
             return;
         }
-        let idx = idx.min(positions.len() - 1);
-        let start = positions[idx];
-        let end = start + q.len();
-        // 字节位置 -> 字符索引（JS setSelectionRange 用 UTF-16 索引；
-        // 对 BMP 字符二者一致，含 emoji 等非 BMP 字符时略有偏差但不会报错）
-        let text = input.read().clone();
-        let s = text[..start].chars().count();
-        let e = text[..end].chars().count();
-        let js = format!(
-            "(function(){{var el=document.getElementById('json-input');if(!el)return;el.focus();try{{el.setSelectionRange({s},{e});}}catch(_){{}}var lh=parseFloat(getComputedStyle(el).lineHeight)||20;var lines=el.value.slice(0,{s}).split('\\n').length-1;el.scrollTop=Math.max(0,lines*lh-el.clientHeight/3);}})();",
-            s = s, e = e
-        );
-        dioxus::document::eval(&js);
+        let m = &positions[idx.min(positions.len() - 1)];
+        select_input_range(&input.peek(), m.start, m.end);
     });
 
     // ========== 输入面板替换逻辑 ==========
     let mut input_replace_one = {
         move |_| {
-            let q = input_find_query.read().clone();
-            if q.is_empty() {
+            // 结构化替换只能整体进行
+            if *replace_target.read() != ReplaceTarget::Text {
                 return;
             }
-
-            let mut text = input.read().clone();
             let positions = input_match_positions.read().clone();
             if positions.is_empty() {
                 return;
             }
 
-            let idx = (*input_current_match_idx.read()).min(positions.len().saturating_sub(1));
-            let start = positions[idx];
-            let end = start + q.len();
-
-            text.replace_range(start..end, &input_replace_query.read());
-            input.set(text);
-
-            input_recompute_matches();
+            let idx = (*input_current_match_idx.read()).min(positions.len() - 1);
+            let text = input.read().clone();
+            if let Some(next) = replace_match(
+                &text,
+                &input_find_query.read(),
+                *input_search_options.read(),
+                positions[idx].clone(),
+                &input_replace_query.read(),
+            ) {
+                commit_input(next);
+            }
         }
     };
 
+    // 结构化替换的预览：(修改的节点数, 因重名跳过的 key 数)
+    let structural_preview = use_memo(move || {
+        let target = *replace_target.read();
+        if target == ReplaceTarget::Text || !*input_show_replace.read() {
+            return None;
+        }
+        let matcher = match Matcher::new(&input_find_query.read(), *input_search_options.read()) {
+            Ok(Some(m)) => m,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+        let Some(doc) = source_value.read().clone() else {
+            return Some(Err("输入不是有效的 JSON".to_string()));
+        };
+        let rep = input_replace_query.read().clone();
+        Some(replace_in(&doc, target, &replace_path.read(), &matcher, &rep).map(|r| (r.changed, r.conflicts)))
+    });
+
     let mut input_replace_all = {
         move |_| {
-            let q = input_find_query.read().clone();
-            if q.is_empty() {
+            let target = *replace_target.read();
+            if target != ReplaceTarget::Text {
+                let Ok(Some(matcher)) = Matcher::new(&input_find_query.read(), *input_search_options.read()) else {
+                    return;
+                };
+                let Some(before) = source_value.peek().clone() else {
+                    return;
+                };
+                let rep = input_replace_query.read().clone();
+                match replace_in(&before, target, &replace_path.read(), &matcher, &rep) {
+                    Ok(r) if r.changed > 0 => commit_doc(&before, &r.doc),
+                    Ok(_) => {}
+                    Err(e) => error_message.set(e),
+                }
                 return;
             }
-            let mut text = input.read().clone();
-            let rep = input_replace_query.read().clone();
-
-            if !text.is_empty() {
-                text = text.replace(&q, &rep);
-                input.set(text);
-                input_recompute_matches();
+            let text = input.read().clone();
+            if let Some(next) = replace_all_matches(
+                &text,
+                &input_find_query.read(),
+                *input_search_options.read(),
+                &input_replace_query.read(),
+            ) {
+                commit_input(next);
             }
         }
     };
@@ -259,7 +989,7 @@ pub fn JsonFormatterTool() -> Element {
     // 输出面板下一个/上一个匹配（高亮由 effect 自动跟随）
     let mut output_next_match = {
         move |_| {
-            let total = output_match_positions.read().len();
+            let total = output_match_total();
             if total == 0 {
                 return;
             }
@@ -269,7 +999,7 @@ pub fn JsonFormatterTool() -> Element {
     };
     let mut output_prev_match = {
         move |_| {
-            let total = output_match_positions.read().len();
+            let total = output_match_total();
             if total == 0 {
                 return;
             }
@@ -282,6 +1012,9 @@ pub fn JsonFormatterTool() -> Element {
     // Cmd/Ctrl+F：打开/聚焦查找（根据“活动侧”）
     // Cmd/Ctrl+H：展开替换
     // Cmd/Ctrl+G / Shift+Cmd/Ctrl+G：下一个/上一个
+    // Cmd/Ctrl+Z / Shift+Cmd/Ctrl+Z：树形编辑撤销/重做（输出侧）；
+    //   输入侧在替换 / 树形编辑之后、手动键入之前也走同一个撤销栈
+    //   （这类改动是整体写入的，输入框自身的撤销记录不含它们）
     let on_keydown = {
         move |e: Event<KeyboardData>| {
             let meta = e.modifiers().contains(Modifiers::META)
@@ -336,6 +1069,29 @@ pub fn JsonFormatterTool() -> Element {
                         output_prev_match(());
                     }
                 }
+                Code::KeyZ
+                    if meta
+                        && *active_panel.read() == "input"
+                        && !*input_typed.read()
+                        && !if shift { redo_stack } else { undo_stack }.read().is_empty() =>
+                {
+                    e.stop_propagation();
+                    e.prevent_default();
+                    if shift {
+                        redo();
+                    } else {
+                        undo();
+                    }
+                }
+                Code::KeyZ if meta && *active_panel.read() == "output" && *view_mode.read() == ViewMode::Tree => {
+                    e.stop_propagation();
+                    e.prevent_default();
+                    if shift {
+                        redo();
+                    } else {
+                        undo();
+                    }
+                }
                 Code::Enter if meta && !alt => {
                     e.stop_propagation();
                     if *active_panel.read() == "input" {
@@ -358,11 +1114,12 @@ pub fn JsonFormatterTool() -> Element {
         move |e: Event<FormData>| {
             active_panel.set("input".to_string());
             input.set(e.value().to_string());
-            // 输出由 compute effect 自动重算
-            // 若输入面板正在查找，重新计算匹配位置（保持选中定位正确）
-            if !input_find_query.read().is_empty() {
-                input_recompute_matches();
+            input_typed.set(true);
+            // 手动修改后树形编辑的重做记录失效
+            if !redo_stack.peek().is_empty() {
+                redo_stack.write().clear();
             }
+            // 输出由 compute effect 自动重算；查找匹配由查找 effect 跟随
         }
     };
 
@@ -380,13 +1137,16 @@ pub fn JsonFormatterTool() -> Element {
         dioxus::document::eval(&js);
     };
     let copy_output = move |_| {
-        // 树形模式：序列化 processed_value；文本模式：直接用 output
-        let text = if *view_mode.read() == ViewMode::Tree {
-            match processed_value.read().clone() {
-                Some(v) => match *format_mode.read() {
-                    FormatMode::Pretty => serde_json::to_string_pretty(&v).unwrap_or_default(),
-                    FormatMode::Minified => serde_json::to_string(&v).unwrap_or_default(),
-                },
+        // 树形模式或有展开的嵌入字符串：序列化 processed_value（展开点还原为原字符串）；
+        // 其余直接用 output
+        let mode = *view_mode.read();
+        let text = if mode == ViewMode::Tree || (mode == ViewMode::Text && !embedded.read().is_empty()) {
+            let records = !record_lines.read().is_empty();
+            match processed_value.read().as_ref() {
+                Some(v) => {
+                    let v = collapse(v, &[], &embedded.read());
+                    serialize_output(&v, records, *format_mode.read(), &print_options.read())
+                }
                 None => String::new(),
             }
         } else {
@@ -409,8 +1169,36 @@ pub fn JsonFormatterTool() -> Element {
     let fmt_pretty_style = if *format_mode.read() == FormatMode::Pretty { seg_active } else { seg_normal };
     let fmt_min_style = if *format_mode.read() == FormatMode::Minified { seg_active } else { seg_normal };
     let sort_style = if *sort_keys.read() { seg_active } else { seg_normal };
+    let print_options_style = if *show_print_options.read() { seg_active } else { seg_normal };
+    let opts = *print_options.read();
     let view_text_style = if *view_mode.read() == ViewMode::Text { seg_active } else { seg_normal };
     let view_tree_style = if *view_mode.read() == ViewMode::Tree { seg_active } else { seg_normal };
+    let view_types_style = if *view_mode.read() == ViewMode::Types { seg_active } else { seg_normal };
+    let is_ndjson = *dialect.read() == InputDialect::Ndjson;
+    let schema_style = if *show_schema.read() { seg_active } else { seg_normal };
+    let edit_style = if *edit_mode.read() { seg_active } else { seg_normal };
+    let expand_style = if *expand_embedded.read() { seg_active } else { seg_normal };
+    let query_active = !query.read().trim().is_empty();
+    let (preview_text, preview_color) = match &*structural_preview.read() {
+        None => (String::new(), "var(--text-dim)"),
+        Some(Ok((0, _))) => ("没有可替换的节点".to_string(), "var(--text-dim)"),
+        Some(Ok((n, 0))) => (format!("将修改 {} 个节点", n), "var(--text-dim)"),
+        Some(Ok((n, c))) => (format!("将修改 {} 个节点，{} 个 key 改名后重名已跳过", n, c), "var(--warn)"),
+        Some(Err(e)) => (e.clone(), "var(--danger)"),
+    };
+    let edit_title = if query_active {
+        "清除查询后可编辑"
+    } else if *expand_embedded.read() {
+        "关闭“展开字符串”后可编辑"
+    } else {
+        "双击 key / 值编辑，悬停显示更多操作"
+    };
+    // 大文档的树形视图默认只展开前几层
+    let expand_depth = if input.read().len() > LARGE_INPUT_BYTES { LARGE_EXPAND_DEPTH } else { usize::MAX };
+    let on_tree_edit = (*edit_mode.read() && !query_active && !*expand_embedded.read()).then(|| EventHandler::new(move |e: TreeEdit| apply_edit(e)));
+    let schema_draft = serde_json::from_str::<Value>(&schema_text.read())
+        .map(|s| detect_draft(&s).label())
+        .unwrap_or("");
 
     // ====== 渲染 ======
     rsx! {
@@ -435,16 +1223,47 @@ pub fn JsonFormatterTool() -> Element {
                         show_replace: input_show_replace,
                         query: input_find_query,
                         replace: input_replace_query,
+                        options: input_search_options,
                         on_prev: move |_| input_prev_match(()),
                         on_next: move |_| input_next_match(()),
                         on_close: move |_| { input_show_find.set(false); input_show_replace.set(false); },
-                        on_query_input: move |q| { input_find_query.set(q); input_recompute_matches(); },
-                        on_replace_one: Some(EventHandler::new(move |_| input_replace_one(()))),
+                        on_query_input: move |q| input_find_query.set(q),
+                        on_replace_one: (*replace_target.read() == ReplaceTarget::Text).then(|| EventHandler::new(move |_| input_replace_one(()))),
                         on_replace_all: Some(EventHandler::new(move |_| input_replace_all(()))),
                         replace_disabled: false,
                         match_current: *input_current_match_idx.read(),
                         match_total: input_match_positions.read().len(),
                     }
+                    // -- 替换范围：文本 / 键 / 字符串值 / JSONPath，结构化时预览受影响的节点数
+                    if *input_show_find.read() && *input_show_replace.read() {
+                        div {
+                            style: "display:flex; align-items:center; gap:6px; padding:0 8px 6px; background:var(--bg-header); border-bottom:1px solid var(--border);",
+                            span { style: "font-size:12px; color:var(--text-dim); user-select:none;", "范围" }
+                            div {
+                                style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                                for t in ReplaceTarget::ALL {
+                                    button {
+                                        key: "{t.label()}",
+                                        class: "tb-seg-btn",
+                                        style: if *replace_target.read() == t { seg_active } else { seg_normal },
+                                        title: t.hint(),
+                                        onclick: move |_| replace_target.set(t),
+                                        "{t.label()}"
+                                    }
+                                }
+                            }
+                            if *replace_target.read() == ReplaceTarget::Path {
+                                input {
+                                    class: "tb-search-input",
+                                    style: "flex:1; min-width:80px;",
+                                    value: "{replace_path}",
+                                    placeholder: "$.items[*].name",
+                                    oninput: move |e| replace_path.set(e.value()),
+                                }
+                            }
+                            span { style: "font-size:12px; color:{preview_color}; user-select:none; white-space:nowrap;", "{preview_text}" }
+                        }
+                    }
 
                     // -- 标题栏 + 格式/压缩/排序 + 复制
                     div {
@@ -457,8 +1276,37 @@ pub fn JsonFormatterTool() -> Element {
                             button { class: "tb-seg-btn", style: "{fmt_min_style}", onclick: move |_| format_mode.set(FormatMode::Minified), "压缩" }
                         }
                         // 排序开关
-                        button { class: "tb-seg-btn", style: "{sort_style}", onclick: move |_| sort_keys.with_mut(|v| *v = !*v), title: "按 key 字典序排序", "排序 ⇕" }
+                        button { class: "tb-seg-btn", style: "{sort_style}", onclick: move |_| sort_keys.with_mut(|v| *v = !*v), title: "按 key 排序（方式见“选项”）", "排序 ⇕" }
+                        button { class: "tb-seg-btn", style: "{print_options_style}", onclick: move |_| show_print_options.with_mut(|v| *v = !*v), title: "缩进、单行合并、转义与排序方式", "选项" }
+                        // 输入方言
+                        div {
+                            style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                            for d in InputDialect::ALL {
+                                button {
+                                    key: "{d.label()}",
+                                    class: "tb-seg-btn",
+                                    style: if *dialect.read() == d { seg_active } else { seg_normal },
+                                    title: d.hint(),
+                                    onclick: move |_| dialect.set(d),
+                                    "{d.label()}"
+                                }
+                            }
+                        }
                         span { style: "flex:1;" }
+                        button {
+                            class: "tb-btn-ghost",
+                            style: "padding:3px 8px; font-size:11px;",
+                            title: "把整个文档写成 JSON 字符串字面量（NDJSON 逐行）",
+                            onclick: stringify_input,
+                            "转义"
+                        }
+                        button {
+                            class: "tb-btn-ghost",
+                            style: "padding:3px 8px; font-size:11px;",
+                            title: "把 JSON 字符串字面量（可多层转义）还原为 JSON",
+                            onclick: unstringify_input,
+                            "去转义"
+                        }
                         button {
                             id: "copy-in-btn",
                             class: "tb-btn-ghost",
@@ -468,6 +1316,75 @@ pub fn JsonFormatterTool() -> Element {
                         }
                     }
 
+                    // -- 输出选项：缩进 / 单行合并 / 转义 / 末尾换行 / 排序方式
+                    if *show_print_options.read() {
+                        div {
+                            style: "display:flex; flex-wrap:wrap; align-items:center; gap:6px 10px; padding:6px 8px; background:var(--bg-header); border-bottom:1px solid var(--border); font-size:12px;",
+                            span { style: "color:var(--text-dim); user-select:none;", "缩进" }
+                            div {
+                                style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                                for ind in Indent::ALL {
+                                    button {
+                                        key: "{ind.label()}",
+                                        class: "tb-seg-btn",
+                                        style: if opts.indent == ind { seg_active } else { seg_normal },
+                                        onclick: move |_| print_options.with_mut(|o| o.indent = ind),
+                                        "{ind.label()}"
+                                    }
+                                }
+                            }
+                            span { style: "color:var(--text-dim); user-select:none;", title: "数组 / 对象写成一行后不超过该宽度时合并为一行", "单行宽度" }
+                            div {
+                                style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                                for w in COMPACT_WIDTHS {
+                                    button {
+                                        key: "{w}",
+                                        class: "tb-seg-btn",
+                                        style: if opts.compact_width == w { seg_active } else { seg_normal },
+                                        onclick: move |_| print_options.with_mut(|o| o.compact_width = w),
+                                        if w == 0 { "关" } else { "{w}" }
+                                    }
+                                }
+                            }
+                            button {
+                                class: "tb-seg-btn",
+                                style: if opts.ascii { seg_active } else { seg_normal },
+                                title: "非 ASCII 字符写成 \\uXXXX",
+                                onclick: move |_| print_options.with_mut(|o| o.ascii = !o.ascii),
+                                "ASCII 转义"
+                            }
+                            button {
+                                class: "tb-seg-btn",
+                                style: if opts.escape_slash { seg_active } else { seg_normal },
+                                title: "/ 写成 \\/",
+                                onclick: move |_| print_options.with_mut(|o| o.escape_slash = !o.escape_slash),
+                                "转义 /"
+                            }
+                            button {
+                                class: "tb-seg-btn",
+                                style: if opts.trailing_newline { seg_active } else { seg_normal },
+                                onclick: move |_| print_options.with_mut(|o| o.trailing_newline = !o.trailing_newline),
+                                "末尾换行"
+                            }
+                            span { style: "color:var(--text-dim); user-select:none;", "排序方式" }
+                            div {
+                                style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                                for o in KeyOrder::ALL {
+                                    button {
+                                        key: "{o.label()}",
+                                        class: "tb-seg-btn",
+                                        style: if *sort_keys.read() && *key_order.read() == o { seg_active } else { seg_normal },
+                                        onclick: move |_| {
+                                            key_order.set(o);
+                                            sort_keys.set(true);
+                                        },
+                                        "{o.label()}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // -- 输入编辑器
                     textarea {
                         id: "json-input",
@@ -478,7 +1395,7 @@ pub fn JsonFormatterTool() -> Element {
                         onfocus: move |_| active_panel.set("input".to_string()),
                         onfocusin: move |_| active_panel.set("input".to_string()),
                         onmouseenter: move |_| active_panel.set("input".to_string()),
-                        placeholder: "在此输入 JSON 数据..."
+                        placeholder: if is_ndjson { "每行一个 JSON 记录..." } else { "在此输入 JSON 数据..." }
                     }
                 }
 
@@ -487,13 +1404,14 @@ pub fn JsonFormatterTool() -> Element {
                     class: "output-panel tb-panel",
                     style: "flex:1; display:flex; flex-direction:column;",
 
-                    // -- 查找条（仅文本模式；禁用替换）
-                    if *view_mode.read() == ViewMode::Text {
+                    // -- 查找条（文本 / 树形视图；禁用替换）
+                    if *view_mode.read() != ViewMode::Types {
                         SearchBar {
                             show: output_show_find,
                             show_replace: output_show_replace,
                             query: output_find_query,
                             replace: output_replace_query,
+                            options: output_search_options,
                             on_prev: move |_| output_prev_match(()),
                             on_next: move |_| output_next_match(()),
                             on_close: move |_| { output_show_find.set(false); output_show_replace.set(false); },
@@ -502,32 +1420,156 @@ pub fn JsonFormatterTool() -> Element {
                             on_replace_all: None,
                             replace_disabled: true,
                             match_current: *output_current_match_idx.read(),
-                            match_total: output_match_positions.read().len(),
+                            match_total: output_match_total(),
+                        }
+                    }
+                    // -- 树形视图查找：匹配范围与过滤
+                    if *view_mode.read() == ViewMode::Tree && *output_show_find.read() {
+                        div {
+                            style: "display:flex; align-items:center; gap:6px; padding:0 8px 6px; background:var(--bg-header); border-bottom:1px solid var(--border);",
+                            span { style: "font-size:12px; color:var(--text-dim); user-select:none;", "范围" }
+                            div {
+                                style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                                for sc in FindScope::ALL {
+                                    button {
+                                        key: "{sc.label()}",
+                                        class: "tb-seg-btn",
+                                        style: if *tree_scope.read() == sc { seg_active } else { seg_normal },
+                                        onclick: move |_| tree_scope.set(sc),
+                                        "{sc.label()}"
+                                    }
+                                }
+                            }
+                            button {
+                                class: "tb-seg-btn",
+                                style: if *tree_find.filter.read() { seg_active } else { seg_normal },
+                                title: "隐藏不含匹配的分支",
+                                onclick: move |_| tree_find.filter.with_mut(|v| *v = !*v),
+                                "仅显示匹配"
+                            }
                         }
                     }
 
-                    // -- 标题栏 + 文本/树形 + 复制
+                    // -- 标题栏 + 文本/树形/类型 + 复制
                     div {
                         class: "tb-panel-header",
                         span { "输出" }
-                        // 文本 / 树形
+                        // 文本 / 树形 / 类型
                         div {
                             style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
                             button { class: "tb-seg-btn", style: "{view_text_style}", onclick: move |_| view_mode.set(ViewMode::Text), "文本" }
                             button { class: "tb-seg-btn", style: "{view_tree_style}", onclick: move |_| view_mode.set(ViewMode::Tree), "树形" }
+                            button { class: "tb-seg-btn", style: "{view_types_style}", title: "由输出生成类型定义", onclick: move |_| view_mode.set(ViewMode::Types), "类型" }
                         }
-                        span { style: "flex:1;" }
+                        button { class: "tb-seg-btn", style: "{schema_style}", onclick: move |_| show_schema.with_mut(|v| *v = !*v), title: "JSON Schema 校验", "Schema" }
                         button {
-                            id: "copy-out-btn",
-                            class: "tb-btn-ghost",
-                            style: "padding:3px 10px; font-size:11px;",
+                            class: "tb-seg-btn",
+                            style: "{expand_style}",
+                            onclick: move |_| expand_embedded.with_mut(|v| *v = !*v),
+                            title: "把内容为 JSON 的字符串值（含多层转义）展开为对象 / 数组；复制时还原",
+                            "展开字符串"
+                        }
+                        // 树形编辑：开关 + 撤销 / 重做
+                        if *view_mode.read() == ViewMode::Tree {
+                            button {
+                                class: "tb-seg-btn",
+                                style: "{edit_style}",
+                                disabled: query_active || *expand_embedded.read(),
+                                title: edit_title,
+                                onclick: move |_| edit_mode.with_mut(|v| *v = !*v),
+                                "编辑"
+                            }
+                            if *edit_mode.read() {
+                                button {
+                                    class: "tb-icon-btn",
+                                    style: "width:24px; height:24px; font-size:13px;",
+                                    disabled: undo_stack.read().is_empty(),
+                                    title: "撤销 (⌘Z)",
+                                    onclick: move |_| undo(),
+                                    "↶"
+                                }
+                                button {
+                                    class: "tb-icon-btn",
+                                    style: "width:24px; height:24px; font-size:13px;",
+                                    disabled: redo_stack.read().is_empty(),
+                                    title: "重做 (⇧⌘Z)",
+                                    onclick: move |_| redo(),
+                                    "↷"
+                                }
+                            }
+                        }
+                        span { style: "flex:1;" }
+                        // 大整数提示 + 输入 / 输出大小与解析耗时
+                        if let Some(st) = stats().filter(|st| st.unsafe_numbers > 0) {
+                            span {
+                                class: "json-badge-warn",
+                                title: "这些整数超出 JavaScript 安全整数范围（±2^53−1），在浏览器中 JSON.parse 后会丢失精度；本工具按原文保留",
+                                "⚠ {st.unsafe_numbers} 个大整数"
+                            }
+                        }
+                        if !parse_warnings.read().is_empty() {
+                            span {
+                                class: "json-badge-warn",
+                                title: parse_warnings.read().join("\n"),
+                                "⚠ {parse_warnings.read().len()} 处改为 null"
+                            }
+                        }
+                        if *computing.read() {
+                            span { class: "tb-count", style: "min-width:auto;", "解析中…" }
+                        } else if let Some(st) = stats() {
+                            span {
+                                class: "tb-count",
+                                style: "min-width:auto;",
+                                title: "输入大小 → 输出大小 · 解析与序列化耗时",
+                                if st.output_bytes > 0 {
+                                    "{format_size(st.input_bytes)} → {format_size(st.output_bytes)} · {st.elapsed.as_millis()} ms"
+                                } else {
+                                    "{format_size(st.input_bytes)} · {st.elapsed.as_millis()} ms"
+                                }
+                            }
+                        }
+                        button {
+                            id: "copy-out-btn",
+                            class: "tb-btn-ghost",
+                            style: "padding:3px 10px; font-size:11px;",
                             onclick: copy_output,
                             "复制"
                         }
                     }
 
-                    // -- 输出内容：文本模式（高亮）/ 树形模式
-                    if *view_mode.read() == ViewMode::Text {
+                    // -- 查询栏：JSONPath（$ 开头）或 jq 子集，结果替换输出内容
+                    div {
+                        style: "display:flex; align-items:center; gap:6px; padding:6px 8px; background:var(--bg-header); border-bottom:1px solid var(--border);",
+                        span { style: "font-size:12px; color:var(--text-dim); user-select:none;", "查询" }
+                        input {
+                            class: "tb-search-input",
+                            value: "{query}",
+                            placeholder: "$.items[*].id 或 .items[] | select(.id > 1)",
+                            oninput: move |e| query.set(e.value()),
+                            onfocus: move |_| active_panel.set("output".to_string()),
+                        }
+                        if !query.read().is_empty() {
+                            button {
+                                class: "tb-icon-btn",
+                                style: "width:26px; height:26px; font-size:14px;",
+                                title: "清除查询",
+                                onclick: move |_| query.set(String::new()),
+                                "✕"
+                            }
+                        }
+                    }
+
+                    // -- 输出内容：文本模式（高亮）/ 类型定义 / 树形模式
+                    if *view_mode.read() == ViewMode::Text && output_rows.read().is_some() {
+                        // 超大输出：只渲染可视区附近的行
+                        VirtualText {
+                            text: output,
+                            rows: output_rows,
+                            positions: output_match_positions,
+                            current: output_current_match_idx,
+                            on_activate: move |_| active_panel.set("output".to_string()),
+                        }
+                    } else if *view_mode.read() == ViewMode::Text {
                         div {
                             id: "json-output",
                             class: "tb-scroll",
@@ -539,27 +1581,532 @@ pub fn JsonFormatterTool() -> Element {
                             onfocusin: move |_| active_panel.set("output".to_string()),
                             onmouseenter: move |_| active_panel.set("output".to_string()),
                         }
+                    } else if *view_mode.read() == ViewMode::Types {
+                        // 目标语言
+                        div {
+                            style: "display:flex; align-items:center; gap:6px; padding:6px 8px; background:var(--bg-header); border-bottom:1px solid var(--border);",
+                            span { style: "font-size:12px; color:var(--text-dim); user-select:none;", "语言" }
+                            div {
+                                style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                                for l in TargetLang::ALL {
+                                    button {
+                                        key: "{l.label()}",
+                                        class: "tb-seg-btn",
+                                        style: if *codegen_lang.read() == l { seg_active } else { seg_normal },
+                                        title: l.hint(),
+                                        onclick: move |_| codegen_lang.set(l),
+                                        "{l.label()}"
+                                    }
+                                }
+                            }
+                        }
+                        pre {
+                            id: "json-output-types",
+                            class: "tb-scroll",
+                            tabindex: "0",
+                            style: "flex:1; background:var(--bg-input); color:var(--text); margin:0; padding:14px; overflow:auto; white-space:pre; font-family:'Menlo','Monaco','Consolas',monospace; font-size:13px; line-height:1.6;",
+                            onclick: move |_| active_panel.set("output".to_string()),
+                            onfocusin: move |_| active_panel.set("output".to_string()),
+                            onmouseenter: move |_| active_panel.set("output".to_string()),
+                            if output.read().is_empty() && *computing.read() {
+                                span { style: "color:var(--text-dim);", "解析中…" }
+                            } else {
+                                "{output}"
+                            }
+                        }
                     } else {
+                        // 面包屑：悬停 / 选中节点的路径与复制操作
+                        TreeBreadcrumbs { doc: processed_value, record_lines }
                         div {
                             id: "json-output-tree",
                             class: "tb-scroll",
+                            tabindex: "0",
+                            onclick: move |_| active_panel.set("output".to_string()),
+                            onfocusin: move |_| active_panel.set("output".to_string()),
+                            onmouseenter: move |_| active_panel.set("output".to_string()),
+                            onmouseleave: move |_| {
+                                let mut hovered = cursor.hovered;
+                                hovered.set(None);
+                            },
                             style: "flex:1; background:var(--bg-input); color:var(--text); margin:0; padding:14px; overflow:auto; font-family:'Menlo','Monaco','Consolas',monospace; font-size:13px; line-height:1.6;",
-                            if let Some(v) = processed_value.read().clone() {
-                                JsonNode { value: v, label: None }
+                            if let Some(doc) = processed_value.read().clone() {
+                                if record_lines.read().is_empty() {
+                                    JsonNode { doc, label: None, is_root: true, on_edit: on_tree_edit, expand_depth }
+                                } else {
+                                    // NDJSON：每条记录一个根节点，标签为输入中的行号；记录多时分批挂载
+                                    for (i, line) in record_lines.read().iter().copied().enumerate().take(tree_records_shown()) {
+                                        JsonNode {
+                                            key: "{line}",
+                                            doc: doc.clone(),
+                                            label: Some(format!("#{}", line)),
+                                            is_index: true,
+                                            path: vec![Seg::Index(i)],
+                                            is_root: true,
+                                            on_edit: on_tree_edit,
+                                            expand_depth,
+                                        }
+                                    }
+                                    if record_lines.read().len() > tree_records_shown() {
+                                        button {
+                                            class: "json-act json-more",
+                                            onclick: move |_| tree_records_shown += TREE_PAGE,
+                                            "显示更多（还有 {record_lines.read().len() - tree_records_shown()} 条）"
+                                        }
+                                    }
+                                }
+                            } else if *computing.read() {
+                                div { style: "color:var(--text-dim);", "解析中…" }
                             } else {
                                 div { style: "color:var(--text-dim);", "（无数据）" }
                             }
                         }
                     }
                 }
+
+                // 第三栏：JSON Schema 校验
+                if *show_schema.read() {
+                    div {
+                        class: "schema-panel tb-panel",
+                        style: "flex:1; display:flex; flex-direction:column; min-width:0;",
+                        div {
+                            class: "tb-panel-header",
+                            span { "Schema" }
+                            span { style: "font-size:11px; color:var(--text-dim);", "{schema_draft}" }
+                            span { style: "flex:1;" }
+                            button {
+                                class: "tb-btn-ghost",
+                                style: "padding:3px 10px; font-size:11px;",
+                                title: "由当前输出推断 schema（覆盖当前内容）",
+                                onclick: infer_schema,
+                                "推断 Schema"
+                            }
+                            button {
+                                class: "tb-icon-btn",
+                                style: "width:24px; height:24px; font-size:13px;",
+                                title: "关闭",
+                                onclick: move |_| show_schema.set(false),
+                                "✕"
+                            }
+                        }
+                        textarea {
+                            id: "json-schema",
+                            class: "tb-textarea tb-scroll",
+                            value: "{schema_text}",
+                            oninput: move |e| schema_text.set(e.value()),
+                            placeholder: "粘贴 JSON Schema（draft 2020-12 / draft-07），或点击“推断 Schema”..."
+                        }
+                        // 校验结果：点击跳到输出中的对应行
+                        div {
+                            class: "tb-scroll",
+                            style: "max-height:40%; overflow:auto; border-top:1px solid var(--border); font-size:12px;",
+                            if !schema_error.read().is_empty() {
+                                div { style: "padding:8px 12px; color:var(--danger);", "{schema_error}" }
+                            } else if schema_text.read().trim().is_empty() || processed_value.read().is_none() {
+                                div { style: "padding:8px 12px; color:var(--text-dim);", "（无数据）" }
+                            } else if schema_issues.read().is_empty() {
+                                div { style: "padding:8px 12px; color:var(--ok);", "✓ 校验通过" }
+                            } else {
+                                div { style: "padding:6px 12px; color:var(--danger); font-weight:600;", "{schema_issues.read().len()} 处不符合" }
+                                for (i, (record, issue)) in schema_issues.read().iter().cloned().enumerate() {
+                                    div {
+                                        key: "{i}",
+                                        class: "diff-item",
+                                        title: "schema 路径: {issue.schema_path}",
+                                        onclick: {
+                                            let pointer = issue.instance_path.clone();
+                                            move |_| jump_to_instance(record, pointer.clone())
+                                        },
+                                        span {
+                                            class: "diff-badge",
+                                            if let Some(r) = record {
+                                                "#{record_lines.read().get(r).copied().unwrap_or(r + 1)} "
+                                            }
+                                            if issue.instance_path.is_empty() { "/" } else { "{issue.instance_path}" }
+                                        }
+                                        span { style: "color:var(--text);", "{issue.message}" }
+                                        span { style: "color:var(--text-dim);", "{issue.schema_path}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
 
-            // 错误信息
+            // 错误信息（解析错误附带出错位置片段与修复入口）
             if !error_message().is_empty() {
                 div {
                     class: "error-message",
-                    style: "padding:10px 12px; color:var(--danger); background:rgba(244,135,113,.1); border:1px solid var(--danger); border-radius:var(--radius-sm); margin:0 14px 14px; font-size:13px;",
-                    "{error_message}"
+                    style: "padding:10px 12px; color:var(--danger); background:rgba(244,135,113,.1); border:1px solid var(--danger); border-radius:var(--radius-sm); margin:0 14px 14px; font-size:13px; display:flex; flex-direction:column; gap:8px;",
+                    div {
+                        style: "display:flex; align-items:center; gap:8px;",
+                        span { style: "flex:1;", "{error_message}" }
+                        if let Some(d) = parse_diag.read().clone() {
+                            button {
+                                class: "tb-btn",
+                                style: "padding:3px 10px; font-size:11px;",
+                                title: "在输入框中选中出错位置",
+                                onclick: move |_| {
+                                    let text = input.read().clone();
+                                    // 诊断来自上一次（防抖后的）解析，其间输入可能已改动：位置失效时不定位
+                                    let Some(rest) = text.get(d.offset..) else {
+                                        return;
+                                    };
+                                    let end = rest.chars().next().map_or(d.offset, |c| d.offset + c.len_utf8());
+                                    select_input_range(&text, d.offset, end);
+                                },
+                                "定位 {d.line}:{d.column}"
+                            }
+                            // 自动修复针对单个文档；NDJSON 的逐行错误请直接定位修改
+                            if !is_ndjson {
+                                button {
+                                    class: "tb-btn",
+                                    style: "padding:3px 10px; font-size:11px;",
+                                    onclick: move |_| repair_preview.set(Some(repair(&input.read()))),
+                                    "尝试修复"
+                                }
+                            }
+                        }
+                    }
+                    if let Some(d) = parse_diag.read().clone() {
+                        pre {
+                            style: "margin:0; padding:8px 10px; background:var(--bg-input); color:var(--text); border-radius:var(--radius-sm); font-family:'Menlo','Monaco','Consolas',monospace; font-size:12px; line-height:1.5; overflow:auto;",
+                            "{d.snippet}"
+                        }
+                    }
+                    // 修复预览：列出每处修改，确认后才写回输入
+                    if let Some(r) = repair_preview.read().clone() {
+                        div {
+                            style: "display:flex; flex-direction:column; gap:4px; padding:8px 10px; background:var(--bg-input); color:var(--text); border-radius:var(--radius-sm); font-size:12px;",
+                            if r.fixes.is_empty() {
+                                span { style: "color:var(--text-dim);", "未发现可自动修复的问题" }
+                            } else {
+                                span { style: "color:var(--text-bright); font-weight:600;", "将进行 {r.fixes.len()} 处修改：" }
+                                div {
+                                    class: "tb-scroll",
+                                    style: "max-height:120px; overflow:auto;",
+                                    for (i, (line, desc)) in r.fixes.iter().cloned().enumerate() {
+                                        div { key: "{i}", "第 {line} 行：{desc}" }
+                                    }
+                                }
+                                if let Err(e) = serde_json::from_str::<Value>(&r.text) {
+                                    span { style: "color:var(--danger);", "修复后仍无法解析：{e}" }
+                                }
+                                div {
+                                    style: "display:flex; gap:8px; margin-top:4px;",
+                                    button {
+                                        class: "tb-btn-primary",
+                                        onclick: move |_| commit_input(r.text.clone()),
+                                        "应用修复"
+                                    }
+                                    button {
+                                        class: "tb-btn",
+                                        onclick: move |_| repair_preview.set(None),
+                                        "取消"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ---------- 超大文本输出的窗口化视图 ----------
+// 只高亮并挂载可视区上下 OVERSCAN 行，外层用占位高度撑出完整滚动条。
+#[component]
+fn VirtualText(
+    text: Signal<String>,
+    rows: Signal<Option<RowIndex>>,
+    positions: Signal<Vec<Range<usize>>>,
+    current: Signal<usize>,
+    on_activate: EventHandler<()>,
+) -> Element {
+    let mut scroll_top = use_signal(|| 0.0f64);
+    let mut viewport = use_signal(|| 600.0f64);
+    let mut container = use_signal(|| Option::<Rc<MountedData>>::None);
+
+    // 挂载 / 滚动后读取滚动位置与可视高度
+    let measure = move || async move {
+        let Some(el) = container.peek().clone() else {
+            return;
+        };
+        if let Ok(offset) = el.get_scroll_offset().await {
+            scroll_top.set(offset.y);
+        }
+        if let Ok(rect) = el.get_client_rect().await {
+            viewport.set(rect.height());
+        }
+    };
+
+    // 当前匹配不在可视区时滚动过去（hook 须在下面的提前返回之前调用）
+    use_effect(move || {
+        let list = positions.read();
+        if list.is_empty() {
+            return;
+        }
+        let pos = list[(*current.read()).min(list.len() - 1)].start;
+        let Some((row, total)) = rows.read().as_ref().map(|r| (r.row_of(pos), r.row_count())) else {
+            return;
+        };
+        let scale = (total as f64 * ROW_HEIGHT / MAX_SCROLL_PX).max(1.0);
+        let (top, height) = (*scroll_top.peek() * scale, *viewport.peek());
+        let y = row as f64 * ROW_HEIGHT;
+        if y < top || y + ROW_HEIGHT > top + height {
+            let target = ((y - height / 3.0) / scale).max(0.0);
+            dioxus::document::eval(&format!(
+                "var el=document.getElementById('json-output');if(el)el.scrollTop={target};"
+            ));
+        }
+    });
+
+    let text_ref = text.read();
+    let rows_ref = rows.read();
+    let Some(index) = rows_ref.as_ref() else {
+        return rsx! {};
+    };
+    let total = index.row_count();
+    let full_height = total as f64 * ROW_HEIGHT;
+    let scale = (full_height / MAX_SCROLL_PX).max(1.0);
+
+    let visible_top = *scroll_top.read() * scale / ROW_HEIGHT;
+    let first = (visible_top as usize).saturating_sub(OVERSCAN).min(total);
+    let last = ((visible_top + *viewport.read() / ROW_HEIGHT).ceil() as usize + OVERSCAN).min(total);
+    // 窗口在占位层中的位置：未压缩时即 first * ROW_HEIGHT
+    let window_top = *scroll_top.read() - (visible_top - first as f64) * ROW_HEIGHT;
+    let spacer_height = full_height / scale;
+
+    // 逐行高亮：状态从窗口起点开始顺次推进；匹配区间裁剪到行内并换算成行内偏移
+    // （正则匹配可能跨行，按结束位置找第一个与本行相交的匹配）
+    let pos = positions.read();
+    let cur = (!pos.is_empty()).then(|| (*current.read()).min(pos.len() - 1));
+    let mut state = index.state_at(&text_ref, first);
+    let mut lines = Vec::with_capacity(last - first);
+    for row in first..last {
+        let r = index.row_range(&text_ref, row);
+        let slice = &text_ref[r.clone()];
+        let lo = pos.partition_point(|m| m.end <= r.start);
+        let hi = pos.partition_point(|m| m.start < r.end);
+        let local: Vec<Range<usize>> = pos[lo..hi.max(lo)]
+            .iter()
+            .map(|m| m.start.max(r.start) - r.start..m.end.min(r.end) - r.start)
+            .collect();
+        let cur_local = match cur {
+            Some(c) if (lo..hi).contains(&c) => c - lo,
+            _ => usize::MAX,
+        };
+        lines.push((row, highlight_json_from(&state, slice, &local, cur_local)));
+        state.advance(slice);
+    }
+
+    rsx! {
+        div {
+            id: "json-output",
+            class: "tb-scroll",
+            tabindex: "0",
+            "data-scale": "{scale}",
+            style: "flex:1; background:var(--bg-input); color:var(--text); margin:0; padding:14px; overflow:auto; white-space:pre; font-family:'Menlo','Monaco','Consolas',monospace; font-size:13px; line-height:1.6;",
+            onmounted: move |e: MountedEvent| {
+                container.set(Some(e.data()));
+                spawn(measure());
+            },
+            onscroll: move |_| {
+                spawn(measure());
+            },
+            onclick: move |_| on_activate.call(()),
+            onfocusin: move |_| on_activate.call(()),
+            onmouseenter: move |_| on_activate.call(()),
+            div {
+                style: "position:relative; height:{spacer_height}px;",
+                div {
+                    style: "position:absolute; top:{window_top}px; left:0; min-width:100%;",
+                    for (row, html) in lines {
+                        div { key: "{row}", style: "height:{ROW_HEIGHT}px;", dangerous_inner_html: "{html}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ---------- 树形视图的面包屑与右键菜单 ----------
+/// 悬停 / 选中的节点路径与右键菜单位置（路径为 processed_value 中的路径）
+#[derive(Clone, Copy)]
+struct TreeCursor {
+    hovered: Signal<Option<Vec<Seg>>>,
+    selected: Signal<Option<Vec<Seg>>>,
+    menu: Signal<Option<(Vec<Seg>, f64, f64)>>,
+}
+
+/// 树形视图共享的嵌入 JSON 展开点
+#[derive(Clone, Copy)]
+struct EmbeddedNodes(Signal<Embedded>);
+
+/// 树形视图的查找结果、当前匹配与过滤开关
+#[derive(Clone, Copy)]
+struct TreeFind {
+    matches: Signal<TreeMatches>,
+    current: Signal<Option<Vec<Seg>>>,
+    /// 只显示含匹配的分支（没有查找词时不生效）
+    filter: Signal<bool>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CopyKind {
+    Path(PathSyntax),
+    Value,
+    Macro,
+}
+
+impl CopyKind {
+    const ALL: [CopyKind; 6] = [
+        Self::Path(PathSyntax::JsonPath),
+        Self::Path(PathSyntax::Js),
+        Self::Path(PathSyntax::Pointer),
+        Self::Path(PathSyntax::Jq),
+        Self::Value,
+        Self::Macro,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Path(s) => s.label(),
+            Self::Value => "值",
+            Self::Macro => "json!",
+        }
+    }
+
+    fn title(self) -> String {
+        match self {
+            Self::Path(s) => format!("复制路径（{}）", s.label()),
+            Self::Value => "复制值（展开过的字符串还原为原文）".into(),
+            Self::Macro => "复制为 serde_json::json! 字面量".into(),
+        }
+    }
+
+    /// NDJSON 时路径首段是记录下标，复制的路径相对于该记录
+    fn render(self, doc: &Value, path: &[Seg], records: bool, embedded: &Embedded) -> Option<String> {
+        let rel = if records { &path[path.len().min(1)..] } else { path };
+        match self {
+            Self::Path(s) => Some(s.format(rel)),
+            Self::Value => get(doc, path).and_then(|v| serde_json::to_string_pretty(&collapse(v, path, embedded)).ok()),
+            Self::Macro => get(doc, path).map(to_json_macro),
+        }
+    }
+}
+
+#[component]
+fn TreeBreadcrumbs(doc: Signal<Option<SharedValue>>, record_lines: Signal<Vec<usize>>) -> Element {
+    let cursor = use_context::<TreeCursor>();
+    let EmbeddedNodes(embedded) = use_context::<EmbeddedNodes>();
+    let records = !record_lines.read().is_empty();
+    let selected = cursor.selected.read().clone();
+    let shown = cursor.hovered.read().clone().or_else(|| selected.clone());
+
+    let copy = move |kind: CopyKind, path: &[Seg], button_id: &str| {
+        let Some(doc) = doc.read().clone() else {
+            return;
+        };
+        if let Some(text) = kind.render(&doc, path, records, &embedded.read()) {
+            copy_to_clipboard(&text, button_id);
+        }
+    };
+
+    // 面包屑各段：(显示文本, 点击后选中的路径)
+    let crumbs: Vec<(String, Vec<Seg>)> = match &shown {
+        Some(path) => {
+            let (root, base) = match (records, path.first()) {
+                (true, Some(Seg::Index(i))) => {
+                    let line = record_lines.read().get(*i).copied().unwrap_or(i + 1);
+                    (format!("#{}", line), 1)
+                }
+                _ => ("$".to_string(), 0),
+            };
+            std::iter::once((root, path[..base].to_vec()))
+                .chain((base..path.len()).map(|i| (crumb(&path[i]), path[..=i].to_vec())))
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    let menu = cursor.menu.read().clone();
+
+    rsx! {
+        div {
+            style: "display:flex; align-items:center; gap:4px; padding:4px 8px; background:var(--bg-header); border-bottom:1px solid var(--border); font-size:12px; min-height:30px;",
+            div {
+                style: "flex:1; min-width:0; overflow:hidden; text-overflow:ellipsis; white-space:nowrap; font-family:'Menlo','Monaco','Consolas',monospace;",
+                if crumbs.is_empty() {
+                    span { style: "color:var(--text-dim);", "悬停或点击节点查看路径" }
+                }
+                for (i, (text, target)) in crumbs.into_iter().enumerate() {
+                    if i > 0 {
+                        span { key: "sep{i}", style: "color:var(--text-dim); padding:0 4px;", "›" }
+                    }
+                    span {
+                        key: "{i}",
+                        class: "json-crumb",
+                        title: "选中该节点",
+                        onclick: move |_| {
+                            let mut selected = cursor.selected;
+                            selected.set(Some(target.clone()));
+                        },
+                        "{text}"
+                    }
+                }
+            }
+            // 复制操作作用于选中的节点
+            for kind in CopyKind::ALL {
+                button {
+                    key: "{kind.label()}",
+                    id: "crumb-copy-{kind.label()}",
+                    class: "json-act",
+                    disabled: selected.is_none(),
+                    title: "{kind.title()}",
+                    onclick: {
+                        let selected = selected.clone();
+                        move |_| {
+                            if let Some(path) = &selected {
+                                copy(kind, path, &format!("crumb-copy-{}", kind.label()));
+                            }
+                        }
+                    },
+                    "{kind.label()}"
+                }
+            }
+        }
+        // 右键菜单：透明遮罩接住菜单外的点击
+        if let Some((path, x, y)) = menu {
+            div {
+                style: "position:fixed; inset:0; z-index:50;",
+                onclick: move |_| {
+                    let mut menu = cursor.menu;
+                    menu.set(None);
+                },
+                oncontextmenu: move |e: MouseEvent| {
+                    e.prevent_default();
+                    let mut menu = cursor.menu;
+                    menu.set(None);
+                },
+            }
+            div {
+                class: "json-menu",
+                style: "left:{x}px; top:{y}px;",
+                for kind in CopyKind::ALL {
+                    div {
+                        key: "{kind.label()}",
+                        class: "json-menu-item",
+                        onclick: {
+                            let path = path.clone();
+                            move |_| {
+                                copy(kind, &path, "");
+                                let mut menu = cursor.menu;
+                                menu.set(None);
+                            }
+                        },
+                        "{kind.title()}"
+                    }
                 }
             }
         }
@@ -567,43 +2114,382 @@ pub fn JsonFormatterTool() -> Element {
 }
 
 // ---------- 递归树形视图组件 ----------
+/// 树形视图中正在拖动的数组元素：所在数组的路径 + 下标
+#[derive(Clone, PartialEq)]
+struct DragItem {
+    parent: Vec<Seg>,
+    index: usize,
+}
+
+/// 节点当前正在编辑的部分
+#[derive(Clone, Copy, PartialEq)]
+enum EditField {
+    Key,
+    Value,
+}
+
 #[derive(Props, Clone, PartialEq)]
 struct JsonNodeProps {
-    value: Value,
+    /// 整个文档；节点按 `path` 取值，父子间只传引用计数，不拷贝子树
+    doc: SharedValue,
+    /// 节点在文档中的路径（同时也是编辑时源文档中的路径）
+    #[props(default)]
+    path: Vec<Seg>,
     #[props(default)]
     label: Option<String>,
     #[props(default)]
     is_index: bool,
+    /// 根节点不可删除 / 复制 / 拖动
+    #[props(default)]
+    is_root: bool,
+    /// 编辑回调；None 表示只读
+    #[props(default)]
+    on_edit: Option<EventHandler<TreeEdit>>,
+    /// 默认展开的层数；更深的容器（以及成员超过 TREE_PAGE 的容器）初始折叠
+    #[props(default = usize::MAX)]
+    expand_depth: usize,
+    #[props(default)]
+    depth: usize,
 }
 
 #[component]
 fn JsonNode(props: JsonNodeProps) -> Element {
-    let JsonNodeProps { value, label, is_index } = props;
-    let mut expanded = use_signal(|| true);
+    let JsonNodeProps { doc, path, label, is_index, is_root, on_edit, expand_depth, depth } = props;
+    let count = match get(&doc, &path) {
+        Some(Value::Object(m)) => m.len(),
+        Some(Value::Array(a)) => a.len(),
+        _ => 0,
+    };
+    // 折叠的容器不挂载子节点；展开后也只先挂载前 TREE_PAGE 个
+    let mut expanded = use_signal(|| depth < expand_depth && count <= TREE_PAGE);
+    let mut shown = use_signal(|| TREE_PAGE);
+    let mut editing = use_signal(|| Option::<EditField>::None);
+    let mut draft = use_signal(String::new);
+    let mut drop_hover = use_signal(|| false);
+    let mut drag = use_context::<Signal<Option<DragItem>>>();
+    let cursor = use_context::<TreeCursor>();
+    // 每个节点只订阅“自己是否被选中”，选中变化时不会重绘整棵树
+    let is_selected = use_memo({
+        let path = path.clone();
+        move || cursor.selected.read().as_ref() == Some(&path)
+    });
+    let EmbeddedNodes(embedded) = use_context::<EmbeddedNodes>();
+    let is_embedded = use_memo({
+        let path = path.clone();
+        move || embedded.read().contains_key(&path)
+    });
+    // 查找：本节点匹配的部分、是否当前匹配、过滤模式下是否保留
+    let find = use_context::<TreeFind>();
+    let hit = use_memo({
+        let path = path.clone();
+        move || find.matches.read().parts.get(&path).copied().unwrap_or_default()
+    });
+    let is_current = use_memo({
+        let path = path.clone();
+        move || find.current.read().as_ref() == Some(&path)
+    });
+    let kept = use_memo({
+        let path = path.clone();
+        move || {
+            let matches = find.matches.read();
+            !(*find.filter.read() && matches.active) || (is_root && path.is_empty()) || matches.keeps(&path)
+        }
+    });
+    // 匹配的祖先自动展开，并放开分页直到通往匹配的子节点
+    use_effect({
+        let path = path.clone();
+        move || {
+            if let Some(&last) = find.matches.read().reveal.get(&path) {
+                expanded.set(true);
+                if last >= *shown.peek() {
+                    shown.set((last / TREE_PAGE + 1) * TREE_PAGE);
+                }
+            }
+        }
+    });
+    // 成为当前匹配时滚动到可见处
+    let mut line_el = use_signal(|| Option::<Rc<MountedData>>::None);
+    use_effect(move || {
+        if !is_current() {
+            return;
+        }
+        if let Some(el) = line_el.read().clone() {
+            spawn(async move {
+                let _ = el.scroll_to(ScrollBehavior::Smooth).await;
+            });
+        }
+    });
+    let Some(value) = get(&doc, &path) else {
+        return rsx! {};
+    };
+    if !kept() {
+        return rsx! {};
+    }
+    let hit_class = |on: bool| match (on, is_current()) {
+        (false, _) => "",
+        (true, false) => " json-hit",
+        (true, true) => " json-hit json-hit-current",
+    };
+    let key_hit = hit_class(hit().key);
+    let value_hit = hit_class(hit().value);
+    let onmounted = move |e: MountedEvent| line_el.set(Some(e.data()));
     let toggle_char = if *expanded.read() { "▾" } else { "▸" };
     // rsx 文本节点中 { } 是特殊字符，用变量绕过
     let ob = "{";
     let cb = "}";
 
-    // 标签前缀（object key 带引号；array index 不带）
+    let editable = on_edit.is_some();
+    let emit = move |edit: TreeEdit| {
+        if let Some(h) = on_edit {
+            h.call(edit);
+        }
+    };
+    let child_path = |seg: Seg| {
+        let mut p = path.clone();
+        p.push(seg);
+        p
+    };
+    // 数组元素：(所在数组路径, 下标)，用于拖动排序
+    let array_slot = match path.split_last() {
+        Some((Seg::Index(i), parent)) if editable && !is_root => Some((parent.to_vec(), *i)),
+        _ => None,
+    };
+
+    // 提交输入框内容；先取出 editing，Enter 之后的 blur 不会重复提交
+    let is_number = value.is_number();
+    let commit = {
+        let path = path.clone();
+        move || {
+            let Some(field) = editing.write().take() else {
+                return;
+            };
+            let text = draft.peek().clone();
+            let edit = match field {
+                EditField::Key => TreeEdit::Rename(path.clone(), text),
+                EditField::Value if is_number => match serde_json::from_str::<Value>(text.trim()) {
+                    Ok(n @ Value::Number(_)) => TreeEdit::Set(path.clone(), n),
+                    _ => return,
+                },
+                EditField::Value => TreeEdit::Set(path.clone(), Value::String(text)),
+            };
+            emit(edit);
+        }
+    };
+    // 数字输入实时校验，非法时标红且 Enter 不提交
+    let invalid = *editing.read() == Some(EditField::Value)
+        && is_number
+        && !serde_json::from_str::<Value>(draft.read().trim()).is_ok_and(|v| v.is_number());
+    let editor: Element = rsx! {
+        input {
+            class: if invalid { "json-edit-input invalid" } else { "json-edit-input" },
+            value: "{draft}",
+            oninput: move |e| draft.set(e.value()),
+            onmounted: move |e: MountedEvent| async move {
+                let _ = e.set_focus(true).await;
+            },
+            onkeydown: {
+                let mut commit = commit.clone();
+                move |e: KeyboardEvent| {
+                    // 不让 Cmd+Z 等冒泡到工具级快捷键
+                    e.stop_propagation();
+                    match e.key() {
+                        Key::Enter if !invalid => commit(),
+                        Key::Escape => editing.set(None),
+                        _ => {}
+                    }
+                }
+            },
+            onblur: {
+                let mut commit = commit.clone();
+                move |_| commit()
+            },
+        }
+    };
+
+    // 标签前缀（object key 带引号；array index 不带，编辑模式下可作为拖动手柄）
     let label_el: Element = match &label {
         Some(l) if is_index => rsx! {
-            span { class: "json-index", "{l}" }
+            span {
+                class: if array_slot.is_some() { "json-index json-drag" } else { "json-index" },
+                draggable: array_slot.is_some(),
+                title: if array_slot.is_some() { "拖动以调整顺序" } else { "" },
+                ondragstart: {
+                    let slot = array_slot.clone();
+                    move |e: DragEvent| {
+                        e.stop_propagation();
+                        if let Some((parent, index)) = slot.clone() {
+                            drag.set(Some(DragItem { parent, index }));
+                        }
+                    }
+                },
+                ondragend: move |_| drag.set(None),
+                "{l}"
+            }
+            span { style: "color:var(--text-dim);", ": " }
+        },
+        Some(_) if *editing.read() == Some(EditField::Key) => rsx! {
+            {editor.clone()}
             span { style: "color:var(--text-dim);", ": " }
         },
         Some(l) => rsx! {
-            span { class: "json-key", "\"{l}\"" }
+            span {
+                class: if editable && !is_root { "json-key json-editable{key_hit}" } else { "json-key{key_hit}" },
+                title: if editable && !is_root { "双击重命名" } else { "" },
+                ondoubleclick: {
+                    let l = l.clone();
+                    move |_| {
+                        if editable && !is_root {
+                            draft.set(l.clone());
+                            editing.set(Some(EditField::Key));
+                        }
+                    }
+                },
+                "\"{l}\""
+            }
             span { style: "color:var(--text-dim);", ": " }
         },
         None => rsx! {},
     };
 
+    // 悬停显示的操作：切换类型 / 添加 / 复制 / 删除
+    let actions: Element = if editable {
+        let is_container = value.is_object() || value.is_array();
+        let current = type_name(value);
+        rsx! {
+            span { class: "json-actions",
+                select {
+                    class: "json-type-select",
+                    title: "切换类型",
+                    onchange: {
+                        let doc = doc.clone();
+                        let path = path.clone();
+                        move |e: FormEvent| {
+                            if let Some(v) = get(&doc, &path) {
+                                emit(TreeEdit::Set(path.clone(), convert_type(v, &e.value())));
+                            }
+                        }
+                    },
+                    for t in TYPES {
+                        option { key: "{t}", value: t, selected: t == current, "{t}" }
+                    }
+                }
+                if is_container {
+                    button {
+                        class: "json-act",
+                        title: "添加成员",
+                        onclick: {
+                            let path = path.clone();
+                            move |_| emit(TreeEdit::Add(path.clone()))
+                        },
+                        "+"
+                    }
+                }
+                if !is_root {
+                    button {
+                        class: "json-act",
+                        title: "复制节点",
+                        onclick: {
+                            let path = path.clone();
+                            move |_| emit(TreeEdit::Duplicate(path.clone()))
+                        },
+                        "⧉"
+                    }
+                    button {
+                        class: "json-act",
+                        title: "删除",
+                        onclick: {
+                            let path = path.clone();
+                            move |_| emit(TreeEdit::Delete(path.clone()))
+                        },
+                        "✕"
+                    }
+                }
+            }
+        }
+    } else {
+        rsx! {}
+    };
+
+    // 同一数组内的元素互为拖放目标
+    let accepts_drop = {
+        let slot = array_slot.clone();
+        move || match (slot.as_ref(), drag.peek().as_ref()) {
+            (Some((parent, _)), Some(d)) => d.parent == *parent,
+            _ => false,
+        }
+    };
+    let line_class = format!(
+        "json-line{}{}",
+        if *drop_hover.read() { " json-drop" } else { "" },
+        if is_selected() { " json-selected" } else { "" }
+    );
+    // 点击选中；悬停更新面包屑；右键打开复制菜单
+    let onclick = {
+        let path = path.clone();
+        let mut selected = cursor.selected;
+        move |_: MouseEvent| selected.set(Some(path.clone()))
+    };
+    let onmouseenter = {
+        let path = path.clone();
+        let mut hovered = cursor.hovered;
+        move |_: MouseEvent| hovered.set(Some(path.clone()))
+    };
+    let oncontextmenu = {
+        let path = path.clone();
+        let (mut selected, mut menu) = (cursor.selected, cursor.menu);
+        move |e: MouseEvent| {
+            e.prevent_default();
+            let p = e.client_coordinates();
+            selected.set(Some(path.clone()));
+            menu.set(Some((path.clone(), p.x, p.y)));
+        }
+    };
+    let ondragover = {
+        let accepts_drop = accepts_drop.clone();
+        move |e: DragEvent| {
+            if accepts_drop() {
+                e.prevent_default();
+                drop_hover.set(true);
+            }
+        }
+    };
+    let ondragleave = move |_: DragEvent| drop_hover.set(false);
+    let ondrop = {
+        let slot = array_slot.clone();
+        move |e: DragEvent| {
+            drop_hover.set(false);
+            if !accepts_drop() {
+                return;
+            }
+            e.prevent_default();
+            e.stop_propagation();
+            if let (Some((parent, to)), Some(d)) = (slot.clone(), drag.write().take()) {
+                if d.index != to {
+                    emit(TreeEdit::Move(parent, d.index, to));
+                }
+            }
+        }
+    };
+
+    // 展开后超过 shown 的成员折叠为“显示更多”
+    let limit = *shown.read();
+    let more: Element = if count > limit {
+        rsx! {
+            button {
+                class: "json-act json-more",
+                onclick: move |_| shown += TREE_PAGE,
+                "显示更多（还有 {count - limit} 项）"
+            }
+        }
+    } else {
+        rsx! {}
+    };
+
     match value {
-        Value::Object(map) => {
-            let count = map.len();
-            rsx! {
-                div { class: "json-row",
-                    {label_el.clone()}
+        Value::Object(map) => rsx! {
+            div { class: "json-row",
+                span { class: "{line_class}", ondragover, ondragleave, ondrop, onclick, onmouseenter, oncontextmenu, onmounted,
+                    {label_el}
                     button {
                         class: "json-toggle",
                         onclick: move |_| expanded.with_mut(|v| *v = !*v),
@@ -611,24 +2497,36 @@ fn JsonNode(props: JsonNodeProps) -> Element {
                     }
                     span { class: "json-brace", "{ob}" }
                     span { class: "json-count", "{count} 项" }
-                    if *expanded.read() {
-                        div { class: "json-children",
-                            for (k, v) in map.iter() {
-                                JsonNode { value: v.clone(), label: Some(k.clone()) }
+                    if is_embedded() {
+                        span { class: "json-badge-embed", title: "由 JSON 字符串展开，复制时还原为原字符串", "⟨字符串⟩" }
+                    }
+                    {actions}
+                }
+                if *expanded.read() {
+                    div { class: "json-children",
+                        for k in map.keys().take(limit) {
+                            JsonNode {
+                                key: "{k}",
+                                doc: doc.clone(),
+                                path: child_path(Seg::Key(k.clone())),
+                                label: Some(k.clone()),
+                                on_edit,
+                                expand_depth,
+                                depth: depth + 1,
                             }
                         }
-                    } else {
-                        span { class: "json-collapsed", " … " }
+                        {more}
                     }
-                    span { class: "json-brace", "{cb}" }
+                } else {
+                    span { class: "json-collapsed", " … " }
                 }
+                span { class: "json-brace", "{cb}" }
             }
-        }
-        Value::Array(arr) => {
-            let count = arr.len();
-            rsx! {
-                div { class: "json-row",
-                    {label_el.clone()}
+        },
+        Value::Array(_) => rsx! {
+            div { class: "json-row",
+                span { class: "{line_class}", ondragover, ondragleave, ondrop, onclick, onmouseenter, oncontextmenu, onmounted,
+                    {label_el}
                     button {
                         class: "json-toggle",
                         onclick: move |_| expanded.with_mut(|v| *v = !*v),
@@ -636,42 +2534,99 @@ fn JsonNode(props: JsonNodeProps) -> Element {
                     }
                     span { class: "json-brace", "[" }
                     span { class: "json-count", "{count} 项" }
-                    if *expanded.read() {
-                        div { class: "json-children",
-                            for (i, v) in arr.iter().enumerate() {
-                                JsonNode { value: v.clone(), label: Some(i.to_string()), is_index: true }
+                    if is_embedded() {
+                        span { class: "json-badge-embed", title: "由 JSON 字符串展开，复制时还原为原字符串", "⟨字符串⟩" }
+                    }
+                    {actions}
+                }
+                if *expanded.read() {
+                    div { class: "json-children",
+                        for i in 0..count.min(limit) {
+                            JsonNode {
+                                key: "{i}",
+                                doc: doc.clone(),
+                                path: child_path(Seg::Index(i)),
+                                label: Some(i.to_string()),
+                                is_index: true,
+                                on_edit,
+                                expand_depth,
+                                depth: depth + 1,
                             }
                         }
-                    } else {
-                        span { class: "json-collapsed", " … " }
+                        {more}
                     }
-                    span { class: "json-brace", "]" }
+                } else {
+                    span { class: "json-collapsed", " … " }
                 }
-            }
-        }
-        Value::String(s) => rsx! {
-            div { class: "json-row",
-                {label_el.clone()}
-                span { class: "json-string", "\"{s}\"" }
-            }
-        },
-        Value::Number(n) => rsx! {
-            div { class: "json-row",
-                {label_el.clone()}
-                span { class: "json-number", "{n}" }
+                span { class: "json-brace", "]" }
             }
         },
-        Value::Bool(b) => rsx! {
-            div { class: "json-row",
-                {label_el.clone()}
-                span { class: "json-bool", "{b}" }
-            }
-        },
-        Value::Null => rsx! {
-            div { class: "json-row",
-                {label_el.clone()}
-                span { class: "json-null", "null" }
+        _ => {
+            // 标量：字符串 / 数字双击编辑，布尔单击切换
+            let scalar: Element = match value {
+                Value::String(_) | Value::Number(_) if *editing.read() == Some(EditField::Value) => editor,
+                Value::String(s) => rsx! {
+                    span {
+                        class: if editable { "json-string json-editable{value_hit}" } else { "json-string{value_hit}" },
+                        ondoubleclick: {
+                            let s = s.clone();
+                            move |_| {
+                                if editable {
+                                    draft.set(s.clone());
+                                    editing.set(Some(EditField::Value));
+                                }
+                            }
+                        },
+                        "\"{s}\""
+                    }
+                },
+                Value::Number(n) => rsx! {
+                    span {
+                        class: if editable { "json-number json-editable{value_hit}" } else { "json-number{value_hit}" },
+                        ondoubleclick: {
+                            let n = n.to_string();
+                            move |_| {
+                                if editable {
+                                    draft.set(n.clone());
+                                    editing.set(Some(EditField::Value));
+                                }
+                            }
+                        },
+                        "{n}"
+                    }
+                    if exceeds_js_safe(n) {
+                        span { class: "json-badge-warn", title: "超出 JS 安全整数范围（±2^53−1），JSON.parse 后会丢失精度", "⚠ >2^53" }
+                    }
+                },
+                Value::Bool(b) => rsx! {
+                    span {
+                        class: if editable { "json-bool json-editable{value_hit}" } else { "json-bool{value_hit}" },
+                        title: if editable { "单击切换" } else { "" },
+                        onclick: {
+                            let path = path.clone();
+                            let b = *b;
+                            move |_| {
+                                if editable {
+                                    emit(TreeEdit::Set(path.clone(), Value::Bool(!b)));
+                                }
+                            }
+                        },
+                        "{b}"
+                    }
+                },
+                _ => rsx! {
+                    span { class: "json-null{value_hit}", "null" }
+                },
+            };
+            rsx! {
+                div { class: "json-row",
+                    span { class: "{line_class}", ondragover, ondragleave, ondrop, onclick, onmouseenter, oncontextmenu, onmounted,
+                        {label_el}
+                        {scalar}
+                        {actions}
+                    }
+                }
             }
-        },
+        }
     }
 }
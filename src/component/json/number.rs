@@ -0,0 +1,30 @@
+// src/component/json/number.rs
+//
+// 数字精度。serde_json 开启了 arbitrary_precision，Number 保存的是原文：
+// 64 位 ID、高精度小数、`1.0`、`1E3` 等写法在格式化、排序、树形视图中都原样输出。
+// 这里只负责找出在 JavaScript（IEEE 754 双精度）中会丢失精度的整数。
+
+use serde_json::{Number, Value};
+
+/// `Number.MAX_SAFE_INTEGER`
+pub const JS_MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// 整数写法且绝对值超过 2^53 − 1（小数 / 指数写法不算）
+pub fn exceeds_js_safe(n: &Number) -> bool {
+    let text = n.to_string();
+    let digits = text.strip_prefix('-').unwrap_or(&text);
+    digits.bytes().all(|b| b.is_ascii_digit())
+        && digits
+            .parse::<u64>()
+            .map_or(true, |u| u > JS_MAX_SAFE_INTEGER)
+}
+
+/// 文档中超出 JS 安全整数范围的数字个数
+pub fn count_unsafe(v: &Value) -> usize {
+    match v {
+        Value::Number(n) => exceeds_js_safe(n) as usize,
+        Value::Array(items) => items.iter().map(count_unsafe).sum(),
+        Value::Object(map) => map.values().map(count_unsafe).sum(),
+        _ => 0,
+    }
+}
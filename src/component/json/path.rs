@@ -0,0 +1,165 @@
+// src/component/json/path.rs
+//
+// 树形视图中节点路径的几种文本表示，以及把值输出为 Rust `serde_json::json!` 字面量。
+
+use super::edit::Seg;
+use serde_json::Value;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PathSyntax {
+    /// `$.a[0].b`
+    JsonPath,
+    /// `a[0].b`
+    Js,
+    /// `/a/0/b`（RFC 6901）
+    Pointer,
+    /// `.a[0].b`
+    Jq,
+}
+
+impl PathSyntax {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::JsonPath => "JSONPath",
+            Self::Js => "JS",
+            Self::Pointer => "Pointer",
+            Self::Jq => "jq",
+        }
+    }
+
+    pub fn format(self, path: &[Seg]) -> String {
+        match self {
+            Self::JsonPath => {
+                let mut s = String::from("$");
+                for seg in path {
+                    match seg {
+                        Seg::Key(k) if is_identifier(k, false) => {
+                            s.push('.');
+                            s.push_str(k);
+                        }
+                        Seg::Key(k) => {
+                            let quoted = k.replace('\\', "\\\\").replace('\'', "\\'");
+                            s.push_str(&format!("['{}']", quoted));
+                        }
+                        Seg::Index(i) => s.push_str(&format!("[{}]", i)),
+                    }
+                }
+                s
+            }
+            Self::Js => {
+                let mut s = String::new();
+                for seg in path {
+                    match seg {
+                        Seg::Key(k) if is_identifier(k, true) => {
+                            if !s.is_empty() {
+                                s.push('.');
+                            }
+                            s.push_str(k);
+                        }
+                        Seg::Key(k) => s.push_str(&format!("[{}]", json_quote(k))),
+                        Seg::Index(i) => s.push_str(&format!("[{}]", i)),
+                    }
+                }
+                s
+            }
+            Self::Pointer => path
+                .iter()
+                .map(|seg| match seg {
+                    Seg::Key(k) => format!("/{}", k.replace('~', "~0").replace('/', "~1")),
+                    Seg::Index(i) => format!("/{}", i),
+                })
+                .collect(),
+            Self::Jq => {
+                if path.is_empty() {
+                    return ".".into();
+                }
+                let mut s = String::new();
+                for seg in path {
+                    match seg {
+                        Seg::Key(k) if is_identifier(k, false) => {
+                            s.push('.');
+                            s.push_str(k);
+                        }
+                        // 下标 / 引号 key 直接跟在前一段后面（`.a[0]["b c"]`），只有开头需要 `.`
+                        Seg::Key(k) => {
+                            if s.is_empty() {
+                                s.push('.');
+                            }
+                            s.push_str(&format!("[{}]", json_quote(k)));
+                        }
+                        Seg::Index(i) => {
+                            if s.is_empty() {
+                                s.push('.');
+                            }
+                            s.push_str(&format!("[{}]", i));
+                        }
+                    }
+                }
+                s
+            }
+        }
+    }
+}
+
+/// 面包屑中单段的显示文本
+pub fn crumb(seg: &Seg) -> String {
+    match seg {
+        Seg::Key(k) => k.clone(),
+        Seg::Index(i) => format!("[{}]", i),
+    }
+}
+
+/// 可以用点号访问的 key；JS 额外允许 `$`
+pub fn is_identifier(k: &str, allow_dollar: bool) -> bool {
+    let ok = |c: char| c == '_' || c.is_ascii_alphanumeric() || (allow_dollar && c == '$');
+    k.chars()
+        .next()
+        .is_some_and(|c| ok(c) && !c.is_ascii_digit())
+        && k.chars().all(ok)
+}
+
+fn json_quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+/// `serde_json::json!(...)` 字面量。字符串用 Rust 的转义（`\u{1}` 而非 JSON 的 `\u0001`），
+/// 保证粘贴进 Rust 源码可以直接编译。
+pub fn to_json_macro(v: &Value) -> String {
+    let mut out = String::from("serde_json::json!(");
+    write_rust(v, 0, &mut out);
+    out.push(')');
+    out
+}
+
+fn write_rust(v: &Value, indent: usize, out: &mut String) {
+    let pad = |n: usize| "    ".repeat(n);
+    match v {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(&b.to_string()),
+        Value::Number(n) => out.push_str(&n.to_string()),
+        Value::String(s) => out.push_str(&format!("{:?}", s)),
+        Value::Array(a) if a.is_empty() => out.push_str("[]"),
+        Value::Object(m) if m.is_empty() => out.push_str("{}"),
+        Value::Array(a) => {
+            out.push_str("[\n");
+            for (i, item) in a.iter().enumerate() {
+                out.push_str(&pad(indent + 1));
+                write_rust(item, indent + 1, out);
+                out.push_str(if i + 1 < a.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&pad(indent));
+            out.push(']');
+        }
+        Value::Object(m) => {
+            out.push_str("{\n");
+            for (i, (k, item)) in m.iter().enumerate() {
+                out.push_str(&pad(indent + 1));
+                out.push_str(&format!("{:?}: ", k));
+                write_rust(item, indent + 1, out);
+                out.push_str(if i + 1 < m.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&pad(indent));
+            out.push('}');
+        }
+    }
+}
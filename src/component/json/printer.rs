@@ -0,0 +1,321 @@
+// src/component/json/printer.rs
+//
+// 可配置的 JSON 序列化：缩进宽度 / Tab、短数组与对象合并为一行、非 ASCII 转义、`/` 转义、末尾换行。
+//
+// 合并规则与 Prettier 类似：容器的单行形式（`[1, 2]`、`{"a": 1}`）连同缩进、key 前缀和结尾逗号
+// 不超过 compact_width 时整体写在一行，否则照常展开，再对子节点逐个判断。
+// 可选记录每个 JSON Pointer 所占的行区间，供 Schema 违规项跳转定位。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Indent {
+    Spaces(u8),
+    Tab,
+}
+
+impl Indent {
+    pub const ALL: [Indent; 3] = [Indent::Spaces(2), Indent::Spaces(4), Indent::Tab];
+
+    pub fn label(&self) -> String {
+        match self {
+            Indent::Spaces(n) => n.to_string(),
+            Indent::Tab => "Tab".into(),
+        }
+    }
+
+    pub fn unit(&self) -> String {
+        match self {
+            Indent::Spaces(n) => " ".repeat(*n as usize),
+            Indent::Tab => "\t".into(),
+        }
+    }
+
+    /// 计算行宽时 Tab 按 4 列算
+    fn width(&self) -> usize {
+        match self {
+            Indent::Spaces(n) => *n as usize,
+            Indent::Tab => 4,
+        }
+    }
+}
+
+/// 单行合并宽度的可选值（0 = 不合并）
+pub const COMPACT_WIDTHS: [usize; 5] = [0, 40, 60, 80, 120];
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrintOptions {
+    pub indent: Indent,
+    /// 容器的单行形式不超过该宽度时写在一行；0 = 总是展开
+    pub compact_width: usize,
+    /// 非 ASCII 字符写成 `\uXXXX`（补充平面字符为代理对）
+    pub ascii: bool,
+    /// `/` 写成 `\/`（嵌入 `<script>` 时避免出现 `</`）
+    pub escape_slash: bool,
+    pub trailing_newline: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            indent: Indent::Spaces(2),
+            compact_width: 0,
+            ascii: false,
+            escape_slash: false,
+            trailing_newline: false,
+        }
+    }
+}
+
+/// 美化输出；与 `serde_json::to_string_pretty` 在默认选项下逐字节一致
+pub fn pretty(v: &Value, opts: &PrintOptions) -> String {
+    let mut w = Writer::new(opts, true, false);
+    w.value(v, "", 0, 0);
+    w.out
+}
+
+/// 压缩输出（只应用转义选项）
+pub fn minified(v: &Value, opts: &PrintOptions) -> String {
+    let mut w = Writer::new(opts, false, false);
+    w.value(v, "", 0, 0);
+    w.out
+}
+
+/// 美化输出，并记录每个 JSON Pointer 所占的行区间（含首尾，0 起）
+pub fn pretty_with_lines(
+    v: &Value,
+    opts: &PrintOptions,
+) -> (String, HashMap<String, (usize, usize)>) {
+    let mut w = Writer::new(opts, true, true);
+    w.value(v, "", 0, 0);
+    w.lines.insert(String::new(), (0, w.line));
+    (w.out, w.lines)
+}
+
+struct Writer<'a> {
+    opts: &'a PrintOptions,
+    pretty: bool,
+    record: bool,
+    unit: String,
+    out: String,
+    line: usize,
+    lines: HashMap<String, (usize, usize)>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(opts: &'a PrintOptions, pretty: bool, record: bool) -> Self {
+        Self {
+            opts,
+            pretty,
+            record,
+            unit: opts.indent.unit(),
+            out: String::new(),
+            line: 0,
+            lines: HashMap::new(),
+        }
+    }
+
+    fn newline(&mut self, depth: usize) {
+        self.out.push('\n');
+        self.line += 1;
+        for _ in 0..depth {
+            self.out.push_str(&self.unit);
+        }
+    }
+
+    /// `prefix` 为本行在值之前已占的列数（缩进 + key），用于判断能否合并为一行
+    fn value(&mut self, v: &Value, ptr: &str, depth: usize, prefix: usize) {
+        match v {
+            Value::Null => self.out.push_str("null"),
+            Value::Bool(b) => self.out.push_str(if *b { "true" } else { "false" }),
+            Value::Number(n) => self.out.push_str(&n.to_string()),
+            Value::String(s) => write_str(&mut self.out, s, self.opts),
+            Value::Array(a) if a.is_empty() => self.out.push_str("[]"),
+            Value::Object(m) if m.is_empty() => self.out.push_str("{}"),
+            _ if self.pretty && self.fits_inline(v, prefix) => {
+                let start = self.line;
+                self.inline(v);
+                if self.record {
+                    self.record_children(v, ptr, start);
+                }
+            }
+            Value::Array(a) => {
+                self.out.push('[');
+                for (i, child) in a.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    let p = self.child_ptr(ptr, &i.to_string());
+                    if self.pretty {
+                        self.newline(depth + 1);
+                    }
+                    let start = self.line;
+                    let col = (depth + 1) * self.opts.indent.width();
+                    self.value(child, &p, depth + 1, col);
+                    if self.record {
+                        self.lines.insert(p, (start, self.line));
+                    }
+                }
+                if self.pretty {
+                    self.newline(depth);
+                }
+                self.out.push(']');
+            }
+            Value::Object(m) => {
+                self.out.push('{');
+                for (i, (k, child)) in m.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    let p = self.child_ptr(ptr, k);
+                    if self.pretty {
+                        self.newline(depth + 1);
+                    }
+                    let start = self.line;
+                    let key_start = self.out.len();
+                    write_str(&mut self.out, k, self.opts);
+                    self.out.push_str(if self.pretty { ": " } else { ":" });
+                    let col = (depth + 1) * self.opts.indent.width()
+                        + self.out[key_start..].chars().count();
+                    self.value(child, &p, depth + 1, col);
+                    if self.record {
+                        self.lines.insert(p, (start, self.line));
+                    }
+                }
+                if self.pretty {
+                    self.newline(depth);
+                }
+                self.out.push('}');
+            }
+        }
+    }
+
+    fn child_ptr(&self, ptr: &str, seg: &str) -> String {
+        if self.record {
+            format!("{}/{}", ptr, seg.replace('~', "~0").replace('/', "~1"))
+        } else {
+            String::new()
+        }
+    }
+
+    /// 合并为一行的容器，子孙节点都在同一行
+    fn record_children(&mut self, v: &Value, ptr: &str, line: usize) {
+        let children: Vec<(String, &Value)> = match v {
+            Value::Array(a) => a
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (i.to_string(), c))
+                .collect(),
+            Value::Object(m) => m.iter().map(|(k, c)| (k.clone(), c)).collect(),
+            _ => return,
+        };
+        for (seg, child) in children {
+            let p = self.child_ptr(ptr, &seg);
+            self.record_children(child, &p, line);
+            self.lines.insert(p, (line, line));
+        }
+    }
+
+    fn fits_inline(&self, v: &Value, prefix: usize) -> bool {
+        let limit = self.opts.compact_width;
+        // 结尾可能还有一个逗号
+        limit > 0 && inline_width(v, self.opts, limit.saturating_sub(prefix + 1)).is_some()
+    }
+
+    fn inline(&mut self, v: &Value) {
+        match v {
+            Value::Array(a) if !a.is_empty() => {
+                self.out.push('[');
+                for (i, child) in a.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.inline(child);
+                }
+                self.out.push(']');
+            }
+            Value::Object(m) if !m.is_empty() => {
+                self.out.push('{');
+                for (i, (k, child)) in m.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    write_str(&mut self.out, k, self.opts);
+                    self.out.push_str(": ");
+                    self.inline(child);
+                }
+                self.out.push('}');
+            }
+            other => self.value(other, "", 0, 0),
+        }
+    }
+}
+
+/// 单行形式的宽度；超过 `budget` 时提前返回 None，避免对大容器做完整计算
+fn inline_width(v: &Value, opts: &PrintOptions, budget: usize) -> Option<usize> {
+    let w = match v {
+        Value::Null => 4,
+        Value::Bool(b) => {
+            if *b {
+                4
+            } else {
+                5
+            }
+        }
+        Value::Number(n) => n.to_string().len(),
+        Value::String(s) => str_width(s, opts),
+        Value::Array(a) => {
+            let mut w = 2;
+            for (i, child) in a.iter().enumerate() {
+                w += if i > 0 { 2 } else { 0 };
+                w += inline_width(child, opts, budget.checked_sub(w)?)?;
+            }
+            w
+        }
+        Value::Object(m) => {
+            let mut w = 2;
+            for (i, (k, child)) in m.iter().enumerate() {
+                w += if i > 0 { 2 } else { 0 };
+                w += str_width(k, opts) + 2;
+                w += inline_width(child, opts, budget.checked_sub(w)?)?;
+            }
+            w
+        }
+    };
+    (w <= budget).then_some(w)
+}
+
+fn str_width(s: &str, opts: &PrintOptions) -> usize {
+    let mut buf = String::new();
+    write_str(&mut buf, s, opts);
+    buf.chars().count()
+}
+
+/// 写出带引号的字符串。控制字符的写法与 serde_json 一致（`\n`、`\u001f` 等）
+fn write_str(out: &mut String, s: &str, opts: &PrintOptions) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '/' if opts.escape_slash => out.push_str("\\/"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c if opts.ascii && !c.is_ascii() => {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
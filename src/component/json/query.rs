@@ -0,0 +1,1190 @@
+// src/component/json/query.rs
+//
+// JSON 查询：JSONPath 与 jq 子集。
+//
+// - 以 `$` 开头按 JSONPath 求值：`$.a.b`、`$['a']`、`$[0]`、`$[-1]`、`$[*]`、`$..name`、
+//   `$[0,2]`、`$[1:3]`、`$[?(@.price < 10 && @.tag)]`。结果是匹配节点列表（数组）。
+// - 其它按 jq 子集求值：`.`、`.a.b`、`.["a"]`、`.[0]`、`.[1:3]`、`.[]`、`..`、`|`、`,`、
+//   `[...]`、`select(f)`、`map(f)`、`keys`、`length`、`not`、`and`/`or`、比较运算、`?`。
+//   jq 的输出是值流：只有一个结果时直接返回该值，否则包成数组。
+
+use super::edit::Seg;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// 对 `root` 执行查询，返回可直接展示的单个值。
+pub fn run_query(query: &str, root: &Value) -> Result<Value, String> {
+    let query = query.trim();
+    if query.starts_with('$') {
+        let path = JsonPathParser::new(query)?.parse_query()?;
+        Ok(Value::Array(
+            select_path(&path, root, root)
+                .into_iter()
+                .cloned()
+                .collect(),
+        ))
+    } else {
+        let expr = JqParser::new(query)?.parse_all()?;
+        let mut out = eval_jq(&expr, root)?;
+        Ok(if out.len() == 1 {
+            out.remove(0)
+        } else {
+            Value::Array(out)
+        })
+    }
+}
+
+/// JSONPath 匹配节点在 `root` 中的路径（文档中的先后顺序与查询结果一致，重复的只保留一次）。
+/// 只接受 `$` 开头的 JSONPath：jq 的结果可能是计算出的新值，没有对应的位置。
+pub fn select_paths(query: &str, root: &Value) -> Result<Vec<Vec<Seg>>, String> {
+    let query = query.trim();
+    if !query.starts_with('$') {
+        return Err("请输入以 $ 开头的 JSONPath".into());
+    }
+    let path = JsonPathParser::new(query)?.parse_query()?;
+    // 求值结果是指向 root 内部的引用，按地址反查路径
+    let mut index = HashMap::new();
+    index_paths(root, &mut Vec::new(), &mut index);
+    let mut seen = HashSet::new();
+    Ok(select_path(&path, root, root)
+        .into_iter()
+        .filter_map(|v| index.get(&(v as *const Value)))
+        .filter(|p| seen.insert(*p))
+        .cloned()
+        .collect())
+}
+
+fn index_paths(v: &Value, path: &mut Vec<Seg>, out: &mut HashMap<*const Value, Vec<Seg>>) {
+    out.insert(v as *const Value, path.clone());
+    match v {
+        Value::Object(m) => {
+            for (k, c) in m {
+                path.push(Seg::Key(k.clone()));
+                index_paths(c, path, out);
+                path.pop();
+            }
+        }
+        Value::Array(a) => {
+            for (i, c) in a.iter().enumerate() {
+                path.push(Seg::Index(i));
+                index_paths(c, path, out);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+// ================= 词法 =================
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Dot,
+    DotDot,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    Comma,
+    Pipe,
+    Colon,
+    Star,
+    Question,
+    At,
+    Dollar,
+    Op(&'static str),
+    Ident(String),
+    Str(String),
+    Num(Value),
+}
+
+impl Tok {
+    /// 报错时展示的原文写法
+    fn text(&self) -> String {
+        match self {
+            Tok::Dot => ".".into(),
+            Tok::DotDot => "..".into(),
+            Tok::LBracket => "[".into(),
+            Tok::RBracket => "]".into(),
+            Tok::LParen => "(".into(),
+            Tok::RParen => ")".into(),
+            Tok::Comma => ",".into(),
+            Tok::Pipe => "|".into(),
+            Tok::Colon => ":".into(),
+            Tok::Star => "*".into(),
+            Tok::Question => "?".into(),
+            Tok::At => "@".into(),
+            Tok::Dollar => "$".into(),
+            Tok::Op(op) => op.to_string(),
+            Tok::Ident(id) => id.clone(),
+            Tok::Str(s) => serde_json::to_string(s).unwrap_or_default(),
+            Tok::Num(n) => n.to_string(),
+        }
+    }
+}
+
+/// 报错用：当前词法单元的原文，没有时为“表达式结尾”
+fn tok_text(t: Option<&Tok>) -> String {
+    t.map_or_else(|| "表达式结尾".into(), Tok::text)
+}
+
+fn tokenize(src: &str) -> Result<Vec<Tok>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut toks = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            ' ' | '\t' | '\n' | '\r' => i += 1,
+            '.' if next == Some('.') => {
+                toks.push(Tok::DotDot);
+                i += 2;
+            }
+            '.' => {
+                toks.push(Tok::Dot);
+                i += 1;
+            }
+            '[' => {
+                toks.push(Tok::LBracket);
+                i += 1;
+            }
+            ']' => {
+                toks.push(Tok::RBracket);
+                i += 1;
+            }
+            '(' => {
+                toks.push(Tok::LParen);
+                i += 1;
+            }
+            ')' => {
+                toks.push(Tok::RParen);
+                i += 1;
+            }
+            ',' => {
+                toks.push(Tok::Comma);
+                i += 1;
+            }
+            ':' => {
+                toks.push(Tok::Colon);
+                i += 1;
+            }
+            '*' => {
+                toks.push(Tok::Star);
+                i += 1;
+            }
+            '?' => {
+                toks.push(Tok::Question);
+                i += 1;
+            }
+            '@' => {
+                toks.push(Tok::At);
+                i += 1;
+            }
+            '$' => {
+                toks.push(Tok::Dollar);
+                i += 1;
+            }
+            '|' if next == Some('|') => {
+                toks.push(Tok::Op("||"));
+                i += 2;
+            }
+            '|' => {
+                toks.push(Tok::Pipe);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                toks.push(Tok::Op("&&"));
+                i += 2;
+            }
+            '=' if next == Some('=') => {
+                toks.push(Tok::Op("=="));
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                toks.push(Tok::Op("!="));
+                i += 2;
+            }
+            '!' => {
+                toks.push(Tok::Op("!"));
+                i += 1;
+            }
+            '<' | '>' => {
+                let op = match (c, next) {
+                    ('<', Some('=')) => "<=",
+                    ('>', Some('=')) => ">=",
+                    ('<', _) => "<",
+                    _ => ">",
+                };
+                i += op.len();
+                toks.push(Tok::Op(op));
+            }
+            '"' | '\'' => {
+                let quote = c;
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    let Some(&ch) = chars.get(i) else {
+                        return Err("字符串缺少结束引号".into());
+                    };
+                    i += 1;
+                    if ch == quote {
+                        break;
+                    }
+                    if ch == '\\' {
+                        let Some(&esc) = chars.get(i) else {
+                            return Err("字符串缺少结束引号".into());
+                        };
+                        i += 1;
+                        s.push(match esc {
+                            'n' => '\n',
+                            't' => '\t',
+                            'r' => '\r',
+                            other => other,
+                        });
+                    } else {
+                        s.push(ch);
+                    }
+                }
+                toks.push(Tok::Str(s));
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_ascii_digit()
+                        || matches!(chars[i], 'e' | 'E')
+                        || (chars[i] == '.'
+                            && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()))
+                        || (matches!(chars[i], '+' | '-') && matches!(chars[i - 1], 'e' | 'E')))
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let num = serde_json::from_str::<Value>(&text)
+                    .map_err(|_| format!("无效的数字: {}", text))?;
+                toks.push(Tok::Num(num));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                toks.push(Tok::Ident(chars[start..i].iter().collect()));
+            }
+            other => return Err(format!("无法识别的字符: {}", other)),
+        }
+    }
+    Ok(toks)
+}
+
+/// 两种语法共用的 token 游标
+struct Cursor {
+    toks: Vec<Tok>,
+    pos: usize,
+}
+
+impl Cursor {
+    fn peek(&self) -> Option<&Tok> {
+        self.toks.get(self.pos)
+    }
+    fn next(&mut self) -> Option<Tok> {
+        let t = self.toks.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+    fn eat(&mut self, tok: &Tok) -> bool {
+        if self.peek() == Some(tok) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn expect(&mut self, tok: &Tok, what: &str) -> Result<(), String> {
+        if self.eat(tok) {
+            Ok(())
+        } else {
+            Err(format!("缺少 {}", what))
+        }
+    }
+    fn at_end(&self) -> bool {
+        self.pos >= self.toks.len()
+    }
+}
+
+fn as_index(v: &Value) -> Option<i64> {
+    v.as_i64()
+}
+
+/// 负索引从尾部计数；越界返回 None
+fn resolve_index(idx: i64, len: usize) -> Option<usize> {
+    let i = if idx < 0 { len as i64 + idx } else { idx };
+    (i >= 0 && (i as usize) < len).then_some(i as usize)
+}
+
+/// Python 风格切片下标（支持负数与步长）
+fn slice_indices(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize> {
+    let len = len as i64;
+    let norm = |v: i64| if v < 0 { (len + v).max(0) } else { v.min(len) };
+    let mut out = Vec::new();
+    if step > 0 {
+        let (mut i, e) = (start.map_or(0, norm), end.map_or(len, norm));
+        while i < e {
+            out.push(i as usize);
+            i += step;
+        }
+    } else if step < 0 {
+        let clamp_back = |v: i64| if v < 0 { len + v } else { v.min(len - 1) };
+        let mut i = start.map_or(len - 1, clamp_back);
+        let e = end.map_or(-1, clamp_back);
+        while i > e && i >= 0 {
+            out.push(i as usize);
+            i += step;
+        }
+    }
+    out
+}
+
+// ================= 比较（jq 的跨类型全序） =================
+
+fn type_rank(v: &Value) -> u8 {
+    match v {
+        Value::Null => 0,
+        Value::Bool(false) => 1,
+        Value::Bool(true) => 2,
+        Value::Number(_) => 3,
+        Value::String(_) => 4,
+        Value::Array(_) => 5,
+        Value::Object(_) => 6,
+    }
+}
+
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        // 两边都是 i64 时精确比较，避免超过 2^53 的 ID 经 f64 后相等
+        (Value::Number(x), Value::Number(y)) => match (x.as_i64(), y.as_i64()) {
+            (Some(i), Some(j)) => i.cmp(&j),
+            _ => {
+                let (x, y) = (x.as_f64().unwrap_or(0.0), y.as_f64().unwrap_or(0.0));
+                x.partial_cmp(&y).unwrap_or(Ordering::Equal)
+            }
+        },
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Array(x), Value::Array(y)) => {
+            for (l, r) in x.iter().zip(y) {
+                let o = compare_values(l, r);
+                if o != Ordering::Equal {
+                    return o;
+                }
+            }
+            x.len().cmp(&y.len())
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            let mut kx: Vec<&String> = x.keys().collect();
+            let mut ky: Vec<&String> = y.keys().collect();
+            kx.sort();
+            ky.sort();
+            kx.cmp(&ky).then_with(|| {
+                for k in kx {
+                    let o = compare_values(&x[k], &y[k]);
+                    if o != Ordering::Equal {
+                        return o;
+                    }
+                }
+                Ordering::Equal
+            })
+        }
+        _ => type_rank(a).cmp(&type_rank(b)),
+    }
+}
+
+fn apply_cmp(op: &str, a: &Value, b: &Value) -> bool {
+    let o = compare_values(a, b);
+    match op {
+        "==" => o == Ordering::Equal,
+        "!=" => o != Ordering::Equal,
+        "<" => o == Ordering::Less,
+        "<=" => o != Ordering::Greater,
+        ">" => o == Ordering::Greater,
+        _ => o != Ordering::Less,
+    }
+}
+
+fn truthy(v: &Value) -> bool {
+    !matches!(v, Value::Null | Value::Bool(false))
+}
+
+fn is_cmp_op(t: Option<&Tok>) -> Option<&'static str> {
+    match t {
+        Some(Tok::Op(op)) if matches!(*op, "==" | "!=" | "<" | "<=" | ">" | ">=") => Some(op),
+        _ => None,
+    }
+}
+
+// ================= JSONPath =================
+
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+    Slice(Option<i64>, Option<i64>, i64),
+    Filter(Box<FilterExpr>),
+}
+
+struct PathSegment {
+    descendant: bool,
+    selectors: Vec<Selector>,
+}
+
+enum FilterExpr {
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Cmp(&'static str, Box<FilterExpr>, Box<FilterExpr>),
+    /// `@...`（from_root=false）或 `$...`（from_root=true）
+    Path {
+        from_root: bool,
+        segments: Vec<PathSegment>,
+    },
+    Literal(Value),
+}
+
+struct JsonPathParser {
+    cur: Cursor,
+}
+
+impl JsonPathParser {
+    fn new(src: &str) -> Result<Self, String> {
+        Ok(Self {
+            cur: Cursor {
+                toks: tokenize(src)?,
+                pos: 0,
+            },
+        })
+    }
+
+    fn parse_query(mut self) -> Result<Vec<PathSegment>, String> {
+        self.cur.expect(&Tok::Dollar, "$")?;
+        let segs = self.parse_segments()?;
+        if !self.cur.at_end() {
+            return Err(format!(
+                "JSONPath 中有多余内容: {}",
+                tok_text(self.cur.peek())
+            ));
+        }
+        Ok(segs)
+    }
+
+    fn parse_segments(&mut self) -> Result<Vec<PathSegment>, String> {
+        let mut segs = Vec::new();
+        loop {
+            match self.cur.peek() {
+                Some(Tok::Dot) => {
+                    self.cur.next();
+                    segs.push(PathSegment {
+                        descendant: false,
+                        selectors: vec![self.parse_dot_member()?],
+                    });
+                }
+                Some(Tok::DotDot) => {
+                    self.cur.next();
+                    let selectors = if self.cur.peek() == Some(&Tok::LBracket) {
+                        self.parse_bracket()?
+                    } else {
+                        vec![self.parse_dot_member()?]
+                    };
+                    segs.push(PathSegment {
+                        descendant: true,
+                        selectors,
+                    });
+                }
+                Some(Tok::LBracket) => {
+                    let selectors = self.parse_bracket()?;
+                    segs.push(PathSegment {
+                        descendant: false,
+                        selectors,
+                    });
+                }
+                _ => return Ok(segs),
+            }
+        }
+    }
+
+    fn parse_dot_member(&mut self) -> Result<Selector, String> {
+        match self.cur.next() {
+            Some(Tok::Star) => Ok(Selector::Wildcard),
+            Some(Tok::Ident(name)) => Ok(Selector::Name(name)),
+            // `.123` 这类纯数字 key
+            Some(Tok::Num(n)) => Ok(Selector::Name(n.to_string())),
+            _ => Err("`.` 后应为字段名或 *".into()),
+        }
+    }
+
+    fn parse_bracket(&mut self) -> Result<Vec<Selector>, String> {
+        self.cur.expect(&Tok::LBracket, "[")?;
+        let mut selectors = Vec::new();
+        loop {
+            selectors.push(self.parse_selector()?);
+            if !self.cur.eat(&Tok::Comma) {
+                break;
+            }
+        }
+        self.cur.expect(&Tok::RBracket, "]")?;
+        Ok(selectors)
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, String> {
+        match self.cur.peek().cloned() {
+            Some(Tok::Star) => {
+                self.cur.next();
+                Ok(Selector::Wildcard)
+            }
+            Some(Tok::Str(s)) => {
+                self.cur.next();
+                Ok(Selector::Name(s))
+            }
+            Some(Tok::Question) => {
+                self.cur.next();
+                // 兼容 `?(expr)` 与 RFC 9535 的 `?expr`
+                let expr = self.parse_filter_or()?;
+                Ok(Selector::Filter(Box::new(expr)))
+            }
+            Some(Tok::Num(_)) | Some(Tok::Colon) => {
+                let start = self.parse_opt_int()?;
+                if !self.cur.eat(&Tok::Colon) {
+                    return start.map(Selector::Index).ok_or_else(|| "缺少索引".into());
+                }
+                let end = self.parse_opt_int()?;
+                let step = if self.cur.eat(&Tok::Colon) {
+                    self.parse_opt_int()?.unwrap_or(1)
+                } else {
+                    1
+                };
+                if step == 0 {
+                    return Err("切片步长不能为 0".into());
+                }
+                Ok(Selector::Slice(start, end, step))
+            }
+            _ => Err("[] 中应为字段名、索引、切片、* 或过滤表达式".into()),
+        }
+    }
+
+    fn parse_opt_int(&mut self) -> Result<Option<i64>, String> {
+        match self.cur.peek() {
+            Some(Tok::Num(n)) => {
+                let v = as_index(n).ok_or("索引必须是整数")?;
+                self.cur.next();
+                Ok(Some(v))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_filter_or(&mut self) -> Result<FilterExpr, String> {
+        let mut lhs = self.parse_filter_and()?;
+        while self.cur.eat(&Tok::Op("||")) {
+            let rhs = self.parse_filter_and()?;
+            lhs = FilterExpr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_filter_and(&mut self) -> Result<FilterExpr, String> {
+        let mut lhs = self.parse_filter_unary()?;
+        while self.cur.eat(&Tok::Op("&&")) {
+            let rhs = self.parse_filter_unary()?;
+            lhs = FilterExpr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_filter_unary(&mut self) -> Result<FilterExpr, String> {
+        if self.cur.eat(&Tok::Op("!")) {
+            return Ok(FilterExpr::Not(Box::new(self.parse_filter_unary()?)));
+        }
+        let lhs = self.parse_filter_primary()?;
+        if let Some(op) = is_cmp_op(self.cur.peek()) {
+            self.cur.next();
+            let rhs = self.parse_filter_primary()?;
+            return Ok(FilterExpr::Cmp(op, Box::new(lhs), Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_filter_primary(&mut self) -> Result<FilterExpr, String> {
+        match self.cur.next() {
+            Some(Tok::LParen) => {
+                let e = self.parse_filter_or()?;
+                self.cur.expect(&Tok::RParen, ")")?;
+                Ok(e)
+            }
+            Some(Tok::At) => Ok(FilterExpr::Path {
+                from_root: false,
+                segments: self.parse_segments()?,
+            }),
+            Some(Tok::Dollar) => Ok(FilterExpr::Path {
+                from_root: true,
+                segments: self.parse_segments()?,
+            }),
+            Some(Tok::Str(s)) => Ok(FilterExpr::Literal(Value::String(s))),
+            Some(Tok::Num(n)) => Ok(FilterExpr::Literal(n)),
+            Some(Tok::Ident(id)) => match id.as_str() {
+                "true" => Ok(FilterExpr::Literal(Value::Bool(true))),
+                "false" => Ok(FilterExpr::Literal(Value::Bool(false))),
+                "null" => Ok(FilterExpr::Literal(Value::Null)),
+                _ => Err(format!("过滤表达式中无法识别: {}", id)),
+            },
+            _ => Err("过滤表达式不完整".into()),
+        }
+    }
+}
+
+fn select_path<'a>(segs: &[PathSegment], node: &'a Value, root: &'a Value) -> Vec<&'a Value> {
+    let mut nodes = vec![node];
+    for seg in segs {
+        let mut next = Vec::new();
+        for n in nodes {
+            if seg.descendant {
+                let mut all = Vec::new();
+                collect_descendants(n, &mut all);
+                for d in all {
+                    apply_selectors(&seg.selectors, d, root, &mut next);
+                }
+            } else {
+                apply_selectors(&seg.selectors, n, root, &mut next);
+            }
+        }
+        nodes = next;
+    }
+    nodes
+}
+
+/// 节点自身 + 所有后代（先序）
+fn collect_descendants<'a>(v: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(v);
+    match v {
+        Value::Object(m) => m.values().for_each(|c| collect_descendants(c, out)),
+        Value::Array(a) => a.iter().for_each(|c| collect_descendants(c, out)),
+        _ => {}
+    }
+}
+
+fn apply_selectors<'a>(sels: &[Selector], v: &'a Value, root: &'a Value, out: &mut Vec<&'a Value>) {
+    for sel in sels {
+        match (sel, v) {
+            (Selector::Name(k), Value::Object(m)) => out.extend(m.get(k)),
+            (Selector::Index(i), Value::Array(a)) => {
+                out.extend(resolve_index(*i, a.len()).map(|i| &a[i]))
+            }
+            (Selector::Wildcard, Value::Object(m)) => out.extend(m.values()),
+            (Selector::Wildcard, Value::Array(a)) => out.extend(a.iter()),
+            (Selector::Slice(s, e, step), Value::Array(a)) => out.extend(
+                slice_indices(a.len(), *s, *e, *step)
+                    .into_iter()
+                    .map(|i| &a[i]),
+            ),
+            (Selector::Filter(f), Value::Object(m)) => {
+                out.extend(m.values().filter(|c| filter_truthy(f, c, root)))
+            }
+            (Selector::Filter(f), Value::Array(a)) => {
+                out.extend(a.iter().filter(|c| filter_truthy(f, c, root)))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 过滤表达式中的路径只取第一个节点参与比较；不存在则视为“无值”。
+fn filter_value<'a>(e: &'a FilterExpr, cur: &'a Value, root: &'a Value) -> Option<Value> {
+    match e {
+        FilterExpr::Path {
+            from_root,
+            segments,
+        } => {
+            let base = if *from_root { root } else { cur };
+            select_path(segments, base, root)
+                .first()
+                .map(|v| (*v).clone())
+        }
+        FilterExpr::Literal(v) => Some(v.clone()),
+        other => Some(Value::Bool(filter_truthy(other, cur, root))),
+    }
+}
+
+fn filter_truthy(e: &FilterExpr, cur: &Value, root: &Value) -> bool {
+    match e {
+        FilterExpr::Or(a, b) => filter_truthy(a, cur, root) || filter_truthy(b, cur, root),
+        FilterExpr::And(a, b) => filter_truthy(a, cur, root) && filter_truthy(b, cur, root),
+        FilterExpr::Not(a) => !filter_truthy(a, cur, root),
+        FilterExpr::Cmp(op, a, b) => {
+            match (filter_value(a, cur, root), filter_value(b, cur, root)) {
+                (Some(x), Some(y)) => apply_cmp(op, &x, &y),
+                // 两边都不存在视为相等；一边不存在则只有 != 成立
+                (None, None) => matches!(*op, "==" | "<=" | ">="),
+                _ => *op == "!=",
+            }
+        }
+        // 单独的路径：存在即为真
+        FilterExpr::Path { .. } => filter_value(e, cur, root).is_some(),
+        FilterExpr::Literal(v) => truthy(v),
+    }
+}
+
+// ================= jq 子集 =================
+
+enum Jq {
+    Identity,
+    Recurse,
+    Field(String),
+    Index(Value),
+    Slice(Option<i64>, Option<i64>),
+    Iterate,
+    Pipe(Box<Jq>, Box<Jq>),
+    Comma(Box<Jq>, Box<Jq>),
+    Literal(Value),
+    Collect(Option<Box<Jq>>),
+    Select(Box<Jq>),
+    Map(Box<Jq>),
+    Keys,
+    Length,
+    Not,
+    Cmp(&'static str, Box<Jq>, Box<Jq>),
+    And(Box<Jq>, Box<Jq>),
+    Or(Box<Jq>, Box<Jq>),
+    /// `expr?`：吞掉错误
+    Try(Box<Jq>),
+}
+
+struct JqParser {
+    cur: Cursor,
+}
+
+impl JqParser {
+    fn new(src: &str) -> Result<Self, String> {
+        Ok(Self {
+            cur: Cursor {
+                toks: tokenize(src)?,
+                pos: 0,
+            },
+        })
+    }
+
+    fn parse_all(mut self) -> Result<Jq, String> {
+        let e = self.parse_pipe()?;
+        if !self.cur.at_end() {
+            return Err(format!(
+                "jq 表达式中有多余内容: {}",
+                tok_text(self.cur.peek())
+            ));
+        }
+        Ok(e)
+    }
+
+    fn parse_pipe(&mut self) -> Result<Jq, String> {
+        let mut lhs = self.parse_comma()?;
+        while self.cur.eat(&Tok::Pipe) {
+            let rhs = self.parse_comma()?;
+            lhs = Jq::Pipe(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_comma(&mut self) -> Result<Jq, String> {
+        let mut lhs = self.parse_or()?;
+        while self.cur.eat(&Tok::Comma) {
+            let rhs = self.parse_or()?;
+            lhs = Jq::Comma(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_or(&mut self) -> Result<Jq, String> {
+        let mut lhs = self.parse_and()?;
+        while self.cur.eat(&Tok::Ident("or".into())) {
+            let rhs = self.parse_and()?;
+            lhs = Jq::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Jq, String> {
+        let mut lhs = self.parse_cmp()?;
+        while self.cur.eat(&Tok::Ident("and".into())) {
+            let rhs = self.parse_cmp()?;
+            lhs = Jq::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_cmp(&mut self) -> Result<Jq, String> {
+        let lhs = self.parse_postfix()?;
+        if let Some(op) = is_cmp_op(self.cur.peek()) {
+            self.cur.next();
+            let rhs = self.parse_postfix()?;
+            return Ok(Jq::Cmp(op, Box::new(lhs), Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_postfix(&mut self) -> Result<Jq, String> {
+        let mut e = self.parse_primary()?;
+        loop {
+            match self.cur.peek() {
+                Some(Tok::Dot) => {
+                    self.cur.next();
+                    let step = self.parse_after_dot()?;
+                    e = Jq::Pipe(Box::new(e), Box::new(step));
+                }
+                Some(Tok::LBracket) => {
+                    let step = self.parse_bracket_suffix()?;
+                    e = Jq::Pipe(Box::new(e), Box::new(step));
+                }
+                Some(Tok::Question) => {
+                    self.cur.next();
+                    e = Jq::Try(Box::new(e));
+                }
+                _ => return Ok(e),
+            }
+        }
+    }
+
+    /// `.` 之后：字段名 / 字符串字段 / `[...]`；都没有时就是恒等 `.`
+    fn parse_after_dot(&mut self) -> Result<Jq, String> {
+        match self.cur.peek().cloned() {
+            Some(Tok::Ident(name)) => {
+                self.cur.next();
+                Ok(Jq::Field(name))
+            }
+            Some(Tok::Str(name)) => {
+                self.cur.next();
+                Ok(Jq::Field(name))
+            }
+            Some(Tok::LBracket) => self.parse_bracket_suffix(),
+            _ => Ok(Jq::Identity),
+        }
+    }
+
+    fn parse_bracket_suffix(&mut self) -> Result<Jq, String> {
+        self.cur.expect(&Tok::LBracket, "[")?;
+        if self.cur.eat(&Tok::RBracket) {
+            return Ok(Jq::Iterate);
+        }
+        let step = match self.cur.next() {
+            Some(Tok::Str(s)) => Jq::Field(s),
+            Some(Tok::Num(n)) if self.cur.peek() == Some(&Tok::Colon) => {
+                self.cur.next();
+                let end = self.parse_opt_int()?;
+                Jq::Slice(Some(as_index(&n).ok_or("切片下标必须是整数")?), end)
+            }
+            Some(Tok::Num(n)) => Jq::Index(n),
+            Some(Tok::Colon) => Jq::Slice(None, self.parse_opt_int()?),
+            _ => return Err("[] 中应为索引、字段名或切片".into()),
+        };
+        self.cur.expect(&Tok::RBracket, "]")?;
+        Ok(step)
+    }
+
+    fn parse_opt_int(&mut self) -> Result<Option<i64>, String> {
+        match self.cur.peek() {
+            Some(Tok::Num(n)) => {
+                let v = as_index(n).ok_or("切片下标必须是整数")?;
+                self.cur.next();
+                Ok(Some(v))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_call_arg(&mut self) -> Result<Box<Jq>, String> {
+        self.cur.expect(&Tok::LParen, "(")?;
+        let arg = self.parse_pipe()?;
+        self.cur.expect(&Tok::RParen, ")")?;
+        Ok(Box::new(arg))
+    }
+
+    fn parse_primary(&mut self) -> Result<Jq, String> {
+        match self.cur.next() {
+            Some(Tok::Dot) => self.parse_after_dot(),
+            Some(Tok::DotDot) => Ok(Jq::Recurse),
+            Some(Tok::LParen) => {
+                let e = self.parse_pipe()?;
+                self.cur.expect(&Tok::RParen, ")")?;
+                Ok(e)
+            }
+            Some(Tok::LBracket) => {
+                if self.cur.eat(&Tok::RBracket) {
+                    return Ok(Jq::Collect(None));
+                }
+                let e = self.parse_pipe()?;
+                self.cur.expect(&Tok::RBracket, "]")?;
+                Ok(Jq::Collect(Some(Box::new(e))))
+            }
+            Some(Tok::Str(s)) => Ok(Jq::Literal(Value::String(s))),
+            Some(Tok::Num(n)) => Ok(Jq::Literal(n)),
+            Some(Tok::Ident(id)) => match id.as_str() {
+                "true" => Ok(Jq::Literal(Value::Bool(true))),
+                "false" => Ok(Jq::Literal(Value::Bool(false))),
+                "null" => Ok(Jq::Literal(Value::Null)),
+                "keys" => Ok(Jq::Keys),
+                "length" => Ok(Jq::Length),
+                "not" => Ok(Jq::Not),
+                "select" => Ok(Jq::Select(self.parse_call_arg()?)),
+                "map" => Ok(Jq::Map(self.parse_call_arg()?)),
+                _ if self.cur.peek() == Some(&Tok::LParen) => {
+                    Err(format!("不支持的 jq 函数: {}", id))
+                }
+                _ => Err(format!("无法识别的 jq 标识符: {}", id)),
+            },
+            Some(t) => Err(format!("jq 表达式中不应出现 {}", t.text())),
+            None => Err("jq 表达式不完整".into()),
+        }
+    }
+}
+
+fn type_name(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn eval_jq(e: &Jq, input: &Value) -> Result<Vec<Value>, String> {
+    Ok(match e {
+        Jq::Identity => vec![input.clone()],
+        Jq::Recurse => {
+            let mut all = Vec::new();
+            collect_descendants(input, &mut all);
+            all.into_iter().cloned().collect()
+        }
+        Jq::Field(k) => match input {
+            Value::Object(m) => vec![m.get(k).cloned().unwrap_or(Value::Null)],
+            Value::Null => vec![Value::Null],
+            other => return Err(format!("无法在 {} 上取字段 \"{}\"", type_name(other), k)),
+        },
+        Jq::Index(idx) => match (input, idx) {
+            (Value::Array(a), Value::Number(_)) => {
+                let i = as_index(idx).ok_or("索引必须是整数")?;
+                vec![resolve_index(i, a.len()).map_or(Value::Null, |i| a[i].clone())]
+            }
+            (Value::Null, _) => vec![Value::Null],
+            (other, _) => return Err(format!("无法用数字索引 {}", type_name(other))),
+        },
+        Jq::Slice(s, end) => match input {
+            Value::Array(a) => vec![Value::Array(
+                slice_indices(a.len(), *s, *end, 1)
+                    .into_iter()
+                    .map(|i| a[i].clone())
+                    .collect(),
+            )],
+            Value::String(st) => {
+                let chars: Vec<char> = st.chars().collect();
+                let picked: String = slice_indices(chars.len(), *s, *end, 1)
+                    .into_iter()
+                    .map(|i| chars[i])
+                    .collect();
+                vec![Value::String(picked)]
+            }
+            Value::Null => vec![Value::Null],
+            other => return Err(format!("无法对 {} 切片", type_name(other))),
+        },
+        Jq::Iterate => match input {
+            Value::Array(a) => a.clone(),
+            Value::Object(m) => m.values().cloned().collect(),
+            other => return Err(format!("无法遍历 {}", type_name(other))),
+        },
+        Jq::Pipe(a, b) => {
+            let mut out = Vec::new();
+            for v in eval_jq(a, input)? {
+                out.extend(eval_jq(b, &v)?);
+            }
+            out
+        }
+        Jq::Comma(a, b) => {
+            let mut out = eval_jq(a, input)?;
+            out.extend(eval_jq(b, input)?);
+            out
+        }
+        Jq::Literal(v) => vec![v.clone()],
+        Jq::Collect(None) => vec![Value::Array(Vec::new())],
+        Jq::Collect(Some(inner)) => vec![Value::Array(eval_jq(inner, input)?)],
+        Jq::Select(cond) => {
+            let keep = eval_jq(cond, input)?.iter().any(truthy);
+            if keep {
+                vec![input.clone()]
+            } else {
+                Vec::new()
+            }
+        }
+        Jq::Map(f) => {
+            let items: Vec<Value> = match input {
+                Value::Array(a) => a.clone(),
+                Value::Object(m) => m.values().cloned().collect(),
+                other => return Err(format!("无法对 {} 使用 map", type_name(other))),
+            };
+            let mut out = Vec::new();
+            for item in &items {
+                out.extend(eval_jq(f, item)?);
+            }
+            vec![Value::Array(out)]
+        }
+        Jq::Keys => match input {
+            Value::Object(m) => {
+                // jq 的 keys 按字典序返回
+                let mut ks: Vec<&String> = m.keys().collect();
+                ks.sort();
+                vec![Value::Array(
+                    ks.into_iter().map(|k| Value::String(k.clone())).collect(),
+                )]
+            }
+            Value::Array(a) => vec![Value::Array((0..a.len()).map(Value::from).collect())],
+            other => return Err(format!("{} 没有 keys", type_name(other))),
+        },
+        Jq::Length => vec![match input {
+            Value::Null => Value::from(0),
+            Value::Bool(_) => return Err("boolean 没有 length".into()),
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Value::from(i.unsigned_abs())
+                } else {
+                    Value::from(n.as_f64().unwrap_or(0.0).abs())
+                }
+            }
+            Value::String(s) => Value::from(s.chars().count()),
+            Value::Array(a) => Value::from(a.len()),
+            Value::Object(m) => Value::from(m.len()),
+        }],
+        Jq::Not => vec![Value::Bool(!truthy(input))],
+        Jq::Cmp(op, a, b) => {
+            let rs = eval_jq(b, input)?;
+            let mut out = Vec::new();
+            for l in eval_jq(a, input)? {
+                for r in &rs {
+                    out.push(Value::Bool(apply_cmp(op, &l, r)));
+                }
+            }
+            out
+        }
+        Jq::And(a, b) => logic(a, b, input, true)?,
+        Jq::Or(a, b) => logic(a, b, input, false)?,
+        Jq::Try(inner) => eval_jq(inner, input).unwrap_or_default(),
+    })
+}
+
+/// and / or：左侧短路，否则对右侧每个输出求值
+fn logic(a: &Jq, b: &Jq, input: &Value, is_and: bool) -> Result<Vec<Value>, String> {
+    let mut out = Vec::new();
+    for l in eval_jq(a, input)? {
+        let lt = truthy(&l);
+        if lt != is_and {
+            out.push(Value::Bool(lt));
+            continue;
+        }
+        for r in eval_jq(b, input)? {
+            out.push(Value::Bool(truthy(&r)));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc() -> Value {
+        json!({
+            "store": {
+                "book": [
+                    {"title": "A", "price": 8, "tag": "x"},
+                    {"title": "B", "price": 12},
+                    {"title": "C", "price": 5, "tag": "y"}
+                ],
+                "name": "shop"
+            }
+        })
+    }
+
+    fn q(query: &str) -> Value {
+        run_query(query, &doc()).unwrap_or_else(|e| panic!("{}: {}", query, e))
+    }
+
+    #[test]
+    fn jsonpath_selectors() {
+        assert_eq!(q("$.store.name"), json!(["shop"]));
+        assert_eq!(q("$['store']['book'][-1].title"), json!(["C"]));
+        assert_eq!(q("$.store.book[0,2].title"), json!(["A", "C"]));
+        assert_eq!(q("$.store.book[1:3].price"), json!([12, 5]));
+        assert_eq!(q("$.store.book[*].price"), json!([8, 12, 5]));
+        assert_eq!(q("$..title"), json!(["A", "B", "C"]));
+        assert_eq!(
+            q("$.store.book[?(@.price < 10 && @.tag)].title"),
+            json!(["A", "C"])
+        );
+        assert_eq!(q("$.missing"), json!([]));
+    }
+
+    #[test]
+    fn jq_expressions() {
+        assert_eq!(q(".store.name"), json!("shop"));
+        assert_eq!(q(".store.book[0].title"), json!("A"));
+        assert_eq!(q(".store.book[].title"), json!(["A", "B", "C"]));
+        assert_eq!(q(".store.book | map(.price)"), json!([8, 12, 5]));
+        assert_eq!(
+            q("[.store.book[] | select(.price > 6) | .title]"),
+            json!(["A", "B"])
+        );
+        assert_eq!(q(".store.book | length"), json!(3));
+        assert_eq!(q(".store | keys"), json!(["book", "name"]));
+        assert_eq!(q(".store.name, .store.book[1].price"), json!(["shop", 12]));
+        assert_eq!(q(".store.name.x?"), json!([]));
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        for (query, msg) in [
+            ("$.a[", "[] 中应为字段名、索引、切片、* 或过滤表达式"),
+            ("$.a[::0]", "切片步长不能为 0"),
+            (".a | frobnicate", "无法识别的 jq 标识符: frobnicate"),
+            (".store.name.x", "无法在 string 上取字段 \"x\""),
+            (".store.name | length | keys", "number 没有 keys"),
+            (".a |", "jq 表达式不完整"),
+            (".a..b", "jq 表达式中有多余内容: .."),
+            (")", "jq 表达式中不应出现 )"),
+            (".a == 1 == 2", "jq 表达式中有多余内容: =="),
+            ("$.a)", "JSONPath 中有多余内容: )"),
+            ("$.a 'b'", "JSONPath 中有多余内容: \"b\""),
+        ] {
+            assert_eq!(
+                run_query(query, &doc()).err().as_deref(),
+                Some(msg),
+                "{}",
+                query
+            );
+        }
+        assert!(run_query("'open", &doc())
+            .unwrap_err()
+            .contains("缺少结束引号"));
+    }
+
+    #[test]
+    fn select_paths_follow_document_order() {
+        let paths = select_paths("$..price", &doc()).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(
+            paths[2],
+            [
+                Seg::Key("store".into()),
+                Seg::Key("book".into()),
+                Seg::Index(2),
+                Seg::Key("price".into())
+            ]
+        );
+        assert!(select_paths(".a", &doc()).is_err());
+    }
+}
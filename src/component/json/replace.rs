@@ -0,0 +1,182 @@
+// src/component/json/replace.rs
+//
+// 结构化替换：按文档结构而不是原始文本替换，不会改坏引号、转义或把 key 与值混在一起。
+//
+// - 键：只改对象的 key；改名后与同一对象中已有的 key 重名时跳过该项。
+// - 字符串值：只改字符串值（不含 key）。
+// - JSONPath：只改路径选中的节点。标量按其 JSON 文本替换，结果仍是合法 JSON 标量时
+//   保留类型（如数字），否则写成字符串；选中容器时替换其中的字符串值。
+//
+// 匹配条件与查找条相同（大小写 / 全词 / 正则，正则替换可用 `$1`）。
+// 结果写回输入时只改写被替换的节点（见 writeback.rs），其余文本、缩进与注释不变。
+
+use super::edit::{get_mut, Seg};
+use super::query::select_paths;
+use crate::component::util::search::Matcher;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ReplaceTarget {
+    /// 原始文本（不解析）
+    Text,
+    Keys,
+    Strings,
+    Path,
+}
+
+impl ReplaceTarget {
+    pub const ALL: [ReplaceTarget; 4] = [
+        ReplaceTarget::Text,
+        ReplaceTarget::Keys,
+        ReplaceTarget::Strings,
+        ReplaceTarget::Path,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReplaceTarget::Text => "文本",
+            ReplaceTarget::Keys => "键",
+            ReplaceTarget::Strings => "字符串值",
+            ReplaceTarget::Path => "JSONPath",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            ReplaceTarget::Text => "在原始文本上替换",
+            ReplaceTarget::Keys => "只替换对象的 key",
+            ReplaceTarget::Strings => "只替换字符串值，不动 key",
+            ReplaceTarget::Path => "只替换 JSONPath 选中的值",
+        }
+    }
+}
+
+/// 替换结果
+pub struct Replaced {
+    pub doc: Value,
+    /// 被修改的节点数
+    pub changed: usize,
+    /// 因改名后重名而跳过的 key 数
+    pub conflicts: usize,
+}
+
+/// 对 `doc` 做结构化替换（`target` 不能是 Text）
+pub fn replace_in(
+    doc: &Value,
+    target: ReplaceTarget,
+    path: &str,
+    matcher: &Matcher,
+    rep: &str,
+) -> Result<Replaced, String> {
+    let mut out = Replaced {
+        doc: doc.clone(),
+        changed: 0,
+        conflicts: 0,
+    };
+    let Replaced {
+        doc,
+        changed,
+        conflicts,
+    } = &mut out;
+    match target {
+        ReplaceTarget::Text => return Err("文本替换不经过结构化处理".into()),
+        ReplaceTarget::Keys => *changed = replace_keys(doc, matcher, rep, conflicts),
+        ReplaceTarget::Strings => *changed = replace_strings(doc, matcher, rep),
+        ReplaceTarget::Path => {
+            // 祖先也被选中时（如 `$..*`）只处理祖先，避免同一字符串被替换两次
+            let selected: HashSet<Vec<Seg>> = select_paths(path, doc)?.into_iter().collect();
+            for p in &selected {
+                if (0..p.len()).any(|n| selected.contains(&p[..n])) {
+                    continue;
+                }
+                if let Ok(v) = get_mut(doc, p) {
+                    *changed += replace_value(v, matcher, rep);
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// 匹配时返回替换后的文本；替换后不变视为未修改
+fn replaced(text: &str, matcher: &Matcher, rep: &str) -> Option<String> {
+    if !matcher.is_match(text) {
+        return None;
+    }
+    let next = matcher.replace_all(text, rep);
+    (next != text).then_some(next)
+}
+
+fn replace_keys(v: &mut Value, matcher: &Matcher, rep: &str, conflicts: &mut usize) -> usize {
+    let mut changed = 0;
+    match v {
+        Value::Object(m) => {
+            // 重建以保持 key 顺序
+            let old = std::mem::take(m);
+            let existing: HashSet<String> = old.keys().cloned().collect();
+            let mut next = Map::with_capacity(old.len());
+            for (k, mut child) in old {
+                changed += replace_keys(&mut child, matcher, rep, conflicts);
+                let key = match replaced(&k, matcher, rep) {
+                    Some(nk) if next.contains_key(&nk) || existing.contains(&nk) => {
+                        *conflicts += 1;
+                        k
+                    }
+                    Some(nk) => {
+                        changed += 1;
+                        nk
+                    }
+                    None => k,
+                };
+                next.insert(key, child);
+            }
+            *m = next;
+        }
+        Value::Array(a) => {
+            for c in a {
+                changed += replace_keys(c, matcher, rep, conflicts);
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+fn replace_strings(v: &mut Value, matcher: &Matcher, rep: &str) -> usize {
+    match v {
+        Value::String(s) => match replaced(s, matcher, rep) {
+            Some(next) => {
+                *s = next;
+                1
+            }
+            None => 0,
+        },
+        Value::Object(m) => m
+            .values_mut()
+            .map(|c| replace_strings(c, matcher, rep))
+            .sum(),
+        Value::Array(a) => a.iter_mut().map(|c| replace_strings(c, matcher, rep)).sum(),
+        _ => 0,
+    }
+}
+
+/// JSONPath 选中的节点
+fn replace_value(v: &mut Value, matcher: &Matcher, rep: &str) -> usize {
+    let text = match v {
+        Value::Object(_) | Value::Array(_) | Value::String(_) => {
+            return replace_strings(v, matcher, rep)
+        }
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+    };
+    let Some(next) = replaced(&text, matcher, rep) else {
+        return 0;
+    };
+    *v = match serde_json::from_str::<Value>(next.trim()) {
+        Ok(scalar) if !scalar.is_object() && !scalar.is_array() && !scalar.is_string() => scalar,
+        _ => Value::String(next),
+    };
+    1
+}
@@ -0,0 +1,905 @@
+// src/component/json/schema.rs
+//
+// JSON Schema 校验（draft 2020-12 / draft-07）与 schema 推断。
+//
+// 校验器是手写的最小实现，覆盖两版草案的常用关键字：
+//   类型与取值：type / enum / const / format（date-time、date、time、email、ipv4、ipv6、uuid、uri）
+//   数值：multipleOf / maximum / exclusiveMaximum / minimum / exclusiveMinimum
+//   字符串：minLength / maxLength / pattern
+//   数组：items / prefixItems / additionalItems / contains / minContains / maxContains /
+//         minItems / maxItems / uniqueItems / unevaluatedItems
+//   对象：properties / patternProperties / additionalProperties / required / propertyNames /
+//         minProperties / maxProperties / dependentRequired / dependentSchemas / dependencies /
+//         unevaluatedProperties
+//   组合：allOf / anyOf / oneOf / not / if-then-else / $ref（文档内 JSON Pointer 与 $anchor）
+// draft-07 中 `items` 可为数组（元组），与 `$ref` 同级的关键字被忽略；其余按 2020-12 语义处理。
+// 远程 $ref 不会去下载，而是报告为一条错误。
+//
+// 推断：遍历文档收集每个位置出现过的类型；数组元素的形状合并为一个 items，
+// 对象的 required 取所有样本都出现的 key。
+
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use super::shape::{type_of, Shape};
+use std::collections::{HashMap, HashSet};
+
+/// 一处校验失败
+#[derive(Clone, PartialEq)]
+pub struct Violation {
+    /// 实例中的位置（JSON Pointer，根为空串）
+    pub instance_path: String,
+    /// schema 中触发失败的关键字位置（JSON Pointer）
+    pub schema_path: String,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Draft {
+    Draft07,
+    Draft202012,
+}
+
+impl Draft {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Draft::Draft07 => "draft-07",
+            Draft::Draft202012 => "draft 2020-12",
+        }
+    }
+}
+
+/// 按 `$schema` 判断草案版本；未声明时按 2020-12 处理
+pub fn detect_draft(schema: &Value) -> Draft {
+    match schema.get("$schema").and_then(Value::as_str) {
+        Some(s) if s.contains("draft-07") || s.contains("draft-06") || s.contains("draft-04") => {
+            Draft::Draft07
+        }
+        _ => Draft::Draft202012,
+    }
+}
+
+pub fn validate(schema: &Value, instance: &Value) -> Result<Vec<Violation>, String> {
+    if !schema.is_object() && !schema.is_boolean() {
+        return Err("schema 必须是对象或布尔值".into());
+    }
+    let v = Validator {
+        root: schema,
+        draft: detect_draft(schema),
+        regex_cache: Default::default(),
+        active_refs: Default::default(),
+    };
+    let mut out = Vec::new();
+    v.check(schema, instance, "", "", &mut out);
+    Ok(out)
+}
+
+/// 已被求值的属性 / 数组项（供 unevaluated* 使用）
+#[derive(Default)]
+struct Evaluated {
+    props: HashSet<String>,
+    /// 前 `items` 个元素已求值
+    items: usize,
+    /// contains 命中的下标
+    contained: HashSet<usize>,
+    all_items: bool,
+}
+
+impl Evaluated {
+    fn merge(&mut self, other: Evaluated) {
+        self.props.extend(other.props);
+        self.items = self.items.max(other.items);
+        self.contained.extend(other.contained);
+        self.all_items |= other.all_items;
+    }
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    draft: Draft,
+    regex_cache: std::cell::RefCell<HashMap<String, Result<Regex, String>>>,
+    /// 正在展开的 (`$ref` 目标, 实例位置)。同一对再次出现说明引用绕了一圈却没有消耗实例
+    /// （如 `{"$ref":"#"}`），是死循环；沿实例逐层深入的递归 schema 不受限制
+    active_refs: std::cell::RefCell<HashSet<(*const Value, String)>>,
+}
+
+fn fail(ip: &str, sp: String, message: String) -> Violation {
+    Violation {
+        instance_path: ip.to_string(),
+        schema_path: sp,
+        message,
+    }
+}
+
+fn ptr(base: &str, seg: &str) -> String {
+    format!("{}/{}", base, seg.replace('~', "~0").replace('/', "~1"))
+}
+
+fn matches_type(v: &Value, t: &str) -> bool {
+    match (t, v) {
+        ("integer", Value::Number(n)) => {
+            n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        ("number", Value::Number(_)) => true,
+        _ => type_of(v) == t,
+    }
+}
+
+/// JSON Schema 的相等：数字按数值比较（1 == 1.0）
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => match (x.as_i64(), y.as_i64()) {
+            (Some(i), Some(j)) => i == j,
+            _ => x.as_f64() == y.as_f64(),
+        },
+        (Value::Array(x), Value::Array(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| y.get(k).is_some_and(|w| json_eq(v, w)))
+        }
+        _ => a == b,
+    }
+}
+
+fn short(v: &Value) -> String {
+    let s = serde_json::to_string(v).unwrap_or_default();
+    if s.chars().count() > 60 {
+        format!("{}…", s.chars().take(60).collect::<String>())
+    } else {
+        s
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(b) = s
+                .get(i + 1..i + 3)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+            {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+impl<'a> Validator<'a> {
+    fn regex(&self, pattern: &str) -> Result<Regex, String> {
+        self.regex_cache
+            .borrow_mut()
+            .entry(pattern.to_string())
+            .or_insert_with(|| Regex::new(pattern).map_err(|e| e.to_string()))
+            .clone()
+    }
+
+    /// 解析文档内引用：`#`、`#/json/pointer`、`#anchor`；也接受与某个 `$id` 完全相同的引用
+    fn resolve(&self, r: &str) -> Option<&'a Value> {
+        if let Some(frag) = r.strip_prefix('#') {
+            if frag.is_empty() {
+                return Some(self.root);
+            }
+            if frag.starts_with('/') {
+                return self.root.pointer(&percent_decode(frag));
+            }
+            return find_schema(self.root, &|s| {
+                s.get("$anchor").and_then(Value::as_str) == Some(frag)
+                    || s.get("$id").and_then(Value::as_str) == Some(r)
+            });
+        }
+        find_schema(self.root, &|s| {
+            s.get("$id").and_then(Value::as_str) == Some(r)
+        })
+    }
+
+    /// 不记录错误地试探 schema 是否通过
+    fn probe(&self, s: &Value, v: &Value, ip: &str, sp: &str) -> (bool, Evaluated) {
+        let mut tmp = Vec::new();
+        let ev = self.check(s, v, ip, sp, &mut tmp);
+        (tmp.is_empty(), ev)
+    }
+
+    fn check(
+        &self,
+        s: &Value,
+        v: &Value,
+        ip: &str,
+        sp: &str,
+        out: &mut Vec<Violation>,
+    ) -> Evaluated {
+        let mut ev = Evaluated::default();
+        let schema = match s {
+            Value::Bool(true) => return ev,
+            Value::Bool(false) => {
+                out.push(fail(
+                    ip,
+                    sp.to_string(),
+                    "schema 为 false，不允许任何值".into(),
+                ));
+                return ev;
+            }
+            Value::Object(m) => m,
+            _ => return ev,
+        };
+
+        // ---------- $ref ----------
+        if let Some(r) = schema.get("$ref").and_then(Value::as_str) {
+            let rp = ptr(sp, "$ref");
+            match self.resolve(r) {
+                Some(target) => {
+                    let key = (target as *const Value, ip.to_string());
+                    if !self.active_refs.borrow_mut().insert(key.clone()) {
+                        out.push(fail(ip, rp, format!("$ref {} 循环引用（未消耗实例）", r)));
+                        return ev;
+                    }
+                    let sub = self.check(target, v, ip, &rp, out);
+                    self.active_refs.borrow_mut().remove(&key);
+                    ev.merge(sub);
+                }
+                None => out.push(fail(
+                    ip,
+                    rp,
+                    format!("无法解析 $ref {}（仅支持文档内引用）", r),
+                )),
+            }
+            if self.draft == Draft::Draft07 {
+                return ev;
+            }
+        }
+
+        // ---------- 通用 ----------
+        if let Some(t) = schema.get("type") {
+            let types: Vec<&str> = match t {
+                Value::String(s) => vec![s.as_str()],
+                Value::Array(a) => a.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|t| matches_type(v, t)) {
+                out.push(fail(
+                    ip,
+                    ptr(sp, "type"),
+                    format!("类型应为 {}，实际为 {}", types.join(" / "), type_of(v)),
+                ));
+            }
+        }
+        if let Some(Value::Array(options)) = schema.get("enum") {
+            if !options.iter().any(|o| json_eq(o, v)) {
+                out.push(fail(
+                    ip,
+                    ptr(sp, "enum"),
+                    format!("{} 不在 enum 允许的取值中", short(v)),
+                ));
+            }
+        }
+        if let Some(c) = schema.get("const") {
+            if !json_eq(c, v) {
+                out.push(fail(ip, ptr(sp, "const"), format!("应等于 {}", short(c))));
+            }
+        }
+
+        // ---------- 数值 ----------
+        if let Some(n) = v.as_f64().filter(|_| v.is_number()) {
+            let num = |k: &str| schema.get(k).and_then(Value::as_f64);
+            if let Some(m) = num("multipleOf").filter(|m| *m > 0.0) {
+                let q = n / m;
+                if (q - q.round()).abs() > 1e-9 {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "multipleOf"),
+                        format!("{} 不是 {} 的倍数", n, m),
+                    ));
+                }
+            }
+            if let Some(max) = num("maximum") {
+                if n > max {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "maximum"),
+                        format!("{} 大于最大值 {}", n, max),
+                    ));
+                }
+            }
+            if let Some(max) = num("exclusiveMaximum") {
+                if n >= max {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "exclusiveMaximum"),
+                        format!("{} 应小于 {}", n, max),
+                    ));
+                }
+            }
+            if let Some(min) = num("minimum") {
+                if n < min {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "minimum"),
+                        format!("{} 小于最小值 {}", n, min),
+                    ));
+                }
+            }
+            if let Some(min) = num("exclusiveMinimum") {
+                if n <= min {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "exclusiveMinimum"),
+                        format!("{} 应大于 {}", n, min),
+                    ));
+                }
+            }
+        }
+
+        // ---------- 字符串 ----------
+        if let Value::String(text) = v {
+            let len = text.chars().count() as u64;
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "maxLength"),
+                        format!("长度 {} 超过 {}", len, max),
+                    ));
+                }
+            }
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "minLength"),
+                        format!("长度 {} 小于 {}", len, min),
+                    ));
+                }
+            }
+            if let Some(p) = schema.get("pattern").and_then(Value::as_str) {
+                match self.regex(p) {
+                    Ok(re) if !re.is_match(text) => {
+                        out.push(fail(ip, ptr(sp, "pattern"), format!("不匹配正则 {}", p)))
+                    }
+                    Ok(_) => {}
+                    Err(e) => out.push(fail(
+                        ip,
+                        ptr(sp, "pattern"),
+                        format!("schema 中的正则无效: {}", e),
+                    )),
+                }
+            }
+            if let Some(f) = schema.get("format").and_then(Value::as_str) {
+                if !check_format(f, text) {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "format"),
+                        format!("不是合法的 {} 格式", f),
+                    ));
+                }
+            }
+        }
+
+        // ---------- 数组 ----------
+        if let Value::Array(items) = v {
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if items.len() as u64 > max {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "maxItems"),
+                        format!("元素个数 {} 超过 {}", items.len(), max),
+                    ));
+                }
+            }
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "minItems"),
+                        format!("元素个数 {} 少于 {}", items.len(), min),
+                    ));
+                }
+            }
+            if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+                'outer: for i in 0..items.len() {
+                    for j in i + 1..items.len() {
+                        if json_eq(&items[i], &items[j]) {
+                            out.push(fail(
+                                ip,
+                                ptr(sp, "uniqueItems"),
+                                format!("第 {} 项与第 {} 项重复", i, j),
+                            ));
+                            break 'outer;
+                        }
+                    }
+                }
+            }
+        }
+        if let Value::Array(items) = v {
+            // 元组：2020-12 用 prefixItems；draft-07 用数组形式的 items
+            let (tuple, tuple_key) = match (schema.get("prefixItems"), schema.get("items")) {
+                (Some(Value::Array(t)), _) => (Some(t), "prefixItems"),
+                (_, Some(Value::Array(t))) => (Some(t), "items"),
+                _ => (None, ""),
+            };
+            let mut prefix = 0;
+            if let Some(tuple) = tuple {
+                let tp = ptr(sp, tuple_key);
+                for (i, (s_i, item)) in tuple.iter().zip(items).enumerate() {
+                    self.check(
+                        s_i,
+                        item,
+                        &ptr(ip, &i.to_string()),
+                        &ptr(&tp, &i.to_string()),
+                        out,
+                    );
+                }
+                prefix = tuple.len().min(items.len());
+                ev.items = ev.items.max(prefix);
+            }
+            // 剩余元素：2020-12 的 items（对象 / 布尔）或 draft-07 的 additionalItems
+            let rest = match (schema.get("items"), tuple_key) {
+                (Some(s @ (Value::Object(_) | Value::Bool(_))), _) => Some((s, "items")),
+                (_, "items") => schema
+                    .get("additionalItems")
+                    .map(|s| (s, "additionalItems")),
+                _ => None,
+            };
+            if let Some((rest_schema, key)) = rest {
+                let rp = ptr(sp, key);
+                for (i, item) in items.iter().enumerate().skip(prefix) {
+                    self.check(rest_schema, item, &ptr(ip, &i.to_string()), &rp, out);
+                }
+                ev.all_items = true;
+            }
+            if let Some(c) = schema.get("contains") {
+                let cp = ptr(sp, "contains");
+                let mut hits = 0u64;
+                for (i, item) in items.iter().enumerate() {
+                    if self.probe(c, item, &ptr(ip, &i.to_string()), &cp).0 {
+                        hits += 1;
+                        ev.contained.insert(i);
+                    }
+                }
+                let min = schema
+                    .get("minContains")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(1);
+                if hits < min {
+                    let key = if schema.contains_key("minContains") {
+                        "minContains"
+                    } else {
+                        "contains"
+                    };
+                    out.push(fail(
+                        ip,
+                        ptr(sp, key),
+                        format!("满足 contains 的元素有 {} 个，至少需要 {} 个", hits, min),
+                    ));
+                }
+                if let Some(max) = schema.get("maxContains").and_then(Value::as_u64) {
+                    if hits > max {
+                        out.push(fail(
+                            ip,
+                            ptr(sp, "maxContains"),
+                            format!("满足 contains 的元素有 {} 个，最多允许 {} 个", hits, max),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // ---------- 对象 ----------
+        if let Value::Object(obj) = v {
+            if let Some(Value::Array(req)) = schema.get("required") {
+                for k in req.iter().filter_map(Value::as_str) {
+                    if !obj.contains_key(k) {
+                        out.push(fail(
+                            ip,
+                            ptr(sp, "required"),
+                            format!("缺少必需属性 \"{}\"", k),
+                        ));
+                    }
+                }
+            }
+            if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64) {
+                if obj.len() as u64 > max {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "maxProperties"),
+                        format!("属性个数 {} 超过 {}", obj.len(), max),
+                    ));
+                }
+            }
+            if let Some(min) = schema.get("minProperties").and_then(Value::as_u64) {
+                if (obj.len() as u64) < min {
+                    out.push(fail(
+                        ip,
+                        ptr(sp, "minProperties"),
+                        format!("属性个数 {} 少于 {}", obj.len(), min),
+                    ));
+                }
+            }
+
+            let props = schema.get("properties").and_then(Value::as_object);
+            let patterns = schema.get("patternProperties").and_then(Value::as_object);
+            for (k, item) in obj {
+                let kp = ptr(ip, k);
+                let mut matched = false;
+                if let Some(s_k) = props.and_then(|p| p.get(k)) {
+                    matched = true;
+                    self.check(s_k, item, &kp, &ptr(&ptr(sp, "properties"), k), out);
+                }
+                if let Some(patterns) = patterns {
+                    for (p, s_p) in patterns {
+                        let pp = ptr(&ptr(sp, "patternProperties"), p);
+                        match self.regex(p) {
+                            Ok(re) if re.is_match(k) => {
+                                matched = true;
+                                self.check(s_p, item, &kp, &pp, out);
+                            }
+                            Ok(_) => {}
+                            Err(e) => out.push(fail(ip, pp, format!("schema 中的正则无效: {}", e))),
+                        }
+                    }
+                }
+                if matched {
+                    ev.props.insert(k.clone());
+                } else if let Some(add) = schema.get("additionalProperties") {
+                    let ap = ptr(sp, "additionalProperties");
+                    if add == &Value::Bool(false) {
+                        out.push(fail(&kp, ap, format!("不允许额外属性 \"{}\"", k)));
+                    } else {
+                        self.check(add, item, &kp, &ap, out);
+                    }
+                    ev.props.insert(k.clone());
+                }
+            }
+
+            if let Some(names) = schema.get("propertyNames") {
+                let np = ptr(sp, "propertyNames");
+                for k in obj.keys() {
+                    let (ok, _) = self.probe(names, &Value::String(k.clone()), &ptr(ip, k), &np);
+                    if !ok {
+                        out.push(fail(
+                            &ptr(ip, k),
+                            np.clone(),
+                            format!("属性名 \"{}\" 不符合 propertyNames", k),
+                        ));
+                    }
+                }
+            }
+
+            // dependentRequired / dependentSchemas，以及 draft-07 的 dependencies（两者合一）
+            for key in ["dependentRequired", "dependentSchemas", "dependencies"] {
+                let Some(Value::Object(deps)) = schema.get(key) else {
+                    continue;
+                };
+                for (k, dep) in deps.iter().filter(|(k, _)| obj.contains_key(*k)) {
+                    let dp = ptr(&ptr(sp, key), k);
+                    match dep {
+                        Value::Array(req) => {
+                            for r in req.iter().filter_map(Value::as_str) {
+                                if !obj.contains_key(r) {
+                                    out.push(fail(
+                                        ip,
+                                        dp.clone(),
+                                        format!("存在 \"{}\" 时必须同时存在 \"{}\"", k, r),
+                                    ));
+                                }
+                            }
+                        }
+                        s_dep => {
+                            let sub = self.check(s_dep, v, ip, &dp, out);
+                            ev.merge(sub);
+                        }
+                    }
+                }
+            }
+        }
+
+        // ---------- 组合 ----------
+        if let Some(Value::Array(all)) = schema.get("allOf") {
+            for (i, s_i) in all.iter().enumerate() {
+                let sub = self.check(s_i, v, ip, &ptr(&ptr(sp, "allOf"), &i.to_string()), out);
+                ev.merge(sub);
+            }
+        }
+        if let Some(Value::Array(any)) = schema.get("anyOf") {
+            let mut passed = false;
+            for (i, s_i) in any.iter().enumerate() {
+                let (ok, sub) = self.probe(s_i, v, ip, &ptr(&ptr(sp, "anyOf"), &i.to_string()));
+                if ok {
+                    passed = true;
+                    ev.merge(sub);
+                }
+            }
+            if !passed {
+                out.push(fail(
+                    ip,
+                    ptr(sp, "anyOf"),
+                    format!("不满足 anyOf 中的任何一个（共 {} 个）", any.len()),
+                ));
+            }
+        }
+        if let Some(Value::Array(one)) = schema.get("oneOf") {
+            let mut passed = Vec::new();
+            for (i, s_i) in one.iter().enumerate() {
+                let (ok, sub) = self.probe(s_i, v, ip, &ptr(&ptr(sp, "oneOf"), &i.to_string()));
+                if ok {
+                    passed.push(i);
+                    ev.merge(sub);
+                }
+            }
+            if passed.len() != 1 {
+                let message = if passed.is_empty() {
+                    format!("不满足 oneOf 中的任何一个（共 {} 个）", one.len())
+                } else {
+                    format!("同时满足 oneOf 中的第 {:?} 个，只允许满足一个", passed)
+                };
+                out.push(fail(ip, ptr(sp, "oneOf"), message));
+            }
+        }
+        if let Some(not) = schema.get("not") {
+            if self.probe(not, v, ip, &ptr(sp, "not")).0 {
+                out.push(fail(ip, ptr(sp, "not"), "不应满足 not 中的 schema".into()));
+            }
+        }
+        if let Some(cond) = schema.get("if") {
+            let (ok, sub) = self.probe(cond, v, ip, &ptr(sp, "if"));
+            let branch = if ok {
+                ev.merge(sub);
+                "then"
+            } else {
+                "else"
+            };
+            if let Some(s_b) = schema.get(branch) {
+                let sub = self.check(s_b, v, ip, &ptr(sp, branch), out);
+                ev.merge(sub);
+            }
+        }
+
+        // ---------- unevaluated*：必须在其它关键字之后 ----------
+        if let (Some(un), Value::Object(obj)) = (schema.get("unevaluatedProperties"), v) {
+            let up = ptr(sp, "unevaluatedProperties");
+            for (k, item) in obj.iter().filter(|(k, _)| !ev.props.contains(*k)) {
+                if un == &Value::Bool(false) {
+                    out.push(fail(
+                        &ptr(ip, k),
+                        up.clone(),
+                        format!("不允许未声明的属性 \"{}\"", k),
+                    ));
+                } else {
+                    self.check(un, item, &ptr(ip, k), &up, out);
+                }
+            }
+            ev.props.extend(obj.keys().cloned());
+        }
+        if let (Some(un), Value::Array(items)) = (schema.get("unevaluatedItems"), v) {
+            if !ev.all_items {
+                let up = ptr(sp, "unevaluatedItems");
+                let unevaluated = items
+                    .iter()
+                    .enumerate()
+                    .skip(ev.items)
+                    .filter(|(i, _)| !ev.contained.contains(i));
+                for (i, item) in unevaluated {
+                    if un == &Value::Bool(false) {
+                        out.push(fail(
+                            &ptr(ip, &i.to_string()),
+                            up.clone(),
+                            format!("不允许未声明的第 {} 项", i),
+                        ));
+                    } else {
+                        self.check(un, item, &ptr(ip, &i.to_string()), &up, out);
+                    }
+                }
+            }
+            ev.all_items = true;
+        }
+
+        ev
+    }
+}
+
+/// 在 schema 树中查找第一个满足条件的子 schema（用于 $anchor / $id）
+fn find_schema<'v>(s: &'v Value, pred: &dyn Fn(&Value) -> bool) -> Option<&'v Value> {
+    if s.is_object() && pred(s) {
+        return Some(s);
+    }
+    match s {
+        Value::Object(m) => m.values().find_map(|c| find_schema(c, pred)),
+        Value::Array(a) => a.iter().find_map(|c| find_schema(c, pred)),
+        _ => None,
+    }
+}
+
+/// 常见 format 的断言；未知 format 视为通过（按规范它只是注解）
+fn check_format(format: &str, s: &str) -> bool {
+    match format {
+        "date-time" => chrono::DateTime::parse_from_rfc3339(s).is_ok(),
+        "date" => chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok(),
+        "time" => chrono::DateTime::parse_from_rfc3339(&format!("1970-01-01T{}", s)).is_ok(),
+        "email" => match s.rsplit_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+            }
+            None => false,
+        },
+        "ipv4" => s.parse::<std::net::Ipv4Addr>().is_ok(),
+        "ipv6" => s.parse::<std::net::Ipv6Addr>().is_ok(),
+        "uuid" => {
+            let parts: Vec<&str> = s.split('-').collect();
+            parts.iter().map(|p| p.len()).eq([8, 4, 4, 4, 12])
+                && parts
+                    .iter()
+                    .all(|p| p.chars().all(|c| c.is_ascii_hexdigit()))
+        }
+        "uri" => s.split_once(':').is_some_and(|(scheme, _)| {
+            scheme
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }),
+        _ => true,
+    }
+}
+
+// ================= 推断 =================
+
+impl Shape {
+    fn to_schema(&self) -> Value {
+        let mut types: Vec<&str> = self.types.clone();
+        // integer 与 number 同时出现时合并为 number
+        if types.contains(&"number") {
+            types.retain(|t| *t != "integer");
+        }
+        let mut s = Map::new();
+        match types.as_slice() {
+            [] => {}
+            [t] => {
+                s.insert("type".into(), json!(t));
+            }
+            many => {
+                s.insert("type".into(), json!(many));
+            }
+        }
+        if types.contains(&"object") {
+            let props: Map<String, Value> = self
+                .properties
+                .iter()
+                .map(|(k, shape, _)| (k.clone(), shape.to_schema()))
+                .collect();
+            let required: Vec<Value> = self
+                .properties
+                .iter()
+                .filter(|(_, _, n)| *n == self.objects)
+                .map(|(k, _, _)| json!(k))
+                .collect();
+            s.insert("properties".into(), Value::Object(props));
+            if !required.is_empty() {
+                s.insert("required".into(), Value::Array(required));
+            }
+        }
+        if let Some(items) = self.items.as_ref().filter(|i| !i.types.is_empty()) {
+            s.insert("items".into(), items.to_schema());
+        }
+        Value::Object(s)
+    }
+}
+
+/// 由样本文档生成 draft 2020-12 schema
+///（多个样本——如 NDJSON 的各条记录——合并为一个 schema）
+pub fn infer<'v>(samples: impl IntoIterator<Item = &'v Value>) -> Value {
+    let mut shape = Shape::default();
+    for v in samples {
+        shape.observe(v);
+    }
+    let mut s = Map::new();
+    s.insert(
+        "$schema".into(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    if let Value::Object(body) = shape.to_schema() {
+        s.extend(body);
+    }
+    Value::Object(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 嵌套 n 层的链表 `{"c":{"c":…}}`
+    fn nested_objects(n: usize) -> Value {
+        (0..n).fold(json!({}), |inner, _| json!({ "c": inner }))
+    }
+
+    fn nested_arrays(n: usize) -> Value {
+        (0..n).fold(json!([]), |inner, _| json!([inner]))
+    }
+
+    /// 调试构建下 `check` 的栈帧较大，深层递归在默认 2 MiB 的测试线程栈上会溢出
+    fn on_big_stack(f: impl FnOnce() + Send + 'static) {
+        std::thread::Builder::new()
+            .stack_size(32 << 20)
+            .spawn(f)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn recursive_defs_accept_deep_instances() {
+        let schema = json!({
+            "$ref": "#/$defs/n",
+            "$defs": {"n": {"type": "object", "properties": {"c": {"$ref": "#/$defs/n"}}}}
+        });
+        on_big_stack(move || {
+            assert!(validate(&schema, &nested_objects(120)).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn recursive_root_ref_accepts_deep_arrays() {
+        let schema = json!({"type": "array", "items": {"$ref": "#"}});
+        let deep = schema.clone();
+        on_big_stack(move || {
+            assert!(validate(&deep, &nested_arrays(100)).unwrap().is_empty());
+        });
+        let bad = json!([[[1]]]);
+        let v = validate(&schema, &bad).unwrap();
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].instance_path, "/0/0/0");
+    }
+
+    #[test]
+    fn recursive_schema_reports_deep_violation() {
+        let schema = json!({
+            "$ref": "#/$defs/n",
+            "$defs": {"n": {"type": "object", "properties": {"c": {"$ref": "#/$defs/n"}}}}
+        });
+        let mut deep = nested_objects(80);
+        let mut cur = &mut deep;
+        for _ in 0..79 {
+            cur = cur.get_mut("c").unwrap();
+        }
+        cur["c"] = json!(1);
+        on_big_stack(move || {
+            let v = validate(&schema, &deep).unwrap();
+            assert_eq!(v.len(), 1);
+            assert_eq!(v[0].instance_path, "/c".repeat(80));
+        });
+    }
+
+    #[test]
+    fn ref_cycle_without_progress_is_reported() {
+        for schema in [
+            json!({"$ref": "#"}),
+            json!({"$ref": "#/$defs/a", "$defs": {"a": {"$ref": "#/$defs/b"}, "b": {"$ref": "#/$defs/a"}}}),
+        ] {
+            let v = validate(&schema, &json!(1)).unwrap();
+            assert_eq!(v.len(), 1);
+            assert!(v[0].message.contains("循环引用"));
+        }
+    }
+
+    #[test]
+    fn same_ref_at_same_place_through_siblings_is_not_a_cycle() {
+        // allOf 两个分支都引用同一定义，不是循环
+        let schema = json!({
+            "allOf": [{"$ref": "#/$defs/s"}, {"$ref": "#/$defs/s"}],
+            "$defs": {"s": {"type": "string"}}
+        });
+        assert!(validate(&schema, &json!("x")).unwrap().is_empty());
+        assert_eq!(validate(&schema, &json!(1)).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,66 @@
+// src/component/json/shape.rs
+//
+// 从样本值归纳“形状”：同一位置上出现过的类型、对象成员及其出现次数、数组元素合并后的形状。
+// Schema 推断与类型代码生成共用。
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// JSON Schema 的类型名（整数单独归为 integer）
+pub fn type_of(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// 某一位置上观察到的所有形状
+#[derive(Default)]
+pub struct Shape {
+    pub types: Vec<&'static str>,
+    /// 出现过该位置的对象个数（判断成员是否必有）
+    pub objects: usize,
+    /// key -> (形状, 出现次数)，按首次出现顺序
+    pub properties: Vec<(String, Shape, usize)>,
+    index: HashMap<String, usize>,
+    pub items: Option<Box<Shape>>,
+}
+
+impl Shape {
+    pub fn observe(&mut self, v: &Value) {
+        let t = type_of(v);
+        if !self.types.contains(&t) {
+            self.types.push(t);
+        }
+        match v {
+            Value::Object(m) => {
+                self.objects += 1;
+                for (k, child) in m {
+                    let i = match self.index.get(k) {
+                        Some(&i) => i,
+                        None => {
+                            self.index.insert(k.clone(), self.properties.len());
+                            self.properties.push((k.clone(), Shape::default(), 0));
+                            self.properties.len() - 1
+                        }
+                    };
+                    let entry = &mut self.properties[i];
+                    entry.1.observe(child);
+                    entry.2 += 1;
+                }
+            }
+            Value::Array(a) => {
+                let items = self.items.get_or_insert_with(Default::default);
+                for child in a {
+                    items.observe(child);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// src/component/json/window.rs
+//
+// 超大输出的窗口化渲染：把序列化后的文本切成“行”，只高亮并挂载可视区附近的行。
+//
+// - 行在换行处断开；压缩输出只有一行，超过 MAX_ROW_BYTES 后在下一个字符串外的逗号处断开，
+//   因此每行都从 token 边界开始，可以单独高亮。
+// - 每 CHECKPOINT_EVERY 行保存一次高亮状态，任意行的起始状态只需从最近的检查点扫描不到一屏文本。
+
+use crate::component::util::highlight::JsonScanState;
+use std::ops::Range;
+
+/// 输出文本超过该字节数时改用窗口化渲染
+pub const VIRTUAL_MIN_BYTES: usize = 256 * 1024;
+/// 行高（px），与输出区 13px 字号 × 1.6 行距一致
+pub const ROW_HEIGHT: f64 = 20.8;
+/// 可视区上下额外渲染的行数，减少快速滚动时的空白
+pub const OVERSCAN: usize = 40;
+
+/// webview 对元素高度有上限（约 3300 万 px），总高度超过它时按比例压缩滚动距离
+pub const MAX_SCROLL_PX: f64 = 8_000_000.0;
+
+const MAX_ROW_BYTES: usize = 200;
+const CHECKPOINT_EVERY: usize = 256;
+
+pub struct RowIndex {
+    starts: Vec<usize>,
+    checkpoints: Vec<JsonScanState>,
+    text_len: usize,
+}
+
+impl RowIndex {
+    pub fn build(text: &str) -> Self {
+        let bytes = text.as_bytes();
+        let mut state = JsonScanState::default();
+        let mut starts = vec![0];
+        let mut checkpoints = vec![state.clone()];
+        let mut row_start = 0;
+        for (i, &b) in bytes.iter().enumerate() {
+            state.step(b);
+            // 逗号紧跟换行时由换行断开，避免产生空行
+            let split = b == b'\n'
+                || (b == b','
+                    && !state.in_string()
+                    && i + 1 - row_start >= MAX_ROW_BYTES
+                    && !matches!(bytes.get(i + 1), Some(b'\n' | b'\r')));
+            if split && i + 1 < bytes.len() {
+                if starts.len() % CHECKPOINT_EVERY == 0 {
+                    checkpoints.push(state.clone());
+                }
+                starts.push(i + 1);
+                row_start = i + 1;
+            }
+        }
+        Self {
+            starts,
+            checkpoints,
+            text_len: text.len(),
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// 第 `row` 行的字节范围（不含行尾换行）
+    pub fn row_range(&self, text: &str, row: usize) -> Range<usize> {
+        let start = self.starts[row];
+        let mut end = self.starts.get(row + 1).copied().unwrap_or(self.text_len);
+        let b = text.as_bytes();
+        while end > start && matches!(b[end - 1], b'\n' | b'\r') {
+            end -= 1;
+        }
+        start..end
+    }
+
+    /// 字节位置所在的行
+    pub fn row_of(&self, pos: usize) -> usize {
+        self.starts.partition_point(|&s| s <= pos).saturating_sub(1)
+    }
+
+    /// 第 `row` 行开头的高亮状态
+    pub fn state_at(&self, text: &str, row: usize) -> JsonScanState {
+        let cp = row / CHECKPOINT_EVERY;
+        let mut state = self.checkpoints[cp].clone();
+        state.advance(&text[self.starts[cp * CHECKPOINT_EVERY]..self.starts[row]]);
+        state
+    }
+}
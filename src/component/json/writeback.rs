@@ -0,0 +1,549 @@
+// src/component/json/writeback.rs
+//
+// 树形编辑 / 结构化替换后把文档写回输入：只改写变化的部分，其余文本（缩进、换行、注释、
+// JSON5 写法、数字原文）原样保留。
+//
+// 1. 先在输入原文上扫出每个值的位置。扫描是宽松的（注释、单引号、无引号 key、尾随逗号都认），
+//    只求边界不求值；结构须与解析结果一一对应，对不上（如重复 key）时放弃，由调用方决定退路。
+// 2. 递归比较编辑前后的文档：相同的子树不动；成员个数不变时逐个改写变化的 key 与值；
+//    成员有增删时只重拼这一层的成员列表，保留的成员连同其前面的空白与注释照搬。
+// 3. 新写入的值按输入自身的缩进（从原文推断）序列化，不套用输出面板的打印选项。
+
+use super::printer::{self, Indent, PrintOptions};
+use serde_json::Value;
+use std::ops::Range;
+
+/// 在 `src` 中把 `before` 改写为 `after`；原文结构与 `before` 对不上时返回 None
+pub fn splice(src: &str, before: &Value, after: &Value) -> Option<String> {
+    let mut sc = Scanner {
+        b: src.as_bytes(),
+        pos: 0,
+    };
+    let root = sc.value()?;
+    sc.trivia();
+    if sc.pos != src.len() || !fits(&root, before) {
+        return None;
+    }
+    let sp = Splicer {
+        src,
+        indent: detect_indent(src),
+    };
+    let span = root.span();
+    Some(format!(
+        "{}{}{}",
+        &src[..span.start],
+        sp.render(&root, before, after),
+        &src[span.end..]
+    ))
+}
+
+/// 无法局部改写时的退路：按输入自身的缩进整体重排（同样不套用输出面板的打印选项）
+pub fn reformat(src: &str, after: &Value) -> String {
+    let opts = PrintOptions {
+        indent: detect_indent(src).unwrap_or(Indent::Spaces(2)),
+        ..PrintOptions::default()
+    };
+    let mut out = if src.trim().contains('\n') {
+        printer::pretty(after, &opts)
+    } else {
+        printer::minified(after, &opts)
+    };
+    if src.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// 原文的缩进单位：第一行带缩进的内容行；没有（单行 / 压缩）时为 None
+fn detect_indent(src: &str) -> Option<Indent> {
+    src.lines().skip(1).find_map(|l| {
+        let ws = &l[..l.len() - l.trim_start().len()];
+        if l.trim().is_empty() || ws.is_empty() {
+            None
+        } else if ws.starts_with('\t') {
+            Some(Indent::Tab)
+        } else {
+            Some(Indent::Spaces(ws.len().min(8) as u8))
+        }
+    })
+}
+
+enum Node {
+    Scalar(Range<usize>),
+    Container {
+        span: Range<usize>,
+        object: bool,
+        members: Vec<Member>,
+    },
+}
+
+impl Node {
+    fn span(&self) -> Range<usize> {
+        match self {
+            Node::Scalar(r) | Node::Container { span: r, .. } => r.clone(),
+        }
+    }
+}
+
+struct Member {
+    /// 前一个分隔符（`{` `[` 或逗号）之后的位置
+    lead: usize,
+    key: Option<Range<usize>>,
+    value: Node,
+    /// 其后的逗号
+    comma: Option<usize>,
+}
+
+impl Member {
+    fn start(&self) -> usize {
+        self.key
+            .as_ref()
+            .map_or(self.value.span().start, |k| k.start)
+    }
+}
+
+struct Scanner<'a> {
+    b: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    /// 跳过空白、BOM 与注释
+    fn trivia(&mut self) {
+        loop {
+            let rest = &self.b[self.pos..];
+            if rest.first().is_some_and(u8::is_ascii_whitespace) {
+                self.pos += 1;
+            } else if rest.starts_with("\u{feff}".as_bytes()) {
+                self.pos += 3;
+            } else if rest.starts_with(b"//") {
+                self.pos += rest.iter().position(|&c| c == b'\n').unwrap_or(rest.len());
+            } else if rest.starts_with(b"/*") {
+                self.pos += rest
+                    .windows(2)
+                    .skip(2)
+                    .position(|w| w == b"*/")
+                    .map_or(rest.len(), |p| p + 4);
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn value(&mut self) -> Option<Node> {
+        self.trivia();
+        let start = self.pos;
+        match *self.b.get(self.pos)? {
+            b'{' | b'[' => self.container(),
+            q @ (b'"' | b'\'') => {
+                self.string(q)?;
+                Some(Node::Scalar(start..self.pos))
+            }
+            _ => {
+                self.word();
+                (self.pos > start).then_some(Node::Scalar(start..self.pos))
+            }
+        }
+    }
+
+    fn string(&mut self, quote: u8) -> Option<()> {
+        self.pos += 1;
+        loop {
+            match *self.b.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                c if c == quote => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    /// 数字、字面量或无引号 key
+    fn word(&mut self) {
+        while self
+            .b
+            .get(self.pos)
+            .is_some_and(|c| !c.is_ascii_whitespace() && !b",:[]{}/\"'".contains(c))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn container(&mut self) -> Option<Node> {
+        let start = self.pos;
+        let object = self.b[start] == b'{';
+        let close = if object { b'}' } else { b']' };
+        self.pos += 1;
+        let mut members = Vec::new();
+        let mut lead = self.pos;
+        loop {
+            self.trivia();
+            if self.b.get(self.pos) == Some(&close) {
+                self.pos += 1;
+                return Some(Node::Container {
+                    span: start..self.pos,
+                    object,
+                    members,
+                });
+            }
+            let key = if object {
+                let k = self.pos;
+                match *self.b.get(self.pos)? {
+                    q @ (b'"' | b'\'') => self.string(q)?,
+                    _ => self.word(),
+                }
+                if self.pos == k {
+                    return None;
+                }
+                let key = k..self.pos;
+                self.trivia();
+                if self.b.get(self.pos) != Some(&b':') {
+                    return None;
+                }
+                self.pos += 1;
+                Some(key)
+            } else {
+                None
+            };
+            let value = self.value()?;
+            self.trivia();
+            let comma = (self.b.get(self.pos) == Some(&b',')).then_some(self.pos);
+            members.push(Member {
+                lead,
+                key,
+                value,
+                comma,
+            });
+            match comma {
+                Some(c) => {
+                    self.pos = c + 1;
+                    lead = self.pos;
+                }
+                None if self.b.get(self.pos) == Some(&close) => {}
+                None => return None,
+            }
+        }
+    }
+}
+
+/// 扫描结果与解析出的值结构一致（成员个数逐层相同）
+fn fits(node: &Node, v: &Value) -> bool {
+    match (node, v) {
+        (
+            Node::Container {
+                object: true,
+                members,
+                ..
+            },
+            Value::Object(m),
+        ) => {
+            members.len() == m.len()
+                && members
+                    .iter()
+                    .zip(m.values())
+                    .all(|(x, v)| fits(&x.value, v))
+        }
+        (
+            Node::Container {
+                object: false,
+                members,
+                ..
+            },
+            Value::Array(a),
+        ) => members.len() == a.len() && members.iter().zip(a).all(|(x, v)| fits(&x.value, v)),
+        (Node::Scalar(_), v) => !v.is_object() && !v.is_array(),
+        _ => false,
+    }
+}
+
+/// 容器的成员：(key, 值)，数组元素的 key 为 None
+fn entries(v: &Value) -> Vec<(Option<&str>, &Value)> {
+    match v {
+        Value::Object(m) => m.iter().map(|(k, v)| (Some(k.as_str()), v)).collect(),
+        Value::Array(a) => a.iter().map(|v| (None, v)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+struct Splicer<'a> {
+    src: &'a str,
+    indent: Option<Indent>,
+}
+
+impl Splicer<'_> {
+    /// `pos` 所在行开头的缩进
+    fn line_indent(&self, pos: usize) -> &str {
+        let start = self.src[..pos].rfind('\n').map_or(0, |p| p + 1);
+        let line = &self.src[start..pos];
+        &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+    }
+
+    /// 序列化新值；多行时后续行接在 `indent` 之后
+    fn write(&self, v: &Value, indent: &str, multiline: bool) -> String {
+        match self.indent {
+            Some(unit) if multiline => {
+                let opts = PrintOptions {
+                    indent: unit,
+                    ..PrintOptions::default()
+                };
+                printer::pretty(v, &opts).replace('\n', &format!("\n{}", indent))
+            }
+            _ => printer::minified(v, &PrintOptions::default()),
+        }
+    }
+
+    fn render(&self, node: &Node, b: &Value, a: &Value) -> String {
+        let span = node.span();
+        if b == a {
+            return self.src[span].to_string();
+        }
+        match node {
+            Node::Container {
+                members, object, ..
+            } if *object == a.is_object() && (a.is_object() || a.is_array()) => {
+                let (be, ae) = (entries(b), entries(a));
+                if be.len() == ae.len() {
+                    self.rewrite(span, members, &be, &ae)
+                } else {
+                    self.relist(span, members, &be, &ae)
+                }
+            }
+            _ => {
+                // 原本写在一行的容器换成新容器时仍写成一行
+                let multiline =
+                    matches!(node, Node::Scalar(_)) || self.src[span.clone()].contains('\n');
+                self.write(a, self.line_indent(span.start), multiline)
+            }
+        }
+    }
+
+    /// 成员个数不变：按位置改写变化的 key 与值
+    fn rewrite(
+        &self,
+        span: Range<usize>,
+        members: &[Member],
+        be: &[(Option<&str>, &Value)],
+        ae: &[(Option<&str>, &Value)],
+    ) -> String {
+        let mut out = String::new();
+        let mut pos = span.start;
+        for (m, ((bk, bv), (ak, av))) in members.iter().zip(be.iter().zip(ae)) {
+            if let (Some(k), Some(new_key)) = (&m.key, ak) {
+                if bk != ak {
+                    out.push_str(&self.src[pos..k.start]);
+                    out.push_str(&serde_json::to_string(new_key).unwrap_or_default());
+                    pos = k.end;
+                }
+            }
+            if bv != av {
+                let vs = m.value.span();
+                out.push_str(&self.src[pos..vs.start]);
+                out.push_str(&self.render(&m.value, bv, av));
+                pos = vs.end;
+            }
+        }
+        out.push_str(&self.src[pos..span.end]);
+        out
+    }
+
+    /// 成员有增删：首尾相同的成员照搬（对象按 key、数组按值比较），中间换成新成员
+    fn relist(
+        &self,
+        span: Range<usize>,
+        members: &[Member],
+        be: &[(Option<&str>, &Value)],
+        ae: &[(Option<&str>, &Value)],
+    ) -> String {
+        let same = |x: &(Option<&str>, &Value), y: &(Option<&str>, &Value)| match x.0 {
+            Some(_) => x.0 == y.0,
+            None => x.1 == y.1,
+        };
+        let head = be.iter().zip(ae).take_while(|(x, y)| same(x, y)).count();
+        let tail = be
+            .iter()
+            .rev()
+            .zip(ae.iter().rev())
+            .take(be.len().min(ae.len()) - head)
+            .take_while(|(x, y)| same(x, y))
+            .count();
+
+        let kept = |i: usize, j: usize| {
+            let m = &members[i];
+            let vs = m.value.span();
+            let end = match m.comma {
+                Some(c) if i + 1 < members.len() => c,
+                _ => vs.end,
+            };
+            format!(
+                "{}{}{}",
+                &self.src[m.lead..vs.start],
+                self.render(&m.value, be[i].1, ae[j].1),
+                &self.src[vs.end..end]
+            )
+        };
+        // 新成员照抄相邻成员的前导空白与 key 后的分隔；原本为空的容器按推断的缩进另起一行
+        let sample = members.get(1).or(members.first());
+        let lead = match sample {
+            Some(m) => {
+                let l = &self.src[m.lead..m.start()];
+                let ws = &l[l.trim_end().len()..];
+                match ws.rfind('\n') {
+                    Some(p) => format!("\n{}", &ws[p + 1..]),
+                    None => ws.to_string(),
+                }
+            }
+            None => match self.indent {
+                Some(unit) => format!("\n{}{}", self.line_indent(span.start), unit.unit()),
+                None => String::new(),
+            },
+        };
+        let colon = match sample.and_then(|m| Some((m.key.as_ref()?.end, m.value.span().start))) {
+            Some((k, v)) if self.src[k..v].trim() == ":" => &self.src[k..v],
+            _ if self.indent.is_some() => ": ",
+            _ => ":",
+        };
+        let indent = lead.rsplit_once('\n').map(|(_, ind)| ind.to_string());
+
+        let mut chunks: Vec<String> = (0..head).map(|i| kept(i, i)).collect();
+        for (k, v) in &ae[head..ae.len() - tail] {
+            let value = self.write(
+                v,
+                indent
+                    .as_deref()
+                    .unwrap_or_else(|| self.line_indent(span.start)),
+                indent.is_some(),
+            );
+            chunks.push(match k {
+                Some(k) => format!(
+                    "{}{}{}{}",
+                    lead,
+                    serde_json::to_string(k).unwrap_or_default(),
+                    colon,
+                    value
+                ),
+                None => format!("{}{}", lead, value),
+            });
+        }
+        chunks.extend((0..tail).map(|k| kept(be.len() - tail + k, ae.len() - tail + k)));
+
+        let open = &self.src[span.start..span.start + 1];
+        let close = &self.src[span.end - 1..span.end];
+        if chunks.is_empty() {
+            return format!("{}{}", open, close);
+        }
+        let rest = match members.last() {
+            Some(m) => self.src[m.value.span().end..span.end - 1].to_string(),
+            None if indent.is_some() => format!("\n{}", self.line_indent(span.start)),
+            None => self.src[span.start + 1..span.end - 1].to_string(),
+        };
+        format!("{}{}{}{}", open, chunks.join(","), rest, close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit(src: &str, after: Value) -> String {
+        let before: Value = match json5(src) {
+            Some(v) => v,
+            None => serde_json::from_str(src).unwrap(),
+        };
+        splice(src, &before, &after).expect("structure should match")
+    }
+
+    fn json5(src: &str) -> Option<Value> {
+        super::super::dialect::parse_document(src, super::super::dialect::InputDialect::Json5)
+            .ok()
+            .map(|(v, _)| v)
+    }
+
+    #[test]
+    fn untouched_text_is_kept() {
+        let src = "{\n    \"a\": 1.50,\n    \"b\": \"x\\/y\",\n    \"c\": [1,2]\n}\n";
+        let before: Value = serde_json::from_str(src).unwrap();
+        let mut after = before.clone();
+        after["c"][1] = json!(3);
+        assert_eq!(
+            splice(src, &before, &after).unwrap(),
+            "{\n    \"a\": 1.50,\n    \"b\": \"x\\/y\",\n    \"c\": [1,3]\n}\n"
+        );
+    }
+
+    #[test]
+    fn rename_keeps_comments_and_json5_syntax() {
+        let src = "{\n  // 注释\n  name: 'x', /* 行内 */\n  list: [1, 2,],\n}";
+        assert_eq!(
+            edit(src, json!({"title": "x", "list": [1, 2]})),
+            "{\n  // 注释\n  \"title\": 'x', /* 行内 */\n  list: [1, 2,],\n}"
+        );
+    }
+
+    #[test]
+    fn members_added_and_removed() {
+        let src = "{\n  \"a\": 1,\n  \"b\": 2\n}";
+        assert_eq!(edit(src, json!({"a": 1})), "{\n  \"a\": 1\n}");
+        assert_eq!(
+            edit(src, json!({"a": 1, "a_copy": 1, "b": 2})),
+            "{\n  \"a\": 1,\n  \"a_copy\": 1,\n  \"b\": 2\n}"
+        );
+        assert_eq!(
+            edit(src, json!({"a": 1, "b": 2, "c": {"d": [null]}})),
+            "{\n  \"a\": 1,\n  \"b\": 2,\n  \"c\": {\n    \"d\": [\n      null\n    ]\n  }\n}"
+        );
+        assert_eq!(edit("[1, 2, 3]", json!([1, 3])), "[1, 3]");
+        assert_eq!(edit("[1,2]", json!([1, 2, {"x": 1}])), "[1,2,{\"x\":1}]");
+        assert_eq!(
+            edit("{\n\t\"a\": []\n}", json!({"a": [true]})),
+            "{\n\t\"a\": [\n\t\ttrue\n\t]\n}"
+        );
+        assert_eq!(edit("[1]", json!([])), "[]");
+    }
+
+    #[test]
+    fn changed_values_use_input_indent() {
+        let src = "{\n    \"a\": 1\n}";
+        assert_eq!(
+            edit(src, json!({"a": {"b": "/é"}})),
+            "{\n    \"a\": {\n        \"b\": \"/é\"\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn structural_replace_touches_only_matches() {
+        use super::super::replace::{replace_in, ReplaceTarget};
+        use crate::component::util::search::{Matcher, SearchOptions};
+
+        let src = "{\n  // 说明\n  \"url\": \"http://a\",  \"n\": 1e3,\n  \"http\": [\"http\"]\n}";
+        let before = json5(src).unwrap();
+        let matcher = Matcher::new("http", SearchOptions::default())
+            .unwrap()
+            .unwrap();
+        let r = replace_in(&before, ReplaceTarget::Strings, "", &matcher, "https").unwrap();
+        assert_eq!(r.changed, 2);
+        assert_eq!(
+            splice(src, &before, &r.doc).unwrap(),
+            "{\n  // 说明\n  \"url\": \"https://a\",  \"n\": 1e3,\n  \"http\": [\"https\"]\n}"
+        );
+    }
+
+    #[test]
+    fn mismatched_structure_is_rejected() {
+        // 重复 key：解析结果只有一个成员
+        let src = r#"{"a": 1, "a": 2}"#;
+        let before: Value = serde_json::from_str(src).unwrap();
+        assert!(splice(src, &before, &json!({"b": 2})).is_none());
+    }
+
+    #[test]
+    fn reformat_follows_input_style() {
+        assert_eq!(reformat("{\"a\":1}", &json!({"a": [1]})), "{\"a\":[1]}");
+        assert_eq!(
+            reformat("{\n\t\"a\": 1\n}\n", &json!({"a": 2})),
+            "{\n\t\"a\": 2\n}\n"
+        );
+    }
+}
@@ -0,0 +1,654 @@
+// src/component/json_diff/diff.rs
+//
+// 结构化 JSON diff + RFC 6902 JSON Patch 导出。
+//
+// 设计要点：
+// 1. 对象按 key 比较，天然与 key 顺序无关；关闭“忽略 key 顺序”时额外报告顺序变化。
+// 2. 数组先做对齐（LCS），未对齐区间内按位置配对为“修改”，多出的部分为增 / 删。
+//    指定匹配 key（如 `id`）时按该字段对齐元素，位置变化的元素报告为“移动”。
+// 3. 每条差异同时记录左右两侧的 JSON Pointer，界面据此在两侧文本中标记行。
+// 4. JSON Patch 按顺序应用语义生成：数组操作用“已应用前面操作后”的下标，
+//    所以导出的补丁可以直接对左侧文档逐条执行得到右侧文档。
+// 5. 并排视图按行对齐：两侧 pretty 文本逐行做 LCS（忽略行尾逗号），
+//    未对齐区间内按位置配对，多出的行对面补空行。
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// 数组 / 文本行 LCS 的 DP 表上限（左长 × 右长）；超过则退化为按位置对齐
+const LCS_CELL_LIMIT: usize = 4_000_000;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+    TypeChanged,
+    Moved,
+    Reordered,
+}
+
+impl DiffKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffKind::Added => "新增",
+            DiffKind::Removed => "删除",
+            DiffKind::Changed => "修改",
+            DiffKind::TypeChanged => "类型变化",
+            DiffKind::Moved => "移动",
+            DiffKind::Reordered => "key 顺序",
+        }
+    }
+
+    /// 行标记用的 CSS class
+    pub fn class(&self) -> &'static str {
+        match self {
+            DiffKind::Added => "diff-added",
+            DiffKind::Removed => "diff-removed",
+            DiffKind::Changed | DiffKind::TypeChanged => "diff-changed",
+            DiffKind::Moved | DiffKind::Reordered => "diff-moved",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct DiffEntry {
+    pub kind: DiffKind,
+    /// 左侧 JSON Pointer（新增项为 None）
+    pub left_ptr: Option<String>,
+    /// 右侧 JSON Pointer（删除项为 None）
+    pub right_ptr: Option<String>,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+impl DiffEntry {
+    /// 展示用路径：优先右侧
+    pub fn path(&self) -> &str {
+        self.right_ptr
+            .as_deref()
+            .or(self.left_ptr.as_deref())
+            .unwrap_or("")
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct DiffOptions {
+    /// 忽略对象 key 顺序（不报告 Reordered）
+    pub ignore_key_order: bool,
+    /// 数组元素按该字段匹配（空 = 按内容对齐）
+    pub array_key: Option<String>,
+}
+
+/// JSON Pointer 转义（RFC 6901）：`~` -> `~0`，`/` -> `~1`
+pub fn pointer_push(base: &str, token: &str) -> String {
+    format!("{}/{}", base, token.replace('~', "~0").replace('/', "~1"))
+}
+
+fn type_name(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+// ================= diff =================
+
+pub fn diff(left: &Value, right: &Value, opts: &DiffOptions) -> Vec<DiffEntry> {
+    let mut out = Vec::new();
+    diff_values(left, right, "", "", opts, &mut out);
+    out
+}
+
+fn entry(
+    kind: DiffKind,
+    lp: Option<&str>,
+    rp: Option<&str>,
+    l: Option<&Value>,
+    r: Option<&Value>,
+) -> DiffEntry {
+    DiffEntry {
+        kind,
+        left_ptr: lp.map(str::to_string),
+        right_ptr: rp.map(str::to_string),
+        left: l.cloned(),
+        right: r.cloned(),
+    }
+}
+
+fn diff_values(
+    a: &Value,
+    b: &Value,
+    lp: &str,
+    rp: &str,
+    opts: &DiffOptions,
+    out: &mut Vec<DiffEntry>,
+) {
+    match (a, b) {
+        (Value::Object(ma), Value::Object(mb)) => diff_objects(ma, mb, lp, rp, opts, out),
+        (Value::Array(xa), Value::Array(xb)) => diff_arrays(xa, xb, lp, rp, opts, out),
+        _ if a == b => {}
+        _ if type_name(a) != type_name(b) => out.push(entry(
+            DiffKind::TypeChanged,
+            Some(lp),
+            Some(rp),
+            Some(a),
+            Some(b),
+        )),
+        _ => out.push(entry(
+            DiffKind::Changed,
+            Some(lp),
+            Some(rp),
+            Some(a),
+            Some(b),
+        )),
+    }
+}
+
+fn diff_objects(
+    ma: &Map<String, Value>,
+    mb: &Map<String, Value>,
+    lp: &str,
+    rp: &str,
+    opts: &DiffOptions,
+    out: &mut Vec<DiffEntry>,
+) {
+    if !opts.ignore_key_order {
+        let common_a: Vec<&String> = ma.keys().filter(|k| mb.contains_key(*k)).collect();
+        let common_b: Vec<&String> = mb.keys().filter(|k| ma.contains_key(*k)).collect();
+        if common_a != common_b {
+            out.push(entry(DiffKind::Reordered, Some(lp), Some(rp), None, None));
+        }
+    }
+    for (k, va) in ma {
+        if !mb.contains_key(k) {
+            out.push(entry(
+                DiffKind::Removed,
+                Some(&pointer_push(lp, k)),
+                None,
+                Some(va),
+                None,
+            ));
+        }
+    }
+    for (k, vb) in mb {
+        let rpk = pointer_push(rp, k);
+        match ma.get(k) {
+            Some(va) => diff_values(va, vb, &pointer_push(lp, k), &rpk, opts, out),
+            None => out.push(entry(DiffKind::Added, None, Some(&rpk), None, Some(vb))),
+        }
+    }
+}
+
+fn diff_arrays(
+    xa: &[Value],
+    xb: &[Value],
+    lp: &str,
+    rp: &str,
+    opts: &DiffOptions,
+    out: &mut Vec<DiffEntry>,
+) {
+    let key = opts.array_key.as_deref();
+    let steps = align_arrays(xa, xb, key);
+
+    // 按 key 匹配时：被 LCS 拆成“删 + 增”但 key 相同的元素视为移动
+    let mut moved_from: HashMap<usize, usize> = HashMap::new(); // ri -> li
+    let mut moved_left: Vec<bool> = vec![false; xa.len()];
+    if let Some(k) = key {
+        let removed: Vec<usize> = steps
+            .iter()
+            .filter_map(|s| match s {
+                Align::Removed(li) => Some(*li),
+                _ => None,
+            })
+            .collect();
+        for s in &steps {
+            if let Align::Added(ri) = s {
+                let Some(kb) = element_key(&xb[*ri], k) else {
+                    continue;
+                };
+                if let Some(&li) = removed
+                    .iter()
+                    .find(|&&li| !moved_left[li] && element_key(&xa[li], k) == Some(kb))
+                {
+                    moved_left[li] = true;
+                    moved_from.insert(*ri, li);
+                }
+            }
+        }
+    }
+
+    for s in steps {
+        match s {
+            Align::Pair(li, ri) => diff_values(
+                &xa[li],
+                &xb[ri],
+                &pointer_push(lp, &li.to_string()),
+                &pointer_push(rp, &ri.to_string()),
+                opts,
+                out,
+            ),
+            Align::Removed(li) if moved_left[li] => {}
+            Align::Removed(li) => out.push(entry(
+                DiffKind::Removed,
+                Some(&pointer_push(lp, &li.to_string())),
+                None,
+                Some(&xa[li]),
+                None,
+            )),
+            Align::Added(ri) => {
+                let rpi = pointer_push(rp, &ri.to_string());
+                if let Some(&li) = moved_from.get(&ri) {
+                    let lpi = pointer_push(lp, &li.to_string());
+                    out.push(entry(DiffKind::Moved, Some(&lpi), Some(&rpi), None, None));
+                    diff_values(&xa[li], &xb[ri], &lpi, &rpi, opts, out);
+                } else {
+                    out.push(entry(
+                        DiffKind::Added,
+                        None,
+                        Some(&rpi),
+                        None,
+                        Some(&xb[ri]),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+// ================= 数组对齐 =================
+
+#[derive(Clone, Copy)]
+enum Align {
+    Pair(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+fn element_key<'a>(v: &'a Value, key: &str) -> Option<&'a Value> {
+    v.as_object().and_then(|m| m.get(key))
+}
+
+fn elements_match(a: &Value, b: &Value, key: Option<&str>) -> bool {
+    match key {
+        Some(k) => match (element_key(a, k), element_key(b, k)) {
+            (Some(x), Some(y)) => x == y,
+            _ => a == b,
+        },
+        None => a == b,
+    }
+}
+
+/// 对齐两个数组，返回可按顺序应用的步骤：
+/// 对齐项（Pair）之间的未对齐区间，前若干项按位置配对为 Pair，剩余的先 Removed 再 Added；
+/// 按 key 对齐时区间内只配对两侧都没有该 key 的元素。
+fn align_arrays(xa: &[Value], xb: &[Value], key: Option<&str>) -> Vec<Align> {
+    let (n, m) = (xa.len(), xb.len());
+    let mut matches: Vec<(usize, usize)> = Vec::new();
+    if n.saturating_mul(m) <= LCS_CELL_LIMIT {
+        // lcs[i][j] = xa[i..] 与 xb[j..] 的 LCS 长度
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if elements_match(&xa[i], &xb[j], key) {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if elements_match(&xa[i], &xb[j], key) {
+                matches.push((i, j));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    let (mut li, mut ri) = (0, 0);
+    for (mi, mj) in matches.into_iter().chain(std::iter::once((n, m))) {
+        // 按 key 对齐时，只有两侧都不含该 key 的元素才按位置配对
+        let gap = (mi - li).min(mj - ri);
+        let paired = match key {
+            None => gap,
+            Some(k) => (0..gap)
+                .take_while(|&i| {
+                    element_key(&xa[li + i], k).is_none() && element_key(&xb[ri + i], k).is_none()
+                })
+                .count(),
+        };
+        for k in 0..paired {
+            steps.push(Align::Pair(li + k, ri + k));
+        }
+        steps.extend((li + paired..mi).map(Align::Removed));
+        steps.extend((ri + paired..mj).map(Align::Added));
+        if mi < n && mj < m {
+            steps.push(Align::Pair(mi, mj));
+        }
+        li = mi + 1;
+        ri = mj + 1;
+    }
+    steps
+}
+
+// ================= 并排视图的行对齐 =================
+
+/// 对齐两侧 pretty 文本的行，返回逐行的（左行号, 右行号），None 表示该侧补空行。
+/// 首尾相同的行直接配对，只对中间部分做 LCS。
+pub fn align_lines(left: &str, right: &str) -> Vec<(Option<usize>, Option<usize>)> {
+    let key = |l: &str| l.strip_suffix(',').unwrap_or(l).to_string();
+    let xa: Vec<String> = left.lines().map(key).collect();
+    let xb: Vec<String> = right.lines().map(key).collect();
+    let (n, m) = (xa.len(), xb.len());
+    let head = xa.iter().zip(&xb).take_while(|(a, b)| a == b).count();
+    let tail = xa[head..]
+        .iter()
+        .rev()
+        .zip(xb[head..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (ea, eb) = (n - tail, m - tail);
+
+    let mut matches: Vec<(usize, usize)> = (0..head).map(|i| (i, i)).collect();
+    let (ma, mb) = (&xa[head..ea], &xb[head..eb]);
+    let (p, q) = (ma.len(), mb.len());
+    if p.saturating_mul(q) <= LCS_CELL_LIMIT {
+        let mut lcs = vec![vec![0u32; q + 1]; p + 1];
+        for i in (0..p).rev() {
+            for j in (0..q).rev() {
+                lcs[i][j] = if ma[i] == mb[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < p && j < q {
+            if ma[i] == mb[j] {
+                matches.push((head + i, head + j));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+    matches.extend((0..tail).map(|k| (ea + k, eb + k)));
+
+    let mut rows = Vec::with_capacity(n.max(m));
+    let (mut li, mut ri) = (0, 0);
+    for (mi, mj) in matches.into_iter().chain(std::iter::once((n, m))) {
+        let paired = (mi - li).min(mj - ri);
+        rows.extend((0..paired).map(|k| (Some(li + k), Some(ri + k))));
+        rows.extend((li + paired..mi).map(|i| (Some(i), None)));
+        rows.extend((ri + paired..mj).map(|j| (None, Some(j))));
+        if mi < n && mj < m {
+            rows.push((Some(mi), Some(mj)));
+        }
+        li = mi + 1;
+        ri = mj + 1;
+    }
+    rows
+}
+
+// ================= JSON Patch =================
+
+/// 生成把 `left` 变成 `right` 的 RFC 6902 JSON Patch（数组）。
+pub fn json_patch(left: &Value, right: &Value, opts: &DiffOptions) -> Value {
+    let mut ops = Vec::new();
+    patch_values(left, right, "", opts, &mut ops);
+    Value::Array(ops)
+}
+
+fn op(kind: &str, path: &str, value: Option<&Value>) -> Value {
+    let mut m = Map::new();
+    m.insert("op".into(), Value::String(kind.into()));
+    m.insert("path".into(), Value::String(path.into()));
+    if let Some(v) = value {
+        m.insert("value".into(), v.clone());
+    }
+    Value::Object(m)
+}
+
+fn patch_values(a: &Value, b: &Value, path: &str, opts: &DiffOptions, ops: &mut Vec<Value>) {
+    if a == b {
+        return;
+    }
+    match (a, b) {
+        (Value::Object(ma), Value::Object(mb)) => {
+            for k in ma.keys().filter(|k| !mb.contains_key(*k)) {
+                ops.push(op("remove", &pointer_push(path, k), None));
+            }
+            for (k, vb) in mb {
+                let p = pointer_push(path, k);
+                match ma.get(k) {
+                    Some(va) => patch_values(va, vb, &p, opts, ops),
+                    None => ops.push(op("add", &p, Some(vb))),
+                }
+            }
+        }
+        (Value::Array(xa), Value::Array(xb)) => {
+            // cur：当前步骤在“已应用前面操作”的数组中的下标
+            let mut cur = 0usize;
+            for s in align_arrays(xa, xb, opts.array_key.as_deref()) {
+                match s {
+                    Align::Pair(li, ri) => {
+                        patch_values(
+                            &xa[li],
+                            &xb[ri],
+                            &pointer_push(path, &cur.to_string()),
+                            opts,
+                            ops,
+                        );
+                        cur += 1;
+                    }
+                    Align::Removed(_) => {
+                        ops.push(op("remove", &pointer_push(path, &cur.to_string()), None))
+                    }
+                    Align::Added(ri) => {
+                        ops.push(op(
+                            "add",
+                            &pointer_push(path, &cur.to_string()),
+                            Some(&xb[ri]),
+                        ));
+                        cur += 1;
+                    }
+                }
+            }
+        }
+        _ => ops.push(op("replace", path, Some(b))),
+    }
+}
+
+// ================= 带行号映射的 pretty 输出 =================
+
+/// 与 `serde_json::to_string_pretty` 相同格式的输出，并记录每个 JSON Pointer
+/// 所占的行区间（含首尾，0 起）。
+pub fn pretty_with_lines(v: &Value) -> (String, HashMap<String, (usize, usize)>) {
+    let mut w = LineWriter {
+        out: String::new(),
+        line: 0,
+        map: HashMap::new(),
+    };
+    w.write_value(v, "", 0);
+    w.map.insert(String::new(), (0, w.line));
+    (w.out, w.map)
+}
+
+struct LineWriter {
+    out: String,
+    line: usize,
+    map: HashMap<String, (usize, usize)>,
+}
+
+impl LineWriter {
+    fn newline(&mut self, indent: usize) {
+        self.out.push('\n');
+        self.line += 1;
+        for _ in 0..indent {
+            self.out.push_str("  ");
+        }
+    }
+
+    fn write_value(&mut self, v: &Value, ptr: &str, indent: usize) {
+        match v {
+            Value::Object(m) if !m.is_empty() => {
+                self.out.push('{');
+                for (i, (k, child)) in m.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    self.newline(indent + 1);
+                    let start = self.line;
+                    self.out
+                        .push_str(&serde_json::to_string(k).unwrap_or_default());
+                    self.out.push_str(": ");
+                    let p = pointer_push(ptr, k);
+                    self.write_value(child, &p, indent + 1);
+                    self.map.insert(p, (start, self.line));
+                }
+                self.newline(indent);
+                self.out.push('}');
+            }
+            Value::Array(a) if !a.is_empty() => {
+                self.out.push('[');
+                for (i, child) in a.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    self.newline(indent + 1);
+                    let start = self.line;
+                    let p = pointer_push(ptr, &i.to_string());
+                    self.write_value(child, &p, indent + 1);
+                    self.map.insert(p, (start, self.line));
+                }
+                self.newline(indent);
+                self.out.push(']');
+            }
+            other => self
+                .out
+                .push_str(&serde_json::to_string(other).unwrap_or_default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 按 RFC 6902 逐条执行 add / remove / replace
+    fn apply_patch(doc: &mut Value, patch: &Value) {
+        for op in patch.as_array().unwrap() {
+            let path = op["path"].as_str().unwrap();
+            if path.is_empty() {
+                *doc = op["value"].clone();
+                continue;
+            }
+            let (parent, last) = path.rsplit_once('/').unwrap();
+            let last = last.replace("~1", "/").replace("~0", "~");
+            let target = doc.pointer_mut(parent).unwrap();
+            match (op["op"].as_str().unwrap(), target) {
+                ("add", Value::Array(a)) => a.insert(last.parse().unwrap(), op["value"].clone()),
+                ("remove", Value::Array(a)) => {
+                    a.remove(last.parse().unwrap());
+                }
+                ("add" | "replace", Value::Object(m)) => {
+                    m.insert(last, op["value"].clone());
+                }
+                ("remove", Value::Object(m)) => {
+                    m.shift_remove(&last);
+                }
+                ("replace", Value::Array(a)) => {
+                    a[last.parse::<usize>().unwrap()] = op["value"].clone()
+                }
+                (kind, _) => panic!("unexpected op {} at {}", kind, path),
+            }
+        }
+    }
+
+    fn round_trip(left: Value, right: Value, array_key: Option<&str>) {
+        let opts = DiffOptions {
+            ignore_key_order: true,
+            array_key: array_key.map(str::to_string),
+        };
+        let mut doc = left.clone();
+        apply_patch(&mut doc, &json_patch(&left, &right, &opts));
+        assert_eq!(doc, right, "{} -> {}", left, right);
+    }
+
+    #[test]
+    fn patch_round_trips() {
+        round_trip(
+            json!({"a": 1, "b": [1, 2, 3]}),
+            json!({"a": 2, "b": [0, 1, 3, 4]}),
+            None,
+        );
+        round_trip(json!([1, 2, 3, 4, 5]), json!([5, 3, 1]), None);
+        round_trip(
+            json!({"a/b": 1, "m~n": {"x": 1}}),
+            json!({"m~n": {"x": 2}, "c": null}),
+            None,
+        );
+        round_trip(json!({"a": [1]}), json!([1]), None);
+        round_trip(
+            json!([{"id": 1, "v": "a"}, {"id": 2, "v": "b"}, {"v": "x"}]),
+            json!([{"id": 2, "v": "B"}, {"id": 3}, {"v": "y"}, {"id": 1, "v": "a"}]),
+            Some("id"),
+        );
+    }
+
+    #[test]
+    fn patch_escapes_pointer_tokens() {
+        let opts = DiffOptions {
+            ignore_key_order: true,
+            array_key: None,
+        };
+        let patch = json_patch(&json!({"a/b": 1}), &json!({"a/b": 2}), &opts);
+        assert_eq!(
+            patch,
+            json!([{"op": "replace", "path": "/a~1b", "value": 2}])
+        );
+    }
+
+    #[test]
+    fn lines_are_aligned_with_fillers() {
+        let left = "{\n  \"a\": 1,\n  \"b\": 2\n}";
+        let right = "{\n  \"a\": 1,\n  \"x\": 0,\n  \"b\": 3\n}";
+        assert_eq!(
+            align_lines(left, right),
+            [
+                (Some(0), Some(0)),
+                (Some(1), Some(1)),
+                (Some(2), Some(2)),
+                (None, Some(3)),
+                (Some(3), Some(4)),
+            ]
+        );
+        // 只差行尾逗号的行视为相同
+        let rows = align_lines("[\n  1\n]", "[\n  1,\n  2\n]");
+        assert_eq!(
+            rows,
+            [
+                (Some(0), Some(0)),
+                (Some(1), Some(1)),
+                (None, Some(2)),
+                (Some(2), Some(3))
+            ]
+        );
+    }
+}
@@ -0,0 +1,381 @@
+mod diff;
+
+use crate::component::json::{sort_value, KeyOrder};
+use crate::component::persist;
+use crate::component::util::highlight::highlight_json_with_search;
+pub(crate) use diff::pretty_with_lines;
+use diff::{align_lines, diff, json_patch, DiffEntry, DiffKind, DiffOptions};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum DiffView {
+    SideBySide,
+    List,
+    Patch,
+}
+
+/// 跨重启保留的会话状态
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonDiffSession {
+    left: String,
+    right: String,
+    ignore_key_order: bool,
+    array_key: String,
+    view: DiffView,
+}
+impl Default for JsonDiffSession {
+    fn default() -> Self {
+        Self {
+            left: String::new(),
+            right: String::new(),
+            ignore_key_order: true,
+            array_key: String::new(),
+            view: DiffView::SideBySide,
+        }
+    }
+}
+
+/// 单行展示用的紧凑 JSON（过长截断）
+fn compact(v: &Option<Value>) -> String {
+    let Some(v) = v else {
+        return String::new();
+    };
+    let s = serde_json::to_string(v).unwrap_or_default();
+    if s.chars().count() > 120 {
+        let head: String = s.chars().take(120).collect();
+        format!("{}…", head)
+    } else {
+        s
+    }
+}
+
+/// 把 pretty 文本高亮后按行包成 div，并按 `marks` 给差异行加底色 class。
+/// `rows` 是与另一侧对齐后的逐行行号，None 处输出空白占位行，两侧因此行行对应。
+/// 高亮结果中换行符只出现在无色段里，所以按 '\n' 切分不会切断 span。
+fn render_side(text: &str, marks: &[&'static str], rows: &[Option<usize>], side: char) -> String {
+    let html = highlight_json_with_search(text, &[], 0);
+    let lines: Vec<&str> = html.split('\n').collect();
+    let mut out = String::with_capacity(html.len() + rows.len() * 48);
+    for row in rows {
+        let Some(i) = *row else {
+            out.push_str("<div class=\"diff-line diff-filler\"></div>");
+            continue;
+        };
+        let line = lines.get(i).copied().unwrap_or("");
+        let cls = marks.get(i).copied().unwrap_or("");
+        out.push_str(&format!(
+            "<div id=\"diff-{side}-{i}\" class=\"diff-line {cls}\">{line}</div>"
+        ));
+    }
+    out
+}
+
+/// 按差异条目给一侧的行打标记：先铺“移动 / 顺序”，再让增删改覆盖。
+fn line_marks(
+    entries: &[DiffEntry],
+    map: &HashMap<String, (usize, usize)>,
+    lines: usize,
+    left: bool,
+) -> Vec<&'static str> {
+    let mut marks = vec![""; lines];
+    let weak = |k: DiffKind| matches!(k, DiffKind::Moved | DiffKind::Reordered);
+    for pass_weak in [true, false] {
+        for e in entries.iter().filter(|e| weak(e.kind) == pass_weak) {
+            let ptr = if left { &e.left_ptr } else { &e.right_ptr };
+            let Some((start, end)) = ptr.as_ref().and_then(|p| map.get(p)).copied() else {
+                continue;
+            };
+            // 顺序变化只标对象首行，避免整块染色盖住内部差异
+            let end = if e.kind == DiffKind::Reordered { start } else { end };
+            for m in marks.iter_mut().take(end + 1).skip(start) {
+                *m = e.kind.class();
+            }
+        }
+    }
+    marks
+}
+
+#[component]
+pub fn JsonDiffTool() -> Element {
+    let mut session = use_context::<Signal<JsonDiffSession>>();
+    let restored = session.peek().clone();
+
+    // ------- 输入与选项 -------
+    let mut left_input = use_signal(|| restored.left.clone());
+    let mut right_input = use_signal(|| restored.right.clone());
+    let mut ignore_key_order = use_signal(|| restored.ignore_key_order);
+    let mut array_key = use_signal(|| restored.array_key.clone());
+    let mut view = use_signal(|| restored.view);
+
+    // ------- 结果 -------
+    let mut entries = use_signal(Vec::<DiffEntry>::new);
+    let mut left_html = use_signal(String::new);
+    let mut right_html = use_signal(String::new);
+    let mut patch_text = use_signal(String::new);
+    // JSON Pointer -> 行区间（列表跳转用）
+    let mut left_lines = use_signal(HashMap::<String, (usize, usize)>::new);
+    let mut right_lines = use_signal(HashMap::<String, (usize, usize)>::new);
+    let mut error_message = use_signal(String::new);
+
+    // 写回会话状态（由 App 防抖落盘）
+    use_effect(move || {
+        let next = JsonDiffSession {
+            left: persist::capped(&left_input.read()),
+            right: persist::capped(&right_input.read()),
+            ignore_key_order: *ignore_key_order.read(),
+            array_key: array_key.read().clone(),
+            view: *view.read(),
+        };
+        if *session.peek() != next {
+            session.set(next);
+        }
+    });
+
+    // ========== 计算 diff：解析 -> (排序) -> diff / patch / 两侧渲染 ==========
+    use_effect(move || {
+        let l_src = left_input.read().clone();
+        let r_src = right_input.read().clone();
+        let ignore_order = *ignore_key_order.read();
+        let key = array_key.read().trim().to_string();
+
+        let mut clear = move || {
+            entries.set(Vec::new());
+            left_html.set(String::new());
+            right_html.set(String::new());
+            patch_text.set(String::new());
+        };
+
+        if l_src.trim().is_empty() || r_src.trim().is_empty() {
+            clear();
+            error_message.set(String::new());
+            return;
+        }
+
+        let parse = |src: &str, side: &str| -> Result<Value, String> {
+            serde_json::from_str::<Value>(src).map_err(|e| format!("{} JSON 解析错误: {}", side, e))
+        };
+        let (mut lv, mut rv) = match (parse(&l_src, "左侧"), parse(&r_src, "右侧")) {
+            (Ok(l), Ok(r)) => (l, r),
+            (Err(e), _) | (_, Err(e)) => {
+                clear();
+                error_message.set(e);
+                return;
+            }
+        };
+        error_message.set(String::new());
+
+        // 忽略 key 顺序：两侧都按 key 排序后再展示，行就能大致对上
+        if ignore_order {
+            lv = sort_value(lv, KeyOrder::Byte);
+            rv = sort_value(rv, KeyOrder::Byte);
+        }
+
+        let opts = DiffOptions {
+            ignore_key_order: ignore_order,
+            array_key: if key.is_empty() { None } else { Some(key) },
+        };
+        let found = diff(&lv, &rv, &opts);
+
+        let (l_text, l_map) = pretty_with_lines(&lv);
+        let (r_text, r_map) = pretty_with_lines(&rv);
+        let l_marks = line_marks(&found, &l_map, l_text.lines().count(), true);
+        let r_marks = line_marks(&found, &r_map, r_text.lines().count(), false);
+        let (l_rows, r_rows): (Vec<_>, Vec<_>) = align_lines(&l_text, &r_text).into_iter().unzip();
+        left_html.set(render_side(&l_text, &l_marks, &l_rows, 'l'));
+        right_html.set(render_side(&r_text, &r_marks, &r_rows, 'r'));
+        left_lines.set(l_map);
+        right_lines.set(r_map);
+
+        let patch = json_patch(&lv, &rv, &opts);
+        patch_text.set(serde_json::to_string_pretty(&patch).unwrap_or_default());
+        entries.set(found);
+    });
+
+    // 跳到某条差异：切到并排视图并把两侧对应行滚到中间
+    let mut jump_to = move |e: DiffEntry| {
+        view.set(DiffView::SideBySide);
+        let line_of = |ptr: &Option<String>, map: &HashMap<String, (usize, usize)>| {
+            ptr.as_ref().and_then(|p| map.get(p)).map(|r| r.0)
+        };
+        let l = line_of(&e.left_ptr, &left_lines.read());
+        let r = line_of(&e.right_ptr, &right_lines.read());
+        let js_line = |o: Option<usize>| o.map_or("-1".to_string(), |n| n.to_string());
+        let js = format!(
+            "setTimeout(function(){{[['l',{l}],['r',{r}]].forEach(function(p){{if(p[1]<0)return;var el=document.getElementById('diff-'+p[0]+'-'+p[1]);if(el)el.scrollIntoView({{block:'center'}});}});}},30);",
+            l = js_line(l),
+            r = js_line(r)
+        );
+        dioxus::document::eval(&js);
+    };
+
+    // ========== 复制 JSON Patch ==========
+    let copy_patch = move |_| {
+        let text = patch_text.read().clone();
+        if text.is_empty() {
+            return;
+        }
+        let escaped = serde_json::to_string(&text).unwrap_or_else(|_| "\"\"".to_string());
+        let js = format!(
+            "navigator.clipboard.writeText({t}).then(function(){{var b=document.getElementById('diff-copy-btn');if(b){{var o=b.textContent;b.textContent='已复制 ✓';setTimeout(function(){{b.textContent=o;}},1200);}}}}).catch(function(){{var b=document.getElementById('diff-copy-btn');if(b){{b.textContent='复制失败';setTimeout(function(){{b.textContent='复制 JSON Patch';}},1200);}}}});",
+            t = escaped
+        );
+        dioxus::document::eval(&js);
+    };
+
+    // 汇总
+    let (n_add, n_del, n_chg) = entries.read().iter().fold((0, 0, 0), |acc, e| match e.kind {
+        DiffKind::Added => (acc.0 + 1, acc.1, acc.2),
+        DiffKind::Removed => (acc.0, acc.1 + 1, acc.2),
+        _ => (acc.0, acc.1, acc.2 + 1),
+    });
+    let summary = if entries.read().is_empty() {
+        "无差异".to_string()
+    } else {
+        format!("新增 {} · 删除 {} · 修改 {}", n_add, n_del, n_chg)
+    };
+
+    let seg_active = "background:var(--accent-soft); color:var(--text-bright);";
+    let seg_normal = "";
+    let v_side_style = if *view.read() == DiffView::SideBySide { seg_active } else { seg_normal };
+    let v_list_style = if *view.read() == DiffView::List { seg_active } else { seg_normal };
+    let v_patch_style = if *view.read() == DiffView::Patch { seg_active } else { seg_normal };
+    let order_style = if *ignore_key_order.read() { seg_active } else { seg_normal };
+    let patch_html = highlight_json_with_search(&patch_text.read(), &[], 0);
+    let mono = "font-family:'Menlo','Monaco','Consolas',monospace; font-size:13px; line-height:1.6;";
+
+    rsx! {
+        div {
+            class: "tool-container",
+            style: "display:flex; flex-direction:column; height:100%; background:var(--bg-app); color:var(--text);",
+
+            // 上：左右两个输入
+            div {
+                style: "display:flex; flex:1; padding:14px 14px 0; gap:14px; min-height:0;",
+                div {
+                    class: "tb-panel",
+                    style: "flex:1; display:flex; flex-direction:column;",
+                    div { class: "tb-panel-header", span { "左侧（原始）" } }
+                    textarea {
+                        class: "tb-textarea tb-scroll",
+                        value: "{left_input}",
+                        oninput: move |e| left_input.set(e.value()),
+                        placeholder: "粘贴原始 JSON…"
+                    }
+                }
+                div {
+                    class: "tb-panel",
+                    style: "flex:1; display:flex; flex-direction:column;",
+                    div { class: "tb-panel-header", span { "右侧（新）" } }
+                    textarea {
+                        class: "tb-textarea tb-scroll",
+                        value: "{right_input}",
+                        oninput: move |e| right_input.set(e.value()),
+                        placeholder: "粘贴要比较的 JSON…"
+                    }
+                }
+            }
+
+            // 下：差异结果
+            div {
+                class: "tb-panel",
+                style: "flex:1.3; display:flex; flex-direction:column; margin:14px; min-height:0;",
+                div {
+                    class: "tb-panel-header",
+                    span { "差异" }
+                    div {
+                        style: "display:flex; border:1px solid var(--border-btn); border-radius:var(--radius-sm); overflow:hidden;",
+                        button { class: "tb-seg-btn", style: "{v_side_style}", onclick: move |_| view.set(DiffView::SideBySide), "并排" }
+                        button { class: "tb-seg-btn", style: "{v_list_style}", onclick: move |_| view.set(DiffView::List), "列表" }
+                        button { class: "tb-seg-btn", style: "{v_patch_style}", onclick: move |_| view.set(DiffView::Patch), "JSON Patch" }
+                    }
+                    button {
+                        class: "tb-seg-btn",
+                        style: "{order_style}",
+                        title: "两侧按 key 排序后比较，不报告 key 顺序变化",
+                        onclick: move |_| ignore_key_order.with_mut(|v| *v = !*v),
+                        "忽略 key 顺序"
+                    }
+                    input {
+                        class: "tb-search-input",
+                        style: "flex:0 0 130px; padding:3px 8px; font-size:12px;",
+                        value: "{array_key}",
+                        placeholder: "数组匹配 key，如 id",
+                        title: "数组元素按该字段匹配（留空则按内容对齐）",
+                        oninput: move |e| array_key.set(e.value()),
+                    }
+                    span { style: "flex:1;" }
+                    span { style: "font-size:11px; color:var(--text-dim); font-weight:400;", "{summary}" }
+                    button {
+                        id: "diff-copy-btn",
+                        class: "tb-btn-ghost",
+                        style: "padding:3px 10px; font-size:11px;",
+                        onclick: copy_patch,
+                        "复制 JSON Patch"
+                    }
+                }
+
+                match *view.read() {
+                    DiffView::SideBySide => rsx! {
+                        div {
+                            style: "flex:1; display:flex; min-height:0;",
+                            div {
+                                class: "tb-scroll",
+                                style: "flex:1; overflow:auto; padding:10px 0; background:var(--bg-input); white-space:pre; border-right:1px solid var(--border); {mono}",
+                                dangerous_inner_html: "{left_html}",
+                            }
+                            div {
+                                class: "tb-scroll",
+                                style: "flex:1; overflow:auto; padding:10px 0; background:var(--bg-input); white-space:pre; {mono}",
+                                dangerous_inner_html: "{right_html}",
+                            }
+                        }
+                    },
+                    DiffView::List => rsx! {
+                        div {
+                            class: "tb-scroll",
+                            style: "flex:1; overflow:auto; background:var(--bg-input); {mono}",
+                            for (i, e) in entries.read().iter().cloned().enumerate() {
+                                div {
+                                    key: "{i}",
+                                    class: "diff-item {e.kind.class()}",
+                                    onclick: move |_| jump_to(e.clone()),
+                                    span { class: "diff-badge", "{e.kind.label()}" }
+                                    span { class: "json-key", "{e.path()}" }
+                                    if e.kind == DiffKind::Moved {
+                                        span { style: "color:var(--text-dim);", "{e.left_ptr.clone().unwrap_or_default()} → {e.right_ptr.clone().unwrap_or_default()}" }
+                                    } else {
+                                        span { style: "color:var(--text-dim);", "{compact(&e.left)}" }
+                                        if e.left.is_some() && e.right.is_some() {
+                                            span { style: "color:var(--text-dim);", "→" }
+                                        }
+                                        span { "{compact(&e.right)}" }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    DiffView::Patch => rsx! {
+                        div {
+                            class: "tb-scroll",
+                            style: "flex:1; overflow:auto; padding:14px; background:var(--bg-input); white-space:pre-wrap; word-break:break-word; {mono}",
+                            dangerous_inner_html: "{patch_html}",
+                        }
+                    },
+                }
+            }
+
+            // 错误信息
+            if !error_message().is_empty() {
+                div {
+                    class: "error-message",
+                    style: "padding:10px 12px; color:var(--danger); background:rgba(244,135,113,.1); border:1px solid var(--danger); border-radius:var(--radius-sm); margin:0 14px 14px; font-size:13px;",
+                    "{error_message}"
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,23 @@
-use dioxus::desktop::use_window;
+use dioxus::desktop::tao::event::Event;
+use dioxus::desktop::{use_window, use_wry_event_handler, WindowEvent};
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
 mod base64;
+mod converter;
 mod json;
+mod json_diff;
+mod persist;
+mod theme;
 mod timestamp;
 mod util;
 
 use crate::component::base64::Base64Tool;
+use crate::component::converter::FormatConverterTool;
 use crate::component::json::JsonFormatterTool;
+use crate::component::json_diff::JsonDiffTool;
+use crate::component::persist::{PersistedState, STATE_VERSION};
+use crate::component::theme::{Base, ThemeChoice, UserThemes};
 pub use timestamp::TimestampTool;
 
 /// 全局样式：统一设计 token + 通用组件 class（含 :hover/:active/:focus 等伪类，
@@ -20,13 +29,17 @@ const GLOBAL_CSS: &str = r#"
   --text:#cccccc; --text-dim:#8a8a8a; --text-bright:#ececec;
   --accent:#0e84d8; --accent-hover:#1196e8; --accent-soft:rgba(14,132,216,.16);
   --danger:#f48771; --danger-soft:rgba(127,58,50,.18); --ok:#73c991;
+  --warn:#cca700; --warn-soft:rgba(204,167,0,.1);
   --bg-btn:#2d2d30; --bg-btn-hover:#3a3a3e; --border-btn:#454545; --border-btn-hover:#5a5a5a;
   --bg-hover:#262626; --bg-input-2:#1b1b1b;
   --text-on-accent:#fff; --placeholder:#5a5a5a;
   --scrollbar:#3a3a3a; --scrollbar-hover:#4a4a4a; --handle-1:#232323; --handle-2:#2b2b2b;
   --shadow:0 2px 10px rgba(0,0,0,.18); --shadow-card:0 4px 14px rgba(0,0,0,.22);
   --radius:8px; --radius-sm:6px;
-  --json-key:#9cdcfe; --json-string:#ce9178; --json-number:#b5cea8; --json-bool:#569cd6; --json-null:#569cd6; --json-brace:#cccccc; --json-index:#dcdcaa;
+  --syn-key:#9cdcfe; --syn-string:#ce9178; --syn-number:#b5cea8; --syn-keyword:#569cd6; --syn-comment:#6a9955; --syn-tag:#4ec9b0; --syn-attr:#dcdcaa;
+  --match-bg:rgba(255,204,0,.35); --match-current-bg:#ffcc00; --match-current-fg:#000;
+  --json-key:var(--syn-key); --json-string:var(--syn-string); --json-number:var(--syn-number); --json-bool:var(--syn-keyword); --json-null:var(--syn-keyword); --json-brace:var(--text); --json-index:var(--syn-attr);
+  --diff-added:rgba(115,201,145,.16); --diff-removed:rgba(244,135,113,.18); --diff-changed:rgba(14,132,216,.20); --diff-moved:rgba(220,220,170,.14);
 }
 [data-theme="light"]{
   --bg-app:#f4f4f4; --bg-panel:#ffffff; --bg-header:#ececec; --bg-input:#ffffff;
@@ -34,12 +47,15 @@ const GLOBAL_CSS: &str = r#"
   --text:#242424; --text-dim:#6a6a6a; --text-bright:#0a0a0a;
   --accent:#0e84d8; --accent-hover:#0a6db5; --accent-soft:rgba(14,132,216,.12);
   --danger:#d04030; --danger-soft:rgba(208,64,48,.10); --ok:#2a8a4a;
+  --warn:#9a6700; --warn-soft:rgba(154,103,0,.08);
   --bg-btn:#ffffff; --bg-btn-hover:#eaeaea; --border-btn:#d0d0d0; --border-btn-hover:#b0b0b0;
   --bg-hover:#e6e6e6; --bg-input-2:#ffffff;
   --text-on-accent:#fff; --placeholder:#9a9a9a;
   --scrollbar:#c4c4c4; --scrollbar-hover:#a0a0a0; --handle-1:#e0e0e0; --handle-2:#d4d4d4;
   --shadow:0 2px 10px rgba(0,0,0,.08); --shadow-card:0 4px 14px rgba(0,0,0,.08);
-  --json-key:#0550ae; --json-string:#a31515; --json-number:#098658; --json-bool:#0000ff; --json-null:#0000ff; --json-brace:#242424; --json-index:#795e26;
+  --syn-key:#0550ae; --syn-string:#a31515; --syn-number:#098658; --syn-keyword:#0000ff; --syn-comment:#008000; --syn-tag:#267f99; --syn-attr:#795e26;
+  --match-bg:rgba(255,204,0,.45); --match-current-bg:#f5b400; --match-current-fg:#000;
+  --diff-added:rgba(42,138,74,.14); --diff-removed:rgba(208,64,48,.12); --diff-changed:rgba(14,132,216,.14); --diff-moved:rgba(121,94,38,.12);
 }
 *{box-sizing:border-box;}
 body{margin:0;background:var(--bg-app);}
@@ -65,6 +81,11 @@ body{margin:0;background:var(--bg-app);}
 .tb-search-input{flex:1;background:var(--bg-input-2);color:var(--text-bright);border:1px solid var(--border-btn);padding:6px 10px;font-family:'Menlo','Monaco','Consolas',monospace;font-size:13px;border-radius:var(--radius-sm);outline:none;transition:border-color .15s,box-shadow .15s;}
 .tb-search-input:focus{border-color:var(--accent);box-shadow:0 0 0 2px var(--accent-soft);}
 .tb-count{font-size:11px;color:var(--text-dim);min-width:36px;text-align:center;user-select:none;font-variant-numeric:tabular-nums;}
+.tb-search-input.invalid,.tb-search-input.invalid:focus{border-color:var(--danger);box-shadow:none;}
+.tb-search-toggle{background:transparent;color:var(--text-dim);border:1px solid transparent;min-width:24px;height:24px;padding:0 4px;border-radius:var(--radius-sm);cursor:pointer;font-size:12px;font-family:'Menlo','Monaco','Consolas',monospace;user-select:none;transition:background .15s,color .15s,border-color .15s;}
+.tb-search-toggle:hover{background:var(--bg-btn);color:var(--text-bright);}
+.tb-search-toggle.on{background:var(--accent-soft);color:var(--text-bright);border-color:var(--accent);}
+.tb-search-error{padding:0 8px 6px 38px;font-size:11px;color:var(--danger);user-select:text;}
 .tb-sidebar-item{cursor:pointer;padding:9px 10px;border-radius:var(--radius-sm);border:1px solid transparent;display:flex;align-items:center;gap:10px;color:var(--text);transition:background .15s,border-color .15s,color .15s;user-select:none;}
 .tb-sidebar-item:hover{background:var(--bg-hover);color:var(--text-bright);}
 .tb-mode-btn{padding:6px 12px;border-radius:var(--radius-sm);font-size:12px;cursor:pointer;border:1px solid transparent;transition:background .15s,color .15s,border-color .15s;user-select:none;}
@@ -88,6 +109,53 @@ body{margin:0;background:var(--bg-app);}
 .json-index{color:var(--json-index);}
 .json-count{color:var(--text-dim);font-size:11px;margin-left:4px;user-select:none;}
 .json-collapsed{color:var(--text-dim);}
+.json-badge-warn{color:var(--warn);background:var(--warn-soft);border-radius:3px;padding:0 4px;margin-left:6px;font-size:11px;white-space:nowrap;user-select:none;}
+.json-badge-embed{color:var(--accent);background:var(--accent-soft);border-radius:3px;padding:0 4px;margin-left:6px;font-size:11px;white-space:nowrap;user-select:none;}
+.json-line{border-radius:3px;}
+.json-line.json-drop{outline:1px dashed var(--accent);}
+.json-actions{display:none;margin-left:8px;gap:2px;align-items:center;vertical-align:middle;}
+.json-line:hover>.json-actions{display:inline-flex;}
+.json-act{background:var(--bg-btn);color:var(--text-dim);border:1px solid var(--border-btn);border-radius:3px;cursor:pointer;font-size:11px;font-family:inherit;line-height:1.2;padding:0 5px;user-select:none;}
+.json-act:hover{color:var(--text-bright);border-color:var(--border-btn-hover);}
+.json-edit-input{background:var(--bg-input-2);color:var(--text-bright);border:1px solid var(--accent);border-radius:3px;padding:0 4px;font-family:inherit;font-size:inherit;line-height:1.4;outline:none;min-width:60px;}
+.json-edit-input.invalid{border-color:var(--danger);}
+.json-type-select{background:var(--bg-btn);color:var(--text-dim);border:1px solid var(--border-btn);border-radius:3px;font-size:11px;font-family:inherit;padding:0 2px;cursor:pointer;}
+.json-editable{cursor:text;}
+.json-editable:hover{text-decoration:underline dotted;}
+.json-drag{cursor:grab;}
+.json-more{display:block;margin:2px 0;padding:1px 8px;}
+.json-line.json-selected{background:var(--accent-soft);}
+.json-hit{background:var(--match-bg);border-radius:2px;}
+.json-hit.json-hit-current{background:var(--match-current-bg);color:var(--match-current-fg);}
+.json-crumb{color:var(--text);cursor:pointer;border-radius:3px;padding:0 2px;}
+.json-crumb:hover{background:var(--bg-hover);color:var(--text-bright);}
+.json-menu{position:fixed;z-index:51;min-width:200px;background:var(--bg-card);border:1px solid var(--border);border-radius:var(--radius-sm);box-shadow:var(--shadow-card);padding:4px 0;font-size:12px;}
+.json-menu-item{padding:5px 12px;color:var(--text);cursor:pointer;user-select:none;}
+.tb-menu{position:fixed;z-index:51;min-width:180px;max-width:320px;max-height:70vh;overflow:auto;background:var(--bg-card);border:1px solid var(--border);border-radius:var(--radius-sm);box-shadow:var(--shadow-card);padding:4px 0;font-size:12px;}
+.tb-menu-item{padding:5px 12px;color:var(--text);cursor:pointer;user-select:none;white-space:nowrap;}
+.tb-menu-item:hover{background:var(--accent-soft);color:var(--text-bright);}
+.tb-menu-item.on{color:var(--accent);font-weight:600;}
+.tb-menu-sep{height:1px;margin:4px 0;background:var(--border-soft);}
+.tb-menu-note{padding:5px 12px;color:var(--danger);white-space:normal;}
+.json-menu-item:hover{background:var(--accent-soft);color:var(--text-bright);}
+.hl-key{color:var(--syn-key);}
+.hl-string{color:var(--syn-string);}
+.hl-number{color:var(--syn-number);}
+.hl-keyword{color:var(--syn-keyword);}
+.hl-comment{color:var(--syn-comment);}
+.hl-tag{color:var(--syn-tag);}
+.hl-attr{color:var(--syn-attr);}
+.hl-match{background:var(--match-bg);}
+.hl-current{background:var(--match-current-bg);color:var(--match-current-fg);}
+.diff-line{display:block;padding:0 14px;min-height:1.6em;}
+.diff-added{background:var(--diff-added);}
+.diff-removed{background:var(--diff-removed);}
+.diff-changed{background:var(--diff-changed);}
+.diff-moved{background:var(--diff-moved);}
+.diff-filler{background:repeating-linear-gradient(135deg,transparent 0 5px,var(--border-soft) 5px 6px);}
+.diff-item{display:flex;align-items:baseline;gap:8px;padding:5px 14px;border-bottom:1px solid var(--border-soft);cursor:pointer;white-space:nowrap;overflow:hidden;text-overflow:ellipsis;}
+.diff-item:hover{filter:brightness(1.15);}
+.diff-badge{font-size:11px;color:var(--text-bright);border:1px solid var(--border-btn);border-radius:4px;padding:0 5px;flex-shrink:0;user-select:none;}
 "#;
 
 /// macOS 透明标题栏下，内容顶部需让出红黄绿按钮 / 拖拽区的高度；其他平台为 0。
@@ -99,6 +167,8 @@ const TITLEBAR_PAD: &str = "0px";
 #[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
 enum Tool {
     JsonFormatter,
+    FormatConverter,
+    JsonDiff,
     Base64Encoder,
     TimestampConverter,
 }
@@ -107,6 +177,8 @@ impl Tool {
     fn name(&self) -> &'static str {
         match self {
             Tool::JsonFormatter => "JSON 格式化",
+            Tool::FormatConverter => "格式转换",
+            Tool::JsonDiff => "JSON 对比",
             Tool::Base64Encoder => "Base64 编解码",
             Tool::TimestampConverter => "时间戳转换",
         }
@@ -114,6 +186,8 @@ impl Tool {
     fn icon(&self) -> &'static str {
         match self {
             Tool::JsonFormatter => "📄",
+            Tool::FormatConverter => "🔄",
+            Tool::JsonDiff => "🔀",
             Tool::Base64Encoder => "🔒",
             Tool::TimestampConverter => "⏰",
         }
@@ -121,15 +195,32 @@ impl Tool {
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 struct AppState {
     current_tool: Tool,
-    dark: bool,
+    theme: ThemeChoice,
 }
 impl Default for AppState {
     fn default() -> Self {
         Self {
             current_tool: Tool::JsonFormatter,
-            dark: true,
+            theme: ThemeChoice::System,
+        }
+    }
+}
+
+/// 侧栏宽度 / 收起状态（持久化用；`width` 为展开时的宽度）
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct SidebarState {
+    width: f32,
+    collapsed: bool,
+}
+impl Default for SidebarState {
+    fn default() -> Self {
+        Self {
+            width: 180.0,
+            collapsed: false,
         }
     }
 }
@@ -137,7 +228,18 @@ impl Default for AppState {
 // 4) App
 #[component]
 pub fn App() -> Element {
-    let app_state = use_context_provider(|| Signal::new(AppState::default()));
+    // 启动时加载一次磁盘状态，拆分为各自的 context，工具组件按需取用
+    let restored = use_hook(persist::load);
+    let app_state = use_context_provider(|| Signal::new(restored.app.clone()));
+    let sidebar_state = use_context_provider(|| Signal::new(restored.sidebar.clone()));
+    let json_session = use_context_provider(|| Signal::new(restored.json.clone()));
+    let converter_session = use_context_provider(|| Signal::new(restored.converter.clone()));
+    let json_diff_session = use_context_provider(|| Signal::new(restored.json_diff.clone()));
+    let base64_session = use_context_provider(|| Signal::new(restored.base64.clone()));
+    let timestamp_session = use_context_provider(|| Signal::new(restored.timestamp.clone()));
+    let user_themes = use_context_provider(|| Signal::new(theme::load_user_themes()));
+    // 状态文件读写失败的说明（加载时的问题在此取到，写盘的问题由下面定时取回）
+    let mut persist_error = use_signal(persist::take_error);
     let desktop = use_window();
     let desktop_max = desktop.clone();
 
@@ -150,12 +252,42 @@ pub fn App() -> Element {
         ));
     });
 
-    // 主题切换：监听 dark 字段，设置 <html data-theme="dark|light">
+    // 主题切换：选择或用户主题文件变化时重新应用（设置 data-theme 与覆盖变量）
     use_effect(move || {
-        let theme = if app_state.read().dark { "dark" } else { "light" };
-        dioxus::document::eval(&format!(
-            "document.documentElement.setAttribute('data-theme','{theme}');"
-        ));
+        theme::apply(&app_state.read().theme, &user_themes.read().themes);
+    });
+
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            if let Some(e) = persist::take_error() {
+                persist_error.set(Some(e));
+            }
+        }
+    });
+
+    // 关闭窗口 / 退出应用时立即写入仍在防抖静默期内的状态
+    use_wry_event_handler(|event, _| {
+        if matches!(
+            event,
+            Event::WindowEvent { event: WindowEvent::CloseRequested, .. } | Event::LoopDestroyed
+        ) {
+            persist::flush();
+        }
+    });
+
+    // 持久化：任一状态变化都交给后台线程防抖写盘
+    use_effect(move || {
+        persist::save_debounced(PersistedState {
+            version: STATE_VERSION,
+            app: app_state.read().clone(),
+            sidebar: sidebar_state.read().clone(),
+            json: json_session.read().clone(),
+            converter: converter_session.read().clone(),
+            json_diff: json_diff_session.read().clone(),
+            base64: base64_session.read().clone(),
+            timestamp: timestamp_session.read().clone(),
+        });
     });
 
     rsx! {
@@ -193,8 +325,18 @@ pub fn App() -> Element {
                             "{app_state().current_tool.name()}"
                         }
 
-                        // 右侧占位
-                        div { style: "margin-left:auto; display:flex; align-items:center; gap:8px;" }
+                        // 右侧：状态文件读写失败时的提示（点击关闭）
+                        div {
+                            style: "margin-left:auto; display:flex; align-items:center; gap:8px;",
+                            if let Some(e) = persist_error() {
+                                span {
+                                    style: "font-size:12px; color:var(--warn); cursor:pointer; max-width:360px; overflow:hidden; text-overflow:ellipsis; white-space:nowrap;",
+                                    title: "{e}",
+                                    onclick: move |_| persist_error.set(None),
+                                    "⚠ {e}"
+                                }
+                            }
+                        }
                     }
 
                     // ===== 工具内容区 =====
@@ -203,6 +345,8 @@ pub fn App() -> Element {
                         style: "flex:1; overflow:hidden;",
                         match app_state().current_tool {
                             Tool::JsonFormatter => rsx! { JsonFormatterTool {} },
+                            Tool::FormatConverter => rsx! { FormatConverterTool {} },
+                            Tool::JsonDiff => rsx! { JsonDiffTool {} },
                             Tool::Base64Encoder => rsx! { Base64Tool {} },
                             Tool::TimestampConverter => rsx! { TimestampTool {} },
                         }
@@ -214,12 +358,16 @@ pub fn App() -> Element {
 }
 #[component]
 fn Sidebar(app_state: Signal<AppState>) -> Element {
+    // 上次会话保存的宽度 / 收起状态
+    let mut sidebar_state = use_context::<Signal<SidebarState>>();
+    let restored = sidebar_state.peek().clone();
+
     // --- 状态：宽度、是否收起、是否拖拽中、上一次鼠标x、收起前宽度 ---
-    let mut width = use_signal(|| 180.0_f32); // 当前宽度（展开时）
-    let mut collapsed = use_signal(|| false); // 是否收起（仅图标）
+    let mut width = use_signal(|| restored.width); // 当前宽度（展开时）
+    let mut collapsed = use_signal(|| restored.collapsed); // 是否收起（仅图标）
     let mut dragging = use_signal(|| false); // 是否正在拖拽
     let mut last_x = use_signal(|| 0.0_f32); // 上一次鼠标 x
-    let saved_width = use_signal(|| 180.0_f32); // 收起前记忆的宽度
+    let saved_width = use_signal(|| restored.width); // 收起前记忆的宽度
 
     // 限制/常量
     let min_w: f32 = 160.0;
@@ -229,9 +377,21 @@ fn Sidebar(app_state: Signal<AppState>) -> Element {
     // 阈值：小于等于该值就触发收起，避免来回抖动
     let collapse_threshold: f32 = min_w + 2.0;
 
+    // 写回会话状态（收起时记录收起前的宽度）
+    use_effect(move || {
+        let is_collapsed = *collapsed.read();
+        let w = if is_collapsed { *saved_width.read() } else { *width.read() };
+        let next = SidebarState { width: w.clamp(min_w, max_w), collapsed: is_collapsed };
+        if *sidebar_state.peek() != next {
+            sidebar_state.set(next);
+        }
+    });
+
     // 工具列表
     let tools = vec![
         Tool::JsonFormatter,
+        Tool::FormatConverter,
+        Tool::JsonDiff,
         Tool::Base64Encoder,
         Tool::TimestampConverter,
     ];
@@ -313,11 +473,28 @@ fn Sidebar(app_state: Signal<AppState>) -> Element {
         }
     };
 
-    // 主题切换按钮：当前是否暗色、对齐方式（收起时居中）、图标与文字
-    let is_dark = app_state().dark;
+    // 主题按钮：对齐方式（收起时居中）、图标与文字；点击弹出主题菜单
+    let mut user_themes = use_context::<Signal<UserThemes>>();
+    let mut theme_menu = use_signal(|| false);
+    let choice = app_state().theme;
     let theme_justify = if *collapsed.read() { "justify-content:center;" } else { "justify-content:flex-start;" };
-    let theme_icon = if is_dark { "🌙" } else { "☀️" };
-    let theme_label = if is_dark { "暗色" } else { "亮色" };
+    let theme_icon = match theme::base_of(&choice, &user_themes.read().themes) {
+        None => "🖥️",
+        Some(Base::Dark) => "🌙",
+        Some(Base::Light) => "☀️",
+    };
+    let theme_label = match &choice {
+        ThemeChoice::System => "跟随系统".to_string(),
+        ThemeChoice::Named(name) => name.clone(),
+    };
+    let theme_names: Vec<String> = theme::builtin()
+        .iter()
+        .chain(&user_themes.read().themes)
+        .map(|t| t.name.clone())
+        .collect();
+    let builtin_count = theme::builtin().len();
+    let theme_error = user_themes.read().error.clone();
+    let menu_left = sidebar_w + 8.0;
 
     rsx! {
         // 外层：包含侧栏与把手，侧栏上监听 move/up 便于拖拽
@@ -370,18 +547,63 @@ fn Sidebar(app_state: Signal<AppState>) -> Element {
                 // 占位撑满
                 div { style: "flex:1;" }
 
-                // 底部：主题切换（暗色🌙 / 亮色☀️）
+                // 底部：主题菜单入口
                 div {
                     class: "tb-sidebar-item",
                     style: "{theme_justify}",
-                    onclick: move |_| {
-                        let new_dark = !app_state.read().dark;
-                        app_state.write().dark = new_dark;
-                    },
-                    title: "切换主题",
+                    onclick: move |_| theme_menu.set(true),
+                    title: "主题：{theme_label}",
                     span { style: "font-size:18px;", "{theme_icon}" }
                     if !*collapsed.read() {
-                        span { style: "font-size:13px;", "{theme_label}" }
+                        span { style: "font-size:13px; overflow:hidden; text-overflow:ellipsis; white-space:nowrap;", "{theme_label}" }
+                    }
+                }
+            }
+
+            // 主题菜单：透明遮罩接住菜单外的点击
+            if *theme_menu.read() {
+                div {
+                    style: "position:fixed; inset:0; z-index:50;",
+                    onclick: move |_| theme_menu.set(false),
+                }
+                div {
+                    class: "tb-menu",
+                    style: "left:{menu_left}px; bottom:12px;",
+                    div {
+                        class: if choice == ThemeChoice::System { "tb-menu-item on" } else { "tb-menu-item" },
+                        onclick: move |_| {
+                            app_state.write().theme = ThemeChoice::System;
+                            theme_menu.set(false);
+                        },
+                        "🖥️ 跟随系统"
+                    }
+                    div { class: "tb-menu-sep" }
+                    for (i, name) in theme_names.into_iter().enumerate() {
+                        if i == builtin_count {
+                            div { key: "user-sep", class: "tb-menu-sep" }
+                        }
+                        div {
+                            key: "{name}",
+                            class: if choice == ThemeChoice::Named(name.clone()) { "tb-menu-item on" } else { "tb-menu-item" },
+                            onclick: {
+                                let name = name.clone();
+                                move |_| {
+                                    app_state.write().theme = ThemeChoice::Named(name.clone());
+                                    theme_menu.set(false);
+                                }
+                            },
+                            "{name}"
+                        }
+                    }
+                    div { class: "tb-menu-sep" }
+                    div {
+                        class: "tb-menu-item",
+                        title: "重新读取配置目录下的 themes.toml",
+                        onclick: move |_| user_themes.set(theme::load_user_themes()),
+                        "↻ 重新加载用户主题"
+                    }
+                    if let Some(err) = theme_error {
+                        div { class: "tb-menu-note", "{err}" }
                     }
                 }
             }
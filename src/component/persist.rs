@@ -0,0 +1,240 @@
+// src/component/persist.rs
+//
+// 会话状态持久化：启动时从磁盘加载，变更后防抖写回。
+//
+// 设计要点：
+// 1. 状态文件位于系统配置目录（Linux 下为 $XDG_CONFIG_HOME/toolbox/state.json，
+//    macOS 为 ~/Library/Application Support/toolbox/state.json）。
+// 2. 文件带 `version` 字段；读取时先按 `serde_json::Value` 解析，逐版本迁移到
+//    当前结构后再反序列化，所以老版本文件不会因为结构变化而整体丢失。
+// 3. 解析 / 迁移失败时把坏文件改名为 `state.json.corrupt-<时间>` 留档，
+//    并以默认状态启动，不会因为一个坏文件导致应用无法打开。
+// 4. 写盘在后台线程完成：最后一次变更后静默 `SAVE_DEBOUNCE` 才真正写入，
+//    且先写临时文件再 rename，避免写到一半被截断。窗口关闭 / 应用退出时由 `flush`
+//    同步写入尚在静默期内的状态，最后几次编辑不会丢失。
+// 5. 各工具的输入缓冲区超过 `MAX_PERSISTED_TEXT` 时不保存（存为空串），
+//    避免大文档每次按键都整体复制并重写 state.json；会话状态由 `capped` 统一处理。
+// 6. 读写失败不打断使用：说明记录在 `take_error` 中，由界面提示。
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::component::base64::Base64Session;
+use crate::component::converter::ConverterSession;
+use crate::component::json::JsonSession;
+use crate::component::json_diff::JsonDiffSession;
+use crate::component::timestamp::TimestampSession;
+use crate::component::{AppState, SidebarState};
+
+/// 当前状态文件结构版本；结构发生不兼容变化时 +1，并在 `migrate` 中补一步迁移。
+pub const STATE_VERSION: u64 = 2;
+
+/// 最后一次变更后等待多久再写盘
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 输入缓冲区超过此大小时不保存
+pub const MAX_PERSISTED_TEXT: usize = 1024 * 1024;
+
+const APP_DIR: &str = "toolbox";
+const STATE_FILE: &str = "state.json";
+
+/// 落盘的完整状态：全局状态 + 侧栏 + 各工具的会话缓冲区。
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersistedState {
+    pub version: u64,
+    pub app: AppState,
+    pub sidebar: SidebarState,
+    pub json: JsonSession,
+    pub converter: ConverterSession,
+    pub json_diff: JsonDiffSession,
+    pub base64: Base64Session,
+    pub timestamp: TimestampSession,
+}
+
+impl Default for PersistedState {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            app: AppState::default(),
+            sidebar: SidebarState::default(),
+            json: JsonSession::default(),
+            converter: ConverterSession::default(),
+            json_diff: JsonDiffSession::default(),
+            base64: Base64Session::default(),
+            timestamp: TimestampSession::default(),
+        }
+    }
+}
+
+/// 尚未写盘的最新状态（静默期内）
+static PENDING: Mutex<Option<PersistedState>> = Mutex::new(None);
+/// 最近一次读写失败的说明，界面取走后清空
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+fn report(msg: String) {
+    if let Ok(mut e) = LAST_ERROR.lock() {
+        *e = Some(msg);
+    }
+}
+
+/// 取走最近一次读写失败的说明
+pub fn take_error() -> Option<String> {
+    LAST_ERROR.lock().ok()?.take()
+}
+
+/// 会话中的输入缓冲区：超过 `MAX_PERSISTED_TEXT` 时存为空串（不复制大文本）
+pub fn capped(text: &str) -> String {
+    if text.len() > MAX_PERSISTED_TEXT {
+        String::new()
+    } else {
+        text.to_string()
+    }
+}
+
+/// 配置目录下的文件（状态文件、用户主题文件等）
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join(APP_DIR).join(name))
+}
+
+fn state_path() -> Option<PathBuf> {
+    config_file(STATE_FILE)
+}
+
+/// 启动时加载状态；文件不存在返回默认值，损坏则留档后返回默认值。
+pub fn load() -> PersistedState {
+    let Some(path) = state_path() else {
+        return PersistedState::default();
+    };
+    let raw = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return PersistedState::default(),
+        Err(e) => {
+            report(format!("无法读取状态文件 {}: {}", path.display(), e));
+            return PersistedState::default();
+        }
+    };
+
+    let parsed = serde_json::from_str::<Value>(&raw)
+        .map_err(|e| format!("JSON 解析错误: {}", e))
+        .and_then(migrate)
+        .and_then(|v| {
+            serde_json::from_value::<PersistedState>(v).map_err(|e| format!("结构不匹配: {}", e))
+        });
+
+    match parsed {
+        Ok(mut state) => {
+            state.version = STATE_VERSION;
+            state
+        }
+        Err(msg) => {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            let backup = path.with_file_name(format!("{}.corrupt-{}", STATE_FILE, stamp));
+            report(format!(
+                "状态文件已损坏（{}），已另存为 {} 并使用默认状态",
+                msg,
+                backup.display()
+            ));
+            let _ = fs::rename(&path, &backup);
+            PersistedState::default()
+        }
+    }
+}
+
+/// 把任意历史版本的状态迁移到 `STATE_VERSION`。
+///
+/// - v0：早期直接序列化的 `AppState`（`{"current_tool":…,"dark":…}`，无 version 字段）
+/// - v1：`PersistedState`，主题为 `app.dark: bool`
+/// - v2：主题改为 `app.theme`（`ThemeChoice`）
+fn migrate(mut v: Value) -> Result<Value, String> {
+    if !v.is_object() {
+        return Err("顶层不是对象".into());
+    }
+    let mut version = v.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version > STATE_VERSION {
+        // 新版本写的文件：尽量按当前结构读取（未知字段会被忽略，缺失字段取默认）
+        return Ok(v);
+    }
+    while version < STATE_VERSION {
+        v = match version {
+            0 => migrate_v0_to_v1(v),
+            1 => migrate_v1_to_v2(v),
+            _ => unreachable!(),
+        };
+        version += 1;
+    }
+    Ok(v)
+}
+
+fn migrate_v0_to_v1(v: Value) -> Value {
+    serde_json::json!({ "version": 1, "app": v })
+}
+
+/// 原来的暗色 / 亮色开关对应同名内置主题
+fn migrate_v1_to_v2(mut v: Value) -> Value {
+    if let Some(app) = v.get_mut("app").and_then(Value::as_object_mut) {
+        let dark = app.remove("dark").and_then(|d| d.as_bool()).unwrap_or(true);
+        let name = if dark { "暗色" } else { "亮色" };
+        app.insert("theme".into(), serde_json::json!({ "Named": name }));
+    }
+    v["version"] = 2.into();
+    v
+}
+
+/// 防抖保存：最新状态放进 `PENDING` 并唤醒后台线程，线程在静默后写盘。
+pub fn save_debounced(state: PersistedState) {
+    static WAKE: OnceLock<Mutex<Sender<()>>> = OnceLock::new();
+    if let Ok(mut pending) = PENDING.lock() {
+        *pending = Some(state);
+    }
+    let wake = WAKE.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<()>();
+        thread::spawn(move || {
+            while rx.recv().is_ok() {
+                // 静默期内持续有变更就继续等
+                while rx.recv_timeout(SAVE_DEBOUNCE).is_ok() {}
+                flush();
+            }
+        });
+        Mutex::new(tx)
+    });
+    if let Ok(tx) = wake.lock() {
+        let _ = tx.send(());
+    }
+}
+
+/// 立即写入尚未落盘的状态（窗口关闭 / 应用退出时调用）
+pub fn flush() {
+    // 持锁写盘：后台线程与关闭时的调用不会同时写
+    let Ok(mut pending) = PENDING.lock() else {
+        return;
+    };
+    if let Some(state) = pending.take() {
+        write_now(&state);
+    }
+}
+
+fn write_now(state: &PersistedState) {
+    let Some(path) = state_path() else {
+        return;
+    };
+    let result = (|| -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let data = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, &path)
+    })();
+    if let Err(e) = result {
+        report(format!("保存状态失败 {}: {}", path.display(), e));
+    }
+}
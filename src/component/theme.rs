@@ -0,0 +1,361 @@
+// src/component/theme.rs
+//
+// 配色主题：内置 暗色 / 亮色 / Solarized / Monokai / 高对比度，外加用户主题文件，以及“跟随系统”。
+//
+// 设计要点：
+// 1. 主题 = 基础明暗 + 覆盖的 CSS 变量。应用时先设置 <html data-theme="dark|light">，
+//    让 GLOBAL_CSS 里对应的整套变量生效，再把覆盖变量写到 <html> 的内联 style 上，
+//    因此一个主题只需列出与基础不同的变量（`bg-app`、`syn-key`、`match-bg` 等，不带 `--`）。
+// 2. 内置主题与用户主题使用同一种 TOML 格式，内置的写在 `BUILTIN_THEMES` 里，也可当作示例：
+//
+//        [[theme]]
+//        name = "Nord"
+//        base = "dark"
+//        [theme.colors]
+//        bg-app = "#2e3440"
+//        syn-key = "#88c0d0"
+//
+// 3. 用户主题文件为配置目录下的 `themes.toml`（与 state.json 同目录），启动时读取一次，
+//    可在主题菜单中重新加载。与内置主题同名的以内置为准。
+// 4. “跟随系统”按 prefers-color-scheme 在暗色 / 亮色间切换，并监听系统设置变化。
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::component::persist;
+
+const THEMES_FILE: &str = "themes.toml";
+
+/// 主题选择（持久化在 AppState 中）
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum ThemeChoice {
+    /// 跟随系统明暗
+    #[default]
+    System,
+    /// 按名称选择内置或用户主题
+    Named(String),
+}
+
+/// 基础明暗：决定 GLOBAL_CSS 中哪一套变量作为底色
+#[derive(Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Base {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Base {
+    fn attr(self) -> &'static str {
+        match self {
+            Base::Dark => "dark",
+            Base::Light => "light",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    #[serde(default)]
+    pub base: Base,
+    /// CSS 变量名（不带 `--`）-> 值
+    #[serde(default)]
+    pub colors: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct ThemeFile {
+    #[serde(default)]
+    theme: Vec<Theme>,
+}
+
+/// 用户主题文件的加载结果；`error` 为读取 / 解析失败的说明
+#[derive(Clone, PartialEq, Default)]
+pub struct UserThemes {
+    pub themes: Vec<Theme>,
+    pub error: Option<String>,
+}
+
+const BUILTIN_THEMES: &str = r##"
+[[theme]]
+name = "暗色"
+base = "dark"
+
+[[theme]]
+name = "亮色"
+base = "light"
+
+[[theme]]
+name = "Solarized Dark"
+base = "dark"
+[theme.colors]
+bg-app = "#002b36"
+bg-panel = "#002b36"
+bg-header = "#073642"
+bg-input = "#00212b"
+bg-sidebar = "#00252e"
+bg-card = "#073642"
+border = "#0f4b5a"
+border-soft = "#073642"
+text = "#839496"
+text-dim = "#586e75"
+text-bright = "#93a1a1"
+accent = "#268bd2"
+accent-hover = "#3a9ad9"
+accent-soft = "rgba(38,139,210,.18)"
+bg-btn = "#073642"
+bg-btn-hover = "#0b4452"
+border-btn = "#0f4b5a"
+border-btn-hover = "#1c5c6b"
+bg-hover = "#06303b"
+bg-input-2 = "#073642"
+placeholder = "#4a626a"
+scrollbar = "#0f4b5a"
+scrollbar-hover = "#1c5c6b"
+handle-1 = "#073642"
+handle-2 = "#0b3d49"
+syn-key = "#268bd2"
+syn-string = "#2aa198"
+syn-number = "#d33682"
+syn-keyword = "#859900"
+syn-comment = "#586e75"
+syn-tag = "#b58900"
+syn-attr = "#cb4b16"
+match-bg = "rgba(181,137,0,.35)"
+match-current-bg = "#b58900"
+match-current-fg = "#002b36"
+
+[[theme]]
+name = "Solarized Light"
+base = "light"
+[theme.colors]
+bg-app = "#fdf6e3"
+bg-panel = "#fdf6e3"
+bg-header = "#eee8d5"
+bg-input = "#fffbee"
+bg-sidebar = "#f5efdc"
+bg-card = "#fdf6e3"
+border = "#ddd6c1"
+border-soft = "#eee8d5"
+text = "#657b83"
+text-dim = "#93a1a1"
+text-bright = "#073642"
+accent = "#268bd2"
+accent-hover = "#1f76b5"
+accent-soft = "rgba(38,139,210,.14)"
+bg-btn = "#fdf6e3"
+bg-btn-hover = "#eee8d5"
+border-btn = "#d9d2bd"
+border-btn-hover = "#c5bea8"
+bg-hover = "#eee8d5"
+bg-input-2 = "#fffbee"
+placeholder = "#a9b3b3"
+scrollbar = "#d9d2bd"
+scrollbar-hover = "#c5bea8"
+handle-1 = "#eee8d5"
+handle-2 = "#e4dec9"
+syn-key = "#268bd2"
+syn-string = "#2aa198"
+syn-number = "#d33682"
+syn-keyword = "#859900"
+syn-comment = "#93a1a1"
+syn-tag = "#b58900"
+syn-attr = "#cb4b16"
+match-bg = "rgba(181,137,0,.25)"
+match-current-bg = "#b58900"
+match-current-fg = "#fdf6e3"
+
+[[theme]]
+name = "Monokai"
+base = "dark"
+[theme.colors]
+bg-app = "#272822"
+bg-panel = "#272822"
+bg-header = "#1e1f1c"
+bg-input = "#1e1f1a"
+bg-sidebar = "#22231e"
+bg-card = "#2f302a"
+border = "#3e3d32"
+border-soft = "#34352d"
+text = "#f8f8f2"
+text-dim = "#90908a"
+text-bright = "#ffffff"
+accent = "#66d9ef"
+accent-hover = "#86e1f2"
+accent-soft = "rgba(102,217,239,.16)"
+text-on-accent = "#272822"
+bg-btn = "#3e3d32"
+bg-btn-hover = "#49483e"
+border-btn = "#49483e"
+border-btn-hover = "#75715e"
+bg-hover = "#3e3d32"
+bg-input-2 = "#1e1f1c"
+placeholder = "#75715e"
+scrollbar = "#49483e"
+scrollbar-hover = "#75715e"
+handle-1 = "#2f302a"
+handle-2 = "#3e3d32"
+syn-key = "#a6e22e"
+syn-string = "#e6db74"
+syn-number = "#ae81ff"
+syn-keyword = "#f92672"
+syn-comment = "#75715e"
+syn-tag = "#f92672"
+syn-attr = "#fd971f"
+match-bg = "rgba(230,219,116,.30)"
+match-current-bg = "#e6db74"
+match-current-fg = "#272822"
+
+[[theme]]
+name = "高对比度"
+base = "dark"
+[theme.colors]
+bg-app = "#000000"
+bg-panel = "#000000"
+bg-header = "#0a0a0a"
+bg-input = "#000000"
+bg-sidebar = "#000000"
+bg-card = "#0a0a0a"
+border = "#6fc3df"
+border-soft = "#3b6f80"
+text = "#ffffff"
+text-dim = "#d0d0d0"
+text-bright = "#ffffff"
+accent = "#f38518"
+accent-hover = "#ffa040"
+accent-soft = "rgba(243,133,24,.25)"
+text-on-accent = "#000000"
+bg-btn = "#000000"
+bg-btn-hover = "#1a1a1a"
+border-btn = "#6fc3df"
+border-btn-hover = "#f38518"
+bg-hover = "#1a1a1a"
+bg-input-2 = "#000000"
+placeholder = "#a0a0a0"
+scrollbar = "#6fc3df"
+scrollbar-hover = "#f38518"
+handle-1 = "#000000"
+handle-2 = "#3b6f80"
+syn-comment = "#7ca668"
+match-bg = "rgba(255,255,0,.35)"
+match-current-bg = "#ffff00"
+match-current-fg = "#000000"
+"##;
+
+/// 内置主题（第一个为默认暗色）
+pub fn builtin() -> &'static [Theme] {
+    static THEMES: OnceLock<Vec<Theme>> = OnceLock::new();
+    THEMES.get_or_init(|| {
+        toml::from_str::<ThemeFile>(BUILTIN_THEMES)
+            .map(|f| f.theme)
+            .unwrap_or_default()
+    })
+}
+
+/// 读取用户主题文件；文件不存在时为空且无错误
+pub fn load_user_themes() -> UserThemes {
+    let Some(path) = persist::config_file(THEMES_FILE) else {
+        return UserThemes::default();
+    };
+    let raw = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return UserThemes::default(),
+        Err(e) => {
+            return UserThemes {
+                themes: Vec::new(),
+                error: Some(format!("无法读取 {}: {}", path.display(), e)),
+            }
+        }
+    };
+    match toml::from_str::<ThemeFile>(&raw) {
+        Ok(f) => {
+            let mut themes: Vec<Theme> = Vec::new();
+            for t in f.theme {
+                let taken = builtin().iter().chain(&themes).any(|b| b.name == t.name);
+                if !t.name.is_empty() && !taken {
+                    themes.push(t);
+                }
+            }
+            UserThemes {
+                themes,
+                error: None,
+            }
+        }
+        Err(e) => UserThemes {
+            themes: Vec::new(),
+            error: Some(format!("{} 解析错误: {}", THEMES_FILE, e.message())),
+        },
+    }
+}
+
+/// 按名称查找主题（已删除的用户主题找不到）
+fn find<'a>(name: &str, user: &'a [Theme]) -> Option<&'a Theme> {
+    builtin().iter().chain(user).find(|t| t.name == name)
+}
+
+/// 当前选择对应的基础明暗（跟随系统时为 None）
+pub fn base_of(choice: &ThemeChoice, user: &[Theme]) -> Option<Base> {
+    match choice {
+        ThemeChoice::System => None,
+        ThemeChoice::Named(name) => Some(find(name, user).map_or(Base::Dark, |t| t.base)),
+    }
+}
+
+/// 把主题应用到页面
+pub fn apply(choice: &ThemeChoice, user: &[Theme]) {
+    // 先撤掉上一次的系统监听与覆盖变量
+    let mut js = String::from(
+        "var d=document.documentElement;d.style.cssText='';var m=window.matchMedia('(prefers-color-scheme: dark)');if(window.__tbThemeMq){m.removeEventListener('change',window.__tbThemeMq);window.__tbThemeMq=null;}",
+    );
+    match choice {
+        ThemeChoice::System => js.push_str(
+            "var f=function(){d.setAttribute('data-theme',m.matches?'dark':'light');};window.__tbThemeMq=f;m.addEventListener('change',f);f();",
+        ),
+        ThemeChoice::Named(name) => {
+            // 找不到时退回默认主题；内置主题也没有时只用暗色基础样式
+            let theme = find(name, user).or_else(|| builtin().first());
+            js.push_str(&format!(
+                "d.setAttribute('data-theme','{}');",
+                theme.map_or(Base::Dark, |t| t.base).attr()
+            ));
+            for (key, value) in theme.iter().flat_map(|t| &t.colors) {
+                if !is_var_name(key) {
+                    continue;
+                }
+                let value = serde_json::to_string(value).unwrap_or_else(|_| "\"\"".into());
+                js.push_str(&format!("d.style.setProperty('--{}',{});", key, value));
+            }
+        }
+    }
+    dioxus::document::eval(&js);
+}
+
+/// 变量名只允许小写字母、数字与 `-`（拼进脚本里，不能带引号等字符）
+fn is_var_name(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_themes_parse() {
+        let themes = toml::from_str::<ThemeFile>(BUILTIN_THEMES)
+            .map(|f| f.theme)
+            .unwrap_or_else(|e| panic!("{}", e.message()));
+        assert_eq!(themes.len(), builtin().len());
+        assert!(themes[0].base == Base::Dark);
+        for (i, t) in themes.iter().enumerate() {
+            assert!(!t.name.is_empty());
+            assert!(themes[..i].iter().all(|p| p.name != t.name), "{}", t.name);
+            assert!(t.colors.keys().all(|k| is_var_name(k)), "{}", t.name);
+        }
+    }
+}
@@ -1,22 +1,53 @@
 use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 跨重启保留的会话状态（首次启动默认填入当前时间）
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimestampSession {
+    ts_input: String,
+    dt_input: String,
+}
+impl Default for TimestampSession {
+    fn default() -> Self {
+        Self {
+            ts_input: current_unix_seconds_string(),
+            dt_input: current_local_datetime_string(),
+        }
+    }
+}
 
 #[component]
 pub fn TimestampTool() -> Element {
+    let mut session = use_context::<Signal<TimestampSession>>();
+    let restored = session.peek().clone();
+
     // --- 状态 ---
     // 上条：时间戳 -> 日期时间
-    let mut ts_input = use_signal(current_unix_seconds_string);
+    let mut ts_input = use_signal(|| restored.ts_input.clone());
     let mut dt_output_utc = use_signal(String::new);
     let mut dt_output_loc = use_signal(String::new);
 
     // 下条：日期时间 -> 时间戳
-    let mut dt_input = use_signal(current_local_datetime_string);
+    let mut dt_input = use_signal(|| restored.dt_input.clone());
     let mut ts_output_sec = use_signal(String::new);
     let mut ts_output_ms = use_signal(String::new);
 
     // 通用错误
     let mut error_message = use_signal(String::new);
 
+    // 写回会话状态（由 App 防抖落盘）
+    use_effect(move || {
+        let next = TimestampSession {
+            ts_input: ts_input.read().clone(),
+            dt_input: dt_input.read().clone(),
+        };
+        if *session.peek() != next {
+            session.set(next);
+        }
+    });
+
     // 固定高度（结果框）
     const BOX_H: i32 = 34;
 
@@ -0,0 +1,80 @@
+// src/component/util/highlight/http.rs
+//
+// HTTP 报文分词：请求行（方法 / 目标 / 版本）或状态行（版本 / 状态码）、`Name: value` 头部，
+// 空行之后的正文按内容交给 JSON / XML 分词器。
+
+use super::{
+    json::JsonTokenizer, scan_while, xml::XmlTokenizer, Token, TokenKind, Tokenizer, Tokens,
+};
+
+const METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "CONNECT", "TRACE",
+];
+
+pub struct HttpTokenizer;
+
+impl Tokenizer for HttpTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut out = Tokens::default();
+        let mut base = 0;
+        let mut first = true;
+        for raw in text.split_inclusive('\n') {
+            let line = raw.trim_end_matches(['\n', '\r']);
+            if first {
+                start_line(line, &mut out);
+                first = false;
+            } else if line.is_empty() {
+                base += raw.len();
+                body(&text[base..], base, &mut out);
+                break;
+            } else if let Some(colon) = line.find(':') {
+                out.push(base..base + colon, TokenKind::Key);
+            }
+            base += raw.len();
+        }
+        out.0
+    }
+}
+
+/// 请求行 `GET /path HTTP/1.1` 或状态行 `HTTP/1.1 200 OK`
+pub fn is_start_line(line: &str) -> bool {
+    let mut parts = line.split(' ');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(v), Some(code), _) if v.starts_with("HTTP/") => {
+            code.len() == 3 && code.bytes().all(|b| b.is_ascii_digit())
+        }
+        (Some(m), Some(_), Some(v)) => METHODS.contains(&m) && v.starts_with("HTTP/"),
+        _ => false,
+    }
+}
+
+fn start_line(line: &str, out: &mut Tokens) {
+    if line.starts_with("HTTP/") {
+        let v = scan_while(line, 0, line.len(), |b| b != b' ');
+        out.push(0..v, TokenKind::Keyword);
+        let code_start = (v + 1).min(line.len());
+        let code = scan_while(line, code_start, line.len(), |b| b.is_ascii_digit());
+        out.push(code_start..code, TokenKind::Number);
+        return;
+    }
+    let m = scan_while(line, 0, line.len(), |b| b != b' ');
+    out.push(0..m, TokenKind::Keyword);
+    if let Some(v) = line.rfind(" HTTP/") {
+        out.push((m + 1).min(v)..v, TokenKind::String);
+        out.push(v + 1..line.len(), TokenKind::Keyword);
+    }
+}
+
+/// 正文：看起来是 JSON / XML 时借用对应分词器
+fn body(text: &str, base: usize, out: &mut Tokens) {
+    let lead = text.len() - text.trim_start().len();
+    let t = &text[lead..];
+    let tokens = if t.starts_with(['{', '[']) {
+        JsonTokenizer::default().tokenize(t)
+    } else if t.starts_with('<') {
+        XmlTokenizer.tokenize(t)
+    } else {
+        return;
+    };
+    out.extend_at(base + lead, tokens);
+}
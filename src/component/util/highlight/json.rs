@@ -0,0 +1,121 @@
+// src/component/util/highlight/json.rs
+//
+// JSON 分词：字节级状态机区分 key 与字符串值；数字与 true / false / null 单独着色。
+
+use super::{Token, TokenKind, Tokenizer, Tokens};
+
+/// 高亮状态机在某一位置的上下文：容器栈 + 下一个字符串是否为 key。
+///
+/// 超大文档只高亮可视窗口时，先用它把状态推进到窗口起点，
+/// 窗口内 key / value 的着色就与整篇高亮一致。
+#[derive(Clone, Default, PartialEq)]
+pub struct JsonScanState {
+    stack: Vec<bool>,
+    key_context: bool,
+    in_string: bool,
+    string_is_key: bool,
+    escape: bool,
+}
+
+impl JsonScanState {
+    /// 推进一个字节。JSON 的结构字符都是 ASCII，多字节字符的后续字节不会被误认。
+    pub fn step(&mut self, b: u8) {
+        if self.in_string {
+            if self.escape {
+                self.escape = false;
+            } else if b == b'\\' {
+                self.escape = true;
+            } else if b == b'"' {
+                self.in_string = false;
+                if self.string_is_key {
+                    self.key_context = false;
+                }
+            }
+            return;
+        }
+        match b {
+            b'"' => {
+                self.in_string = true;
+                self.string_is_key = self.key_context;
+            }
+            b'{' => {
+                self.stack.push(true);
+                self.key_context = true;
+            }
+            b'[' => {
+                self.stack.push(false);
+                self.key_context = false;
+            }
+            b',' => self.key_context = self.stack.last().copied().unwrap_or(true),
+            b':' => self.key_context = false,
+            b'}' | b']' => {
+                self.stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn advance(&mut self, text: &str) {
+        for &b in text.as_bytes() {
+            self.step(b);
+        }
+    }
+
+    pub fn in_string(&self) -> bool {
+        self.in_string
+    }
+}
+
+/// JSON 分词器；`state` 为文本开头处的上下文（整篇高亮时为默认值）
+#[derive(Default)]
+pub struct JsonTokenizer {
+    pub state: JsonScanState,
+}
+
+impl Tokenizer for JsonTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let bytes = text.as_bytes();
+        let mut st = self.state.clone();
+        let mut out = Tokens::default();
+        let string_kind = |is_key: bool| {
+            if is_key {
+                TokenKind::Key
+            } else {
+                TokenKind::String
+            }
+        };
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+            // 字符串内（含结束引号）：多字节字符的后续字节不会是引号或反斜杠
+            if st.in_string {
+                let kind = string_kind(st.string_is_key);
+                st.step(b);
+                out.push(i..i + 1, kind);
+                i += 1;
+                continue;
+            }
+            let keyword = [&b"true"[..], b"false", b"null"]
+                .into_iter()
+                .find(|w| bytes[i..].starts_with(w));
+            match b {
+                b'"' => {
+                    st.step(b);
+                    out.push(i..i + 1, string_kind(st.string_is_key));
+                }
+                _ if keyword.is_some() => {
+                    let len = keyword.map_or(1, <[u8]>::len);
+                    out.push(i..i + len, TokenKind::Keyword);
+                    i += len;
+                    continue;
+                }
+                b if b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E') => {
+                    out.push(i..i + 1, TokenKind::Number);
+                }
+                _ => st.step(b),
+            }
+            i += 1;
+        }
+        out.0
+    }
+}
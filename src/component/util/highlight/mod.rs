@@ -0,0 +1,358 @@
+// src/component/util/highlight/mod.rs
+//
+// 语法高亮 + 搜索高亮，按语言插拔分词器（JSON / YAML / TOML / XML·HTML / SQL / HTTP）。
+//
+// 设计要点（修复旧版在已高亮 HTML 上做 find/replace 导致结构被破坏的 bug）：
+// 1. 先把“搜索匹配”转成**字节级标记数组**（0=无 / 1=弱 / 2=强=当前匹配）。
+//    匹配区间来自纯文本上的字节位置，先扩到字素簇边界（见 util::offset），
+//    因此一个字符的所有字节标记一致，取首字节标记即可代表整字符。
+// 2. 分词器（`Tokenizer`）把纯文本切成带语法类别的字节区间；各语言只负责这一步，
+//    不接触 HTML，也不关心搜索。
+// 3. 逐字符遍历，按所在 token 取语法类别，用 SegmentBuilder 把相邻“同(语法类别, 搜索标记)”
+//    的字符合并为一段，每段输出一个带 class 的 span，两者不再互相破坏。
+// 4. 只输出 class（`hl-key`、`hl-match` 等），颜色由 GLOBAL_CSS 里的 `--syn-*` / `--match-*`
+//    变量决定，随主题切换。
+
+mod http;
+mod json;
+mod sql;
+mod toml;
+mod xml;
+mod yaml;
+
+pub use json::{JsonScanState, JsonTokenizer};
+
+use super::offset::snap_to_graphemes;
+use std::ops::Range;
+
+/// 语法类别；不在任何 token 内的文本按默认色显示
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenKind {
+    /// 对象 key、YAML / TOML 的键、HTTP 头名、SQL 的带引号标识符
+    Key,
+    String,
+    Number,
+    /// 字面量（true / null）、SQL 关键字、HTTP 方法与版本、文档标记
+    Keyword,
+    Comment,
+    /// XML 标签名、TOML 表头
+    Tag,
+    /// XML 属性名
+    Attr,
+}
+
+impl TokenKind {
+    fn class(self) -> &'static str {
+        match self {
+            TokenKind::Key => "hl-key",
+            TokenKind::String => "hl-string",
+            TokenKind::Number => "hl-number",
+            TokenKind::Keyword => "hl-keyword",
+            TokenKind::Comment => "hl-comment",
+            TokenKind::Tag => "hl-tag",
+            TokenKind::Attr => "hl-attr",
+        }
+    }
+}
+
+/// 一个语法片段（字节区间）
+#[derive(Clone, PartialEq, Debug)]
+pub struct Token {
+    pub range: Range<usize>,
+    pub kind: TokenKind,
+}
+
+/// 分词器：输出按位置升序、互不重叠的 token
+pub trait Tokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token>;
+}
+
+/// 收集 token；相邻同类的合并为一个
+#[derive(Default)]
+struct Tokens(Vec<Token>);
+
+impl Tokens {
+    fn push(&mut self, range: Range<usize>, kind: TokenKind) {
+        if range.is_empty() {
+            return;
+        }
+        if let Some(last) = self.0.last_mut() {
+            if last.kind == kind && last.range.end == range.start {
+                last.range.end = range.end;
+                return;
+            }
+        }
+        self.0.push(Token { range, kind });
+    }
+
+    /// 把以 `base` 为起点的子文本的 token 并入
+    fn extend_at(&mut self, base: usize, tokens: Vec<Token>) {
+        for t in tokens {
+            self.push(base + t.range.start..base + t.range.end, t.kind);
+        }
+    }
+}
+
+/// 支持的语言
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lang {
+    Json,
+    Yaml,
+    Toml,
+    Xml,
+    Sql,
+    Http,
+    Plain,
+}
+
+impl Lang {
+    pub fn tokenize(self, text: &str) -> Vec<Token> {
+        match self {
+            Lang::Json => JsonTokenizer::default().tokenize(text),
+            Lang::Yaml => yaml::YamlTokenizer.tokenize(text),
+            Lang::Toml => toml::TomlTokenizer.tokenize(text),
+            Lang::Xml => xml::XmlTokenizer.tokenize(text),
+            Lang::Sql => sql::SqlTokenizer.tokenize(text),
+            Lang::Http => http::HttpTokenizer.tokenize(text),
+            Lang::Plain => Vec::new(),
+        }
+    }
+
+    /// 按内容粗略判断语言（用于来源不确定的文本，如解码结果）
+    pub fn detect(text: &str) -> Lang {
+        let t = text.trim_start();
+        let first_line = t.lines().next().unwrap_or("").trim_end();
+        if t.starts_with(['{', '[']) && serde_json::from_str::<serde_json::Value>(t).is_ok() {
+            Lang::Json
+        } else if t.starts_with('<') {
+            Lang::Xml
+        } else if http::is_start_line(first_line) {
+            Lang::Http
+        } else if sql::looks_like_sql(t) {
+            Lang::Sql
+        } else if first_line.starts_with('[') && first_line.ends_with(']')
+            || first_line.contains(" = ")
+        {
+            Lang::Toml
+        } else if first_line == "---" || first_line.starts_with("- ") || first_line.contains(": ") {
+            Lang::Yaml
+        } else {
+            Lang::Plain
+        }
+    }
+}
+
+/// 语法高亮 + 搜索高亮。
+///
+/// - `text`: 纯文本
+/// - `tokenizer`: 所用语言的分词器
+/// - `matches`: 每处匹配的**字节区间**（正则匹配长度各不相同）
+/// - `current_idx`: 当前匹配在 `matches` 中的索引（用加强色高亮）
+pub fn highlight_with(
+    text: &str,
+    tokenizer: &dyn Tokenizer,
+    matches: &[Range<usize>],
+    current_idx: usize,
+) -> String {
+    render(text, &tokenizer.tokenize(text), matches, current_idx)
+}
+
+/// 按语言高亮
+pub fn highlight(text: &str, lang: Lang, matches: &[Range<usize>], current_idx: usize) -> String {
+    render(text, &lang.tokenize(text), matches, current_idx)
+}
+
+/// JSON 高亮（`json` 为 pretty 后的纯文本）
+pub fn highlight_json_with_search(
+    json: &str,
+    matches: &[Range<usize>],
+    current_idx: usize,
+) -> String {
+    highlight_json_from(&JsonScanState::default(), json, matches, current_idx)
+}
+
+/// 与 [`highlight_json_with_search`] 相同，但从给定状态开始（`json` 须从字符串外的位置开始）。
+pub fn highlight_json_from(
+    state: &JsonScanState,
+    json: &str,
+    matches: &[Range<usize>],
+    current_idx: usize,
+) -> String {
+    let tokenizer = JsonTokenizer {
+        state: state.clone(),
+    };
+    highlight_with(json, &tokenizer, matches, current_idx)
+}
+
+fn render(text: &str, tokens: &[Token], matches: &[Range<usize>], current_idx: usize) -> String {
+    // 1. 字节级匹配标记
+    let n = text.len();
+    let mut mark = vec![0u8; n];
+    for (i, m) in matches.iter().enumerate() {
+        if m.start < m.end && m.end <= n {
+            let val = if i == current_idx { 2 } else { 1 };
+            // 扩到字素簇边界：组合符、emoji ZWJ 序列不会被拆进两个 span
+            for b in &mut mark[snap_to_graphemes(text, m.clone())] {
+                *b = val.max(*b);
+            }
+        }
+    }
+
+    // 2. 逐字符取所在 token 的语法类别 -> 段
+    let mut builder = SegmentBuilder::new();
+    let mut t = 0;
+    for (byte_pos, ch) in text.char_indices() {
+        while t < tokens.len() && tokens[t].range.end <= byte_pos {
+            t += 1;
+        }
+        let kind = tokens
+            .get(t)
+            .filter(|tok| tok.range.start <= byte_pos)
+            .map(|tok| tok.kind);
+        builder.push(kind, mark[byte_pos], ch);
+    }
+
+    builder.finish()
+}
+
+// ============== 分词辅助 ==============
+
+/// 从 `start`（引号处）起的带引号字符串的结束位置（含结束引号）；到 `limit` 仍未闭合时返回 `limit`。
+/// `escapes` 为 true 时反斜杠转义下一个字符
+fn quoted_end(text: &str, start: usize, limit: usize, escapes: bool) -> usize {
+    let bytes = text.as_bytes();
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < limit {
+        match bytes[i] {
+            b'\\' if escapes => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    limit
+}
+
+/// 从 `start` 起满足 `pred` 的最长 ASCII 片段的结束位置
+fn scan_while(text: &str, start: usize, limit: usize, pred: impl Fn(u8) -> bool) -> usize {
+    let bytes = text.as_bytes();
+    let mut i = start;
+    while i < limit && pred(bytes[i]) {
+        i += 1;
+    }
+    i
+}
+
+/// 未加引号的标量：数字 / 字面量 / 其它
+fn scalar_kind(word: &str) -> Option<TokenKind> {
+    let w = word.trim();
+    if w.is_empty() {
+        None
+    } else if matches!(
+        w,
+        "true" | "false" | "null" | "True" | "False" | "Null" | "TRUE" | "FALSE" | "NULL" | "~"
+    ) {
+        Some(TokenKind::Keyword)
+    } else if looks_numeric(w) {
+        Some(TokenKind::Number)
+    } else {
+        None
+    }
+}
+
+/// 整数、小数、指数、十六进制 / 八进制 / 二进制、inf / nan（YAML 的 .inf 也算）
+fn looks_numeric(w: &str) -> bool {
+    let body = w.trim_start_matches(['+', '-']);
+    if matches!(body, "inf" | "nan" | ".inf" | ".nan" | ".Inf" | ".NaN") {
+        return true;
+    }
+    if let Some(rest) = body
+        .strip_prefix("0x")
+        .or_else(|| body.strip_prefix("0o"))
+        .or_else(|| body.strip_prefix("0b"))
+    {
+        return !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_hexdigit() || b == b'_');
+    }
+    body.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && body.replace('_', "").parse::<f64>().is_ok()
+}
+
+// ============== 输出 ==============
+
+/// 一个连续的、同(语法类别, 搜索标记)的文本段（已 HTML 转义）。
+struct Segment {
+    kind: Option<TokenKind>,
+    search: u8, // 0 / 1 / 2
+    text: String,
+}
+
+struct SegmentBuilder {
+    segments: Vec<Segment>,
+    cur_kind: Option<TokenKind>,
+    cur_search: u8,
+    cur_text: String,
+}
+
+impl SegmentBuilder {
+    fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            cur_kind: None,
+            cur_search: 0,
+            cur_text: String::new(),
+        }
+    }
+
+    fn push(&mut self, kind: Option<TokenKind>, search: u8, ch: char) {
+        if kind != self.cur_kind || search != self.cur_search {
+            self.flush();
+            self.cur_kind = kind;
+            self.cur_search = search;
+        }
+        match ch {
+            '<' => self.cur_text.push_str("&lt;"),
+            '>' => self.cur_text.push_str("&gt;"),
+            '&' => self.cur_text.push_str("&amp;"),
+            '"' => self.cur_text.push_str("&quot;"),
+            _ => self.cur_text.push(ch),
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.cur_text.is_empty() {
+            self.segments.push(Segment {
+                kind: self.cur_kind,
+                search: self.cur_search,
+                text: std::mem::take(&mut self.cur_text),
+            });
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        let mut html = String::with_capacity(self.segments.len() * 24);
+        for seg in &self.segments {
+            let syntax = seg.kind.map(TokenKind::class);
+            let class = match (seg.search, syntax) {
+                // 当前匹配：最醒目的配色（覆盖语法色）
+                (2, _) => Some("hl-current".to_string()),
+                // 其它匹配：半透明底色，保留语法色
+                (1, Some(c)) => Some(format!("hl-match {}", c)),
+                (1, None) => Some("hl-match".to_string()),
+                // 无搜索：仅语法色
+                (_, c) => c.map(str::to_string),
+            };
+            match class {
+                Some(c) => {
+                    html.push_str("<span class=\"");
+                    html.push_str(&c);
+                    html.push_str("\">");
+                    html.push_str(&seg.text);
+                    html.push_str("</span>");
+                }
+                None => html.push_str(&seg.text),
+            }
+        }
+        html
+    }
+}
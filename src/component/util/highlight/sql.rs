@@ -0,0 +1,112 @@
+// src/component/util/highlight/sql.rs
+//
+// SQL 分词：`--` / `/* */` 注释、`'...'` 字符串（`''` 转义）、`"..."` / 反引号标识符、数字、
+// 关键字（大小写不敏感，覆盖常见 DML / DDL / 函数名即可，不区分方言）。
+
+use super::{looks_numeric, scan_while, Token, TokenKind, Tokenizer, Tokens};
+
+/// 空白分隔
+const KEYWORDS: &str = "\
+    add all alter and as asc avg begin between by case cast commit count create cross \
+    database default delete desc distinct drop else end exists false foreign from full \
+    group having if in index inner insert into is join key left like limit max min not null \
+    offset on or order outer primary references returning right rollback select set sum \
+    table then true union unique update values view when where with";
+
+pub struct SqlTokenizer;
+
+impl Tokenizer for SqlTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let bytes = text.as_bytes();
+        let end = text.len();
+        let mut out = Tokens::default();
+        let mut i = 0;
+        while i < end {
+            let rest = &text[i..];
+            match bytes[i] {
+                b'-' if rest.starts_with("--") => {
+                    let e = rest.find('\n').map_or(end, |p| i + p);
+                    out.push(i..e, TokenKind::Comment);
+                    i = e;
+                }
+                b'/' if rest.starts_with("/*") => {
+                    let e = rest[2..].find("*/").map_or(end, |p| i + 2 + p + 2);
+                    out.push(i..e, TokenKind::Comment);
+                    i = e;
+                }
+                b'\'' => {
+                    let e = string_end(text, i);
+                    out.push(i..e, TokenKind::String);
+                    i = e;
+                }
+                b'"' | b'`' => {
+                    let e = rest[1..]
+                        .find(bytes[i] as char)
+                        .map_or(end, |p| i + 1 + p + 1);
+                    out.push(i..e, TokenKind::Key);
+                    i = e;
+                }
+                b if is_word_byte(b) => {
+                    let e = scan_while(text, i, end, is_word_byte);
+                    let word = &text[i..e];
+                    if looks_numeric(word) {
+                        out.push(i..e, TokenKind::Number);
+                    } else if is_keyword(word) {
+                        out.push(i..e, TokenKind::Keyword);
+                    }
+                    i = e;
+                }
+                _ => i += rest.chars().next().map_or(1, char::len_utf8),
+            }
+        }
+        out.0
+    }
+}
+
+/// `'...'` 的结束位置；`''` 是转义的单引号
+fn string_end(text: &str, start: usize) -> usize {
+    let bytes = text.as_bytes();
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if bytes[i] == b'\'' {
+            if bytes.get(i + 1) == Some(&b'\'') {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'.'
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS
+        .split_whitespace()
+        .any(|k| k.eq_ignore_ascii_case(word))
+}
+
+/// 以常见语句开头（`SELECT ... FROM`、`INSERT INTO`、`UPDATE ... SET` 等）
+pub fn looks_like_sql(text: &str) -> bool {
+    let words: Vec<&str> = text
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .collect();
+    let first = words
+        .first()
+        .map_or(String::new(), |w| w.to_ascii_lowercase());
+    let has = |kw: &str| words.iter().any(|w| w.eq_ignore_ascii_case(kw));
+    match first.as_str() {
+        "select" => has("from"),
+        "insert" => has("into"),
+        "update" => has("set"),
+        "delete" => has("from"),
+        "create" | "drop" | "alter" => has("table") || has("index") || has("view"),
+        "with" => has("as") && has("select"),
+        _ => false,
+    }
+}
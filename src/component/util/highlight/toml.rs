@@ -0,0 +1,121 @@
+// src/component/util/highlight/toml.rs
+//
+// TOML 分词：注释、`[表]` / `[[表数组]]` 表头、`key =`（含点号与引号 key）、
+// 四种字符串（含跨行的 `"""` / `'''`）、数字与日期、布尔，以及内联表里的 key。
+
+use super::{quoted_end, scalar_kind, scan_while, Token, TokenKind, Tokenizer, Tokens};
+
+pub struct TomlTokenizer;
+
+impl Tokenizer for TomlTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let bytes = text.as_bytes();
+        let end = text.len();
+        let mut out = Tokens::default();
+        // 行首（跳过空白后）处于 key 位置：表头或 `key = value`
+        let mut line_start = true;
+        // 内联表 `{ a = 1 }` 中逗号后又回到 key 位置；跨行数组里换行后仍是值
+        let mut depth_inline = 0usize;
+        let mut depth_array = 0usize;
+        let mut expect_key = true;
+        let mut i = 0;
+        while i < end {
+            let b = bytes[i];
+            match b {
+                b'\n' => {
+                    line_start = true;
+                    expect_key = depth_inline == 0 && depth_array == 0;
+                    i += 1;
+                }
+                b' ' | b'\t' | b'\r' => i += 1,
+                b'#' => {
+                    let e = text[i..].find('\n').map_or(end, |p| i + p);
+                    out.push(i..e, TokenKind::Comment);
+                    i = e;
+                }
+                b'[' if line_start && depth_inline == 0 && depth_array == 0 => {
+                    let e = text[i..].find('\n').map_or(end, |p| i + p);
+                    let close = text[i..e].rfind(']').map_or(e, |p| i + p + 1);
+                    out.push(i..close, TokenKind::Tag);
+                    i = close;
+                    line_start = false;
+                    expect_key = false;
+                }
+                b'"' | b'\'' => {
+                    let delim = if b == b'"' { "\"\"\"" } else { "'''" };
+                    let e = if text[i..].starts_with(delim) {
+                        text[i + 3..].find(delim).map_or(end, |p| i + 3 + p + 3)
+                    } else {
+                        let line_end = text[i..].find('\n').map_or(end, |p| i + p);
+                        quoted_end(text, i, line_end, b == b'"')
+                    };
+                    out.push(i..e, key_or(expect_key, TokenKind::String));
+                    i = e;
+                    line_start = false;
+                }
+                b'=' => {
+                    expect_key = false;
+                    line_start = false;
+                    i += 1;
+                }
+                b'{' => {
+                    depth_inline += 1;
+                    expect_key = true;
+                    i += 1;
+                }
+                b'}' => {
+                    depth_inline = depth_inline.saturating_sub(1);
+                    i += 1;
+                }
+                b',' => {
+                    expect_key = depth_inline > 0;
+                    i += 1;
+                }
+                b'[' => {
+                    depth_array += 1;
+                    i += 1;
+                }
+                b']' => {
+                    depth_array = depth_array.saturating_sub(1);
+                    i += 1;
+                }
+                b'.' => i += 1,
+                _ => {
+                    // 裸 key / 数字 / 日期 / 布尔：到空白或分隔符为止
+                    let e = scan_while(text, i, end, |c| !is_word_end(c, expect_key));
+                    let e = e.max(i + text[i..].chars().next().map_or(1, char::len_utf8));
+                    let kind = if expect_key {
+                        Some(TokenKind::Key)
+                    } else {
+                        scalar_kind(&text[i..e]).or_else(|| {
+                            // 日期时间：1979-05-27T07:32:00Z
+                            bytes[i].is_ascii_digit().then_some(TokenKind::Number)
+                        })
+                    };
+                    if let Some(k) = kind {
+                        out.push(i..e, k);
+                    }
+                    i = e;
+                    line_start = false;
+                }
+            }
+        }
+        out.0
+    }
+}
+
+/// 裸词的结束字节；key 里的 `.` 是分隔符（`a.b = 1`），值里的不是（`3.14`）
+fn is_word_end(c: u8, in_key: bool) -> bool {
+    matches!(
+        c,
+        b' ' | b'\t' | b'\r' | b'\n' | b',' | b']' | b'}' | b'=' | b'#'
+    ) || (in_key && c == b'.')
+}
+
+fn key_or(expect_key: bool, other: TokenKind) -> TokenKind {
+    if expect_key {
+        TokenKind::Key
+    } else {
+        other
+    }
+}
@@ -0,0 +1,93 @@
+// src/component/util/highlight/xml.rs
+//
+// XML / HTML 分词：注释、CDATA、处理指令与 DOCTYPE、标签（名 / 属性名 / 属性值）、实体引用。
+// 标签外的文本不着色。
+
+use super::{quoted_end, scan_while, Token, TokenKind, Tokenizer, Tokens};
+
+pub struct XmlTokenizer;
+
+impl Tokenizer for XmlTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let bytes = text.as_bytes();
+        let end = text.len();
+        let mut out = Tokens::default();
+        let mut i = 0;
+        while i < end {
+            let rest = &text[i..];
+            if let Some(body) = rest.strip_prefix("<!--") {
+                let e = body.find("-->").map_or(end, |p| i + 4 + p + 3);
+                out.push(i..e, TokenKind::Comment);
+                i = e;
+            } else if rest.starts_with("<![CDATA[") {
+                let e = rest.find("]]>").map_or(end, |p| i + p + 3);
+                out.push(i..e, TokenKind::String);
+                i = e;
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                let close = if rest.starts_with("<?") { "?>" } else { ">" };
+                let e = rest[2..]
+                    .find(close)
+                    .map_or(end, |p| i + 2 + p + close.len());
+                out.push(i..e, TokenKind::Keyword);
+                i = e;
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c == '/' || c.is_alphabetic() || c == '_')
+            {
+                i = tag(text, i, &mut out);
+            } else if bytes[i] == b'&' {
+                let e = scan_while(text, i + 1, end, |c| c.is_ascii_alphanumeric() || c == b'#');
+                if bytes.get(e) == Some(&b';') {
+                    out.push(i..e + 1, TokenKind::Keyword);
+                    i = e + 1;
+                } else {
+                    i += 1;
+                }
+            } else {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        out.0
+    }
+}
+
+/// 从 `<` 起的一个标签，返回其结束位置（`>` 之后）
+fn tag(text: &str, start: usize, out: &mut Tokens) -> usize {
+    let bytes = text.as_bytes();
+    let end = text.len();
+    let name_start = if bytes.get(start + 1) == Some(&b'/') {
+        start + 2
+    } else {
+        start + 1
+    };
+    let mut i = scan_while(text, name_start, end, is_name_byte);
+    out.push(start..i, TokenKind::Tag);
+    while i < end {
+        match bytes[i] {
+            b'>' => {
+                out.push(i..i + 1, TokenKind::Tag);
+                return i + 1;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                out.push(i..i + 2, TokenKind::Tag);
+                return i + 2;
+            }
+            b'"' | b'\'' => {
+                let e = quoted_end(text, i, end, false);
+                out.push(i..e, TokenKind::String);
+                i = e;
+            }
+            b if is_name_byte(b) => {
+                let e = scan_while(text, i, end, is_name_byte);
+                out.push(i..e, TokenKind::Attr);
+                i = e;
+            }
+            _ => i += 1,
+        }
+    }
+    end
+}
+
+/// 标签名 / 属性名可含的字节（非 ASCII 一律视为名字的一部分）
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b':' | b'.') || b >= 0x80
+}
@@ -0,0 +1,159 @@
+// src/component/util/highlight/yaml.rs
+//
+// YAML 分词（逐行）：注释、文档标记、`key:`、列表项、锚点 / 别名 / 标签、
+// 引号字符串、流式集合里的标量，以及 `|` / `>` 块标量的后续缩进行。
+
+use super::{quoted_end, scalar_kind, Token, TokenKind, Tokenizer, Tokens};
+
+pub struct YamlTokenizer;
+
+impl Tokenizer for YamlTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut out = Tokens::default();
+        // 块标量所属行的缩进；更深的行（以及空行）都是块内容
+        let mut block: Option<usize> = None;
+        let mut base = 0;
+        for raw in text.split_inclusive('\n') {
+            let line = raw.trim_end_matches(['\n', '\r']);
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            let blank = line.trim().is_empty();
+            if let Some(b) = block {
+                if blank || indent > b {
+                    out.push(base + indent..base + line.len(), TokenKind::String);
+                    base += raw.len();
+                    continue;
+                }
+                block = None;
+            }
+            if tokenize_line(line, base, &mut out) {
+                block = Some(indent);
+            }
+            base += raw.len();
+        }
+        out.0
+    }
+}
+
+/// 返回该行是否以块标量指示符（`|`、`>-` 等）结尾
+fn tokenize_line(line: &str, base: usize, out: &mut Tokens) -> bool {
+    let end = line.len();
+    let mut i = line.len() - line.trim_start().len();
+    let rest = &line[i..];
+    if rest.starts_with('#') {
+        out.push(base + i..base + end, TokenKind::Comment);
+        return false;
+    }
+    if rest.starts_with("---") || rest.starts_with("...") {
+        out.push(base + i..base + i + 3, TokenKind::Keyword);
+        i += 3;
+    }
+    // 列表项前缀
+    while line[i..].starts_with("- ") || &line[i..] == "-" {
+        i = (i + 2).min(end);
+        i += line[i..].len() - line[i..].trim_start().len();
+    }
+    // key：引号 key 或到 `: ` / 行尾 `:` 为止的普通 key
+    let key_end = if line[i..].starts_with(['"', '\'']) {
+        let q = quoted_end(line, i, end, line.as_bytes()[i] == b'"');
+        line[q..].trim_start().starts_with(':').then_some(q)
+    } else {
+        find_key_colon(line, i)
+    };
+    if let Some(k) = key_end {
+        out.push(base + i..base + k, TokenKind::Key);
+        i = k + line[k..].find(':').map_or(0, |c| c + 1);
+    }
+    value(line, i, base, out)
+}
+
+/// 普通 key 的结束位置（冒号之前），冒号后须为空白或行尾；遇到注释 / 流式集合则不是 key
+fn find_key_colon(line: &str, start: usize) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'#' if i > start && bytes[i - 1] == b' ' => return None,
+            b'[' | b'{' if i == start => return None,
+            b':' if bytes.get(i + 1).is_none_or(|b| *b == b' ' || *b == b'\t') => {
+                return (i > start).then(|| line[..i].trim_end().len());
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// 值部分；返回是否为块标量指示符
+fn value(line: &str, mut i: usize, base: usize, out: &mut Tokens) -> bool {
+    let bytes = line.as_bytes();
+    let end = line.len();
+    loop {
+        i += line[i..].len() - line[i..].trim_start().len();
+        if i >= end {
+            return false;
+        }
+        match bytes[i] {
+            b'#' => {
+                out.push(base + i..base + end, TokenKind::Comment);
+                return false;
+            }
+            b'"' | b'\'' => {
+                let q = quoted_end(line, i, end, bytes[i] == b'"');
+                out.push(base + i..base + q, TokenKind::String);
+                i = q;
+            }
+            // 锚点 / 别名 / 标签
+            b'&' | b'*' | b'!' => {
+                let w = line[i..].find([' ', ',', ']', '}']).map_or(end, |p| i + p);
+                out.push(base + i..base + w, TokenKind::Keyword);
+                i = w;
+            }
+            b'|' | b'>' if block_indicator(&line[i..]).is_some() => {
+                let w = i + block_indicator(&line[i..]).unwrap_or(1);
+                out.push(base + i..base + w, TokenKind::Keyword);
+                value(line, w, base, out);
+                return true;
+            }
+            // 流式集合：逐个标量着色，`key:` 着 key 色
+            b'[' | b']' | b'{' | b'}' | b',' => i += 1,
+            _ => {
+                let in_flow = line[..i].contains(['[', '{']);
+                let stop = scalar_end(line, i, in_flow);
+                let word = &line[i..stop];
+                if in_flow && line[stop..].starts_with(':') {
+                    out.push(base + i..base + stop, TokenKind::Key);
+                    i = stop + 1;
+                    continue;
+                }
+                let kind = scalar_kind(word).unwrap_or(TokenKind::String);
+                out.push(base + i..base + i + word.trim_end().len(), kind);
+                i = stop;
+            }
+        }
+    }
+}
+
+/// 普通标量的结束位置：注释、流式分隔符（仅在流式集合内）或行尾
+fn scalar_end(line: &str, start: usize, in_flow: bool) -> usize {
+    let bytes = line.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'#' if i > start && bytes[i - 1] == b' ' => return i,
+            b',' | b']' | b'}' if in_flow => return i,
+            b':' if in_flow && bytes.get(i + 1).is_none_or(|b| *b == b' ') => return i,
+            _ => i += 1,
+        }
+    }
+    i
+}
+
+/// `|`、`>-`、`|2+` 之类的块标量指示符（其后只能是空白或注释），返回其长度
+fn block_indicator(s: &str) -> Option<usize> {
+    let len = 1 + s[1..]
+        .bytes()
+        .take_while(|b| matches!(b, b'-' | b'+' | b'1'..=b'9'))
+        .count();
+    let after = s[len..].trim_start();
+    (after.is_empty() || after.starts_with('#')).then_some(len)
+}
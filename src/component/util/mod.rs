@@ -1,2 +1,3 @@
 pub mod highlight;
+pub mod offset;
 pub mod search;
@@ -0,0 +1,181 @@
+// src/component/util/offset.rs
+//
+// 文本位置换算。同一处位置有三种计法：
+// - Rust 字符串切片用 UTF-8 字节偏移；
+// - JS（textarea.setSelectionRange、String.slice）用 UTF-16 码元，emoji 等非 BMP 字符占 2 个；
+// - 用户眼中的“一个字”是扩展字素簇：emoji ZWJ 序列（👨‍👩‍👧）、字母加组合符（é = e + U+0301）都只算一个。
+//
+// 查找匹配是字节区间，可能落在字素簇中间（查 `e` 命中 `é` 的前半）。高亮与选中前先扩到簇边界，
+// 避免把一个字拆成两段渲染或只选中半个；再按需换算成 UTF-16 交给 JS。
+
+use std::ops::Range;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+
+/// 向前取到字符边界（越界时取文本末尾）
+fn floor_char(text: &str, byte: usize) -> usize {
+    let mut b = byte.min(text.len());
+    while !text.is_char_boundary(b) {
+        b -= 1;
+    }
+    b
+}
+
+/// 向后取到字符边界
+fn ceil_char(text: &str, byte: usize) -> usize {
+    let mut b = byte.min(text.len());
+    while !text.is_char_boundary(b) {
+        b += 1;
+    }
+    b
+}
+
+/// 字节偏移 -> UTF-16 码元偏移；落在字符中间时按该字符起点算
+pub fn byte_to_utf16(text: &str, byte: usize) -> usize {
+    text[..floor_char(text, byte)].encode_utf16().count()
+}
+
+/// UTF-16 码元偏移 -> 字节偏移；落在代理对中间时按该字符起点算，越界时取文本末尾。
+/// 用于把 JS 读到的光标 / 选区位置换回字符串切片位置
+#[allow(dead_code)]
+pub fn utf16_to_byte(text: &str, unit: usize) -> usize {
+    let mut units = 0;
+    for (i, c) in text.char_indices() {
+        if units + c.len_utf16() > unit {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+/// 包含 `byte` 的字素簇的起点
+pub fn floor_grapheme(text: &str, byte: usize) -> usize {
+    let byte = floor_char(text, byte);
+    let mut cursor = GraphemeCursor::new(byte, text.len(), true);
+    if cursor.is_boundary(text, 0).unwrap_or(true) {
+        return byte;
+    }
+    cursor.prev_boundary(text, 0).ok().flatten().unwrap_or(0)
+}
+
+/// 不小于 `byte` 的第一个字素簇边界
+pub fn ceil_grapheme(text: &str, byte: usize) -> usize {
+    let byte = ceil_char(text, byte);
+    let mut cursor = GraphemeCursor::new(byte, text.len(), true);
+    if cursor.is_boundary(text, 0).unwrap_or(true) {
+        return byte;
+    }
+    cursor
+        .next_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(text.len())
+}
+
+/// 字节偏移 -> 第几个字素簇（落在簇中间时按该簇算）
+pub fn byte_to_grapheme(text: &str, byte: usize) -> usize {
+    text[..floor_grapheme(text, byte)].graphemes(true).count()
+}
+
+/// 第几个字素簇 -> 其起点的字节偏移；越界时取文本末尾
+#[allow(dead_code)]
+pub fn grapheme_to_byte(text: &str, index: usize) -> usize {
+    text.grapheme_indices(true)
+        .nth(index)
+        .map_or(text.len(), |(i, _)| i)
+}
+
+/// 把字节区间向两端扩到字素簇边界
+pub fn snap_to_graphemes(text: &str, range: Range<usize>) -> Range<usize> {
+    floor_grapheme(text, range.start)..ceil_grapheme(text, range.end)
+}
+
+/// 字节区间 -> 供 JS 使用的 UTF-16 区间（先扩到字素簇边界）
+pub fn utf16_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let r = snap_to_graphemes(text, range);
+    byte_to_utf16(text, r.start)..byte_to_utf16(text, r.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 👨‍👩‍👧：三个非 BMP 字符由两个 ZWJ 连接，18 字节、8 个 UTF-16 码元、1 个字素簇
+    const FAMILY: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    // é 的分解形式：e + 组合锐音符
+    const E_ACUTE: &str = "e\u{301}";
+
+    #[test]
+    fn ascii_offsets_are_identical() {
+        let t = "hello";
+        for i in 0..=t.len() {
+            assert_eq!(byte_to_utf16(t, i), i);
+            assert_eq!(utf16_to_byte(t, i), i);
+            assert_eq!(byte_to_grapheme(t, i), i);
+            assert_eq!(grapheme_to_byte(t, i), i);
+        }
+    }
+
+    #[test]
+    fn cjk_is_three_bytes_one_unit() {
+        let t = "a中文b";
+        assert_eq!(byte_to_utf16(t, 1), 1);
+        assert_eq!(byte_to_utf16(t, 4), 2);
+        assert_eq!(byte_to_utf16(t, 7), 3);
+        assert_eq!(utf16_to_byte(t, 3), 7);
+        assert_eq!(byte_to_grapheme(t, 7), 3);
+        assert_eq!(grapheme_to_byte(t, 2), 4);
+        // 字符中间的字节按字符起点算
+        assert_eq!(byte_to_utf16(t, 2), 1);
+        assert_eq!(floor_grapheme(t, 5), 4);
+        assert_eq!(ceil_grapheme(t, 5), 7);
+    }
+
+    #[test]
+    fn emoji_zwj_sequence_is_one_grapheme() {
+        let t = format!("x{FAMILY}y");
+        let end = 1 + FAMILY.len();
+        assert_eq!(FAMILY.len(), 18);
+        assert_eq!(byte_to_utf16(&t, end), 9);
+        assert_eq!(utf16_to_byte(&t, 9), end);
+        assert_eq!(byte_to_grapheme(&t, end), 2);
+        assert_eq!(grapheme_to_byte(&t, 2), end);
+        // 簇内任意位置都归到整个簇
+        for b in 2..end {
+            assert_eq!(byte_to_grapheme(&t, b), 1);
+            assert_eq!(floor_grapheme(&t, b), 1);
+            assert_eq!(ceil_grapheme(&t, b), end);
+        }
+        // 代理对中间的 UTF-16 偏移按字符起点算
+        assert_eq!(utf16_to_byte(&t, 2), 1);
+    }
+
+    #[test]
+    fn combining_mark_stays_with_base() {
+        let t = format!("caf{E_ACUTE}!");
+        // 查 `e` 命中 é 的前半，扩到整个簇
+        assert_eq!(snap_to_graphemes(&t, 3..4), 3..6);
+        assert_eq!(utf16_range(&t, 3..4), 3..5);
+        assert_eq!(byte_to_grapheme(&t, 6), 4);
+        assert_eq!(grapheme_to_byte(&t, 4), 6);
+        assert_eq!(byte_to_utf16(&t, t.len()), 6);
+    }
+
+    #[test]
+    fn selection_range_covers_whole_emoji() {
+        let t = format!("{FAMILY} ok");
+        // 只匹配到第一个 👨 时也选中整个家庭 emoji
+        assert_eq!(utf16_range(&t, 0..4), 0..8);
+        assert_eq!(utf16_range(&t, 19..21), 9..11);
+    }
+
+    #[test]
+    fn out_of_range_is_clamped() {
+        let t = "中";
+        assert_eq!(byte_to_utf16(t, 99), 1);
+        assert_eq!(utf16_to_byte(t, 99), 3);
+        assert_eq!(grapheme_to_byte(t, 99), 3);
+        assert_eq!(ceil_grapheme(t, 99), 3);
+        assert_eq!(byte_to_utf16("", 0), 0);
+    }
+}
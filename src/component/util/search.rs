@@ -1,4 +1,173 @@
 use dioxus::prelude::*;
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+// ============== 查找引擎 ==============
+//
+// 所有嵌入 SearchBar 的工具共用：匹配结果是字节区间（不重叠、按位置升序），
+// 正则模式下各处匹配长度不同，高亮与替换都以区间为准。
+
+/// 查找条上的三个开关
+#[derive(Clone, Copy, PartialEq)]
+pub struct SearchOptions {
+    /// 区分大小写
+    pub match_case: bool,
+    /// 全词匹配：匹配两侧不能紧挨字母、数字或下划线
+    pub whole_word: bool,
+    /// 正则表达式（替换中可用 `$1` / `${name}` 引用捕获组）
+    pub regex: bool,
+}
+
+/// 默认区分大小写，与加开关之前的纯文本查找一致
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            match_case: true,
+            whole_word: false,
+            regex: false,
+        }
+    }
+}
+
+/// 编译好的查找条件。非正则模式也转成转义后的正则，两种模式走同一套逻辑。
+pub struct Matcher {
+    re: Regex,
+    whole_word: bool,
+    expand: bool,
+}
+
+impl Matcher {
+    /// 空查询返回 `Ok(None)`；正则无效时返回简短的错误说明
+    pub fn new(query: &str, opts: SearchOptions) -> Result<Option<Self>, String> {
+        if query.is_empty() {
+            return Ok(None);
+        }
+        let pattern = if opts.regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        let re = RegexBuilder::new(&pattern)
+            .case_insensitive(!opts.match_case)
+            .multi_line(true)
+            .build()
+            .map_err(|e| regex_error(&e))?;
+        Ok(Some(Self {
+            re,
+            whole_word: opts.whole_word,
+            expand: opts.regex,
+        }))
+    }
+
+    /// 所有不重叠的非空匹配
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        let mut out = Vec::new();
+        let mut at = 0;
+        while let Some(m) = self.find_at(text, at) {
+            at = m.end;
+            out.push(m);
+        }
+        out
+    }
+
+    /// 文本中是否有匹配
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find_at(text, 0).is_some()
+    }
+
+    /// 从 `at` 起的第一个非空匹配
+    fn find_at(&self, text: &str, mut at: usize) -> Option<Range<usize>> {
+        while at <= text.len() {
+            let m = self.re.find_at(text, at)?;
+            if m.start() == m.end() || (self.whole_word && !is_word_bounded(text, m.range())) {
+                // 空匹配（如 `a*`）不计；全词不满足时从下一个字符重试
+                at = m.start() + text[m.start()..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            }
+            return Some(m.range());
+        }
+        None
+    }
+
+    /// `m` 处匹配的替换文本；正则模式下展开捕获组引用，否则原样使用
+    pub fn replacement(&self, text: &str, m: Range<usize>, rep: &str) -> String {
+        if !self.expand {
+            return rep.to_string();
+        }
+        let mut dst = String::new();
+        if let Some(caps) = self.re.captures_at(text, m.start) {
+            caps.expand(rep, &mut dst);
+        }
+        dst
+    }
+
+    /// 替换全部匹配
+    pub fn replace_all(&self, text: &str, rep: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in self.find_all(text) {
+            out.push_str(&text[last..m.start]);
+            out.push_str(&self.replacement(text, m.clone(), rep));
+            last = m.end;
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+}
+
+/// 查找全部匹配；空查询或正则无效时为空
+pub fn find_matches(text: &str, query: &str, opts: SearchOptions) -> Vec<Range<usize>> {
+    match Matcher::new(query, opts) {
+        Ok(Some(m)) => m.find_all(text),
+        _ => Vec::new(),
+    }
+}
+
+/// 替换 `m` 处的一个匹配，返回新文本
+pub fn replace_match(
+    text: &str,
+    query: &str,
+    opts: SearchOptions,
+    m: Range<usize>,
+    rep: &str,
+) -> Option<String> {
+    let matcher = Matcher::new(query, opts).ok()??;
+    let mut out = text.to_string();
+    let with = matcher.replacement(text, m.clone(), rep);
+    out.replace_range(m, &with);
+    Some(out)
+}
+
+/// 替换全部匹配；空查询或正则无效时返回 None
+pub fn replace_all_matches(
+    text: &str,
+    query: &str,
+    opts: SearchOptions,
+    rep: &str,
+) -> Option<String> {
+    let matcher = Matcher::new(query, opts).ok()??;
+    Some(matcher.replace_all(text, rep))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_word_bounded(text: &str, m: Range<usize>) -> bool {
+    let before = text[..m.start].chars().next_back();
+    let after = text[m.end..].chars().next();
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+}
+
+/// regex 的错误信息是带示意箭头的多行文本，查找条里只取最后一行说明
+fn regex_error(e: &regex::Error) -> String {
+    let msg = e.to_string();
+    let line = msg.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let line = line.trim().trim_start_matches("error:").trim();
+    format!("正则无效：{}", line)
+}
+
+// ============== 查找条 ==============
 
 #[derive(Props, PartialEq, Clone)]
 pub struct SearchBarProps {
@@ -11,6 +180,8 @@ pub struct SearchBarProps {
     pub query: Signal<String>,
     /// 替换关键字
     pub replace: Signal<String>,
+    /// 大小写 / 全词 / 正则开关；切换后由各工具按新条件重算匹配
+    pub options: Signal<SearchOptions>,
 
     /// 上/下一处、关闭
     pub on_prev: EventHandler<()>,
@@ -20,7 +191,7 @@ pub struct SearchBarProps {
     /// 查找输入变化时触发
     pub on_query_input: EventHandler<String>,
 
-    /// 替换当前 / 全部替换
+    /// 替换当前 / 全部替换（可替换时 on_replace_one 为 None 则不显示“替换”按钮）
     pub on_replace_one: Option<EventHandler<()>>,
     pub on_replace_all: Option<EventHandler<()>>,
 
@@ -53,10 +224,26 @@ pub fn SearchBar(props: SearchBarProps) -> Element {
         format!("{}/{}", cur + 1, total)
     };
     let count_color = if total == 0 { "#6a6a6a" } else { "var(--text-dim)" };
+    // 正则无效时在查找行下方提示
+    let opts = *props.options.read();
+    let error = Matcher::new(&props.query.read(), opts).err();
+    let input_class = if error.is_some() {
+        "tb-search-input invalid"
+    } else {
+        "tb-search-input"
+    };
+    let toggle_class = |on: bool| {
+        if on {
+            "tb-search-toggle on"
+        } else {
+            "tb-search-toggle"
+        }
+    };
 
     // 预先克隆必要的信号/事件，避免在闭包内多次 borrow
     let mut show_replace_sig = props.show_replace;
     let mut replace_sig = props.replace;
+    let mut options_sig = props.options;
     let on_replace_one = props.on_replace_one;
     let on_replace_all = props.on_replace_all;
 
@@ -80,7 +267,7 @@ pub fn SearchBar(props: SearchBarProps) -> Element {
                 }
 
                 input {
-                    class: "tb-search-input",
+                    class: input_class,
                     value: "{props.query}",
                     placeholder: "查找",
                     oninput: move |e| props.on_query_input.call(e.value().clone()),
@@ -95,6 +282,26 @@ pub fn SearchBar(props: SearchBarProps) -> Element {
                     }
                 }
 
+                // 大小写 / 全词 / 正则
+                button {
+                    class: toggle_class(opts.match_case),
+                    title: "区分大小写",
+                    onclick: move |_| options_sig.with_mut(|o| o.match_case = !o.match_case),
+                    "Aa"
+                }
+                button {
+                    class: toggle_class(opts.whole_word),
+                    title: "全词匹配",
+                    onclick: move |_| options_sig.with_mut(|o| o.whole_word = !o.whole_word),
+                    "ab"
+                }
+                button {
+                    class: toggle_class(opts.regex),
+                    title: "使用正则表达式（替换中用 $1 引用捕获组）",
+                    onclick: move |_| options_sig.with_mut(|o| o.regex = !o.regex),
+                    ".*"
+                }
+
                 span {
                     class: "tb-count",
                     style: "color:{count_color};",
@@ -121,6 +328,10 @@ pub fn SearchBar(props: SearchBarProps) -> Element {
                 }
             }
 
+            if let Some(err) = error {
+                div { class: "tb-search-error", "{err}" }
+            }
+
             // 替换行
             if *props.show_replace.read() {
                 div {
@@ -140,17 +351,19 @@ pub fn SearchBar(props: SearchBarProps) -> Element {
                     }
 
                     // 替换当前
-                    button {
-                        class: "tb-btn",
-                        disabled: "{disabled}",
-                        onclick: move |_| {
-                            if !disabled {
-                                if let Some(h) = on_replace_one.as_ref() {
-                                    h.call(());
+                    if disabled || on_replace_one.is_some() {
+                        button {
+                            class: "tb-btn",
+                            disabled: "{disabled}",
+                            onclick: move |_| {
+                                if !disabled {
+                                    if let Some(h) = on_replace_one.as_ref() {
+                                        h.call(());
+                                    }
                                 }
-                            }
-                        },
-                        "替换"
+                            },
+                            "替换"
+                        }
                     }
 
                     // 全部替换
@@ -171,3 +384,20 @@ pub fn SearchBar(props: SearchBarProps) -> Element {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_case_sensitive() {
+        let opts = SearchOptions::default();
+        let found = find_matches("Key key KEY", "key", opts);
+        assert_eq!((found.len(), found[0].clone()), (1, 4..7));
+        let opts = SearchOptions {
+            match_case: false,
+            ..opts
+        };
+        assert_eq!(find_matches("Key key KEY", "key", opts).len(), 3);
+    }
+}